@@ -8,15 +8,20 @@ pub use self::{
     types::*,
 };
 
+use chashmap::CHashMap;
 use futures::{channel::mpsc, prelude::*};
 use log::error;
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
 #[derive(Debug)]
 pub struct MessageHandler<S, C> {
     pub server: Arc<S>,
     pub client: Arc<C>,
     pub output: mpsc::Sender<String>,
+    pending_requests: Arc<CHashMap<Id, Arc<AtomicBool>>>,
 }
 
 impl<S, C> MessageHandler<S, C>
@@ -24,6 +29,15 @@ where
     S: RequestHandler + Middleware + Send + Sync + 'static,
     C: ResponseHandler + Send + Sync + 'static,
 {
+    pub fn new(server: Arc<S>, client: Arc<C>, output: mpsc::Sender<String>) -> Self {
+        Self {
+            server,
+            client,
+            output,
+            pending_requests: Arc::default(),
+        }
+    }
+
     pub async fn handle(&mut self, json: &str) {
         self.server.before_message().await;
 
@@ -31,8 +45,16 @@ where
             Ok(Message::Request(request)) => {
                 let server = Arc::clone(&self.server);
                 let mut output = self.output.clone();
+                let pending_requests = Arc::clone(&self.pending_requests);
+                let cancelled = Arc::new(AtomicBool::new(false));
+                pending_requests.insert(request.id.clone(), Arc::clone(&cancelled));
                 tokio::spawn(async move {
-                    let response = server.handle_request(request).await;
+                    let id = request.id.clone();
+                    let mut response = server.handle_request(request, Arc::clone(&cancelled)).await;
+                    pending_requests.remove(&id);
+                    if cancelled.load(Ordering::SeqCst) {
+                        response = Response::error(Error::request_cancelled(), Some(id));
+                    }
                     if let Some(error) = response.error.as_ref() {
                         error!("{:?}", error);
                     }
@@ -41,6 +63,12 @@ where
                     server.after_message().await;
                 });
             }
+            Ok(Message::Notification(notification))
+                if notification.method == CANCEL_REQUEST_METHOD =>
+            {
+                self.cancel(notification);
+                self.after_message();
+            }
             Ok(Message::Notification(notification)) => {
                 self.server.handle_notification(notification).await;
                 self.after_message();
@@ -58,6 +86,17 @@ where
         };
     }
 
+    fn cancel(&self, notification: Notification) {
+        match serde_json::from_value::<CancelParams>(notification.params) {
+            Ok(params) => {
+                if let Some(cancelled) = self.pending_requests.get(&params.id) {
+                    cancelled.store(true, Ordering::SeqCst);
+                }
+            }
+            Err(_) => error!("{:?}", Error::deserialize_error()),
+        }
+    }
+
     fn after_message(&self) {
         let server = Arc::clone(&self.server);
         tokio::spawn(async move {
@@ -65,3 +104,60 @@ where
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    struct BlockingServer;
+
+    #[async_trait]
+    impl RequestHandler for BlockingServer {
+        async fn handle_request(&self, request: Request, cancelled: Arc<AtomicBool>) -> Response {
+            while !cancelled.load(Ordering::SeqCst) {
+                tokio::task::yield_now().await;
+            }
+            Response::result(json!(null), request.id)
+        }
+
+        async fn handle_notification(&self, _notification: Notification) {}
+    }
+
+    #[async_trait]
+    impl Middleware for BlockingServer {
+        async fn before_message(&self) {}
+
+        async fn after_message(&self) {}
+    }
+
+    struct NullClient;
+
+    #[async_trait]
+    impl ResponseHandler for NullClient {
+        async fn handle(&self, _response: Response) {}
+    }
+
+    #[tokio::test]
+    async fn cancel_request() {
+        let (output_tx, mut output_rx) = mpsc::channel(0);
+        let mut handler =
+            MessageHandler::new(Arc::new(BlockingServer), Arc::new(NullClient), output_tx);
+
+        let request = Request::new("foo".into(), json!(null), Id::Number(1));
+        handler
+            .handle(&serde_json::to_string(&request).unwrap())
+            .await;
+
+        let params = json!(CancelParams { id: Id::Number(1) });
+        let notification = Notification::new(CANCEL_REQUEST_METHOD.into(), params);
+        handler
+            .handle(&serde_json::to_string(&notification).unwrap())
+            .await;
+
+        let response: Response = serde_json::from_str(&output_rx.next().await.unwrap()).unwrap();
+        assert_eq!(response.id, Some(Id::Number(1)));
+        assert_eq!(response.error.unwrap().code, ErrorCode::RequestCancelled);
+    }
+}
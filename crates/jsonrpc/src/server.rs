@@ -3,12 +3,13 @@ use async_trait::async_trait;
 use futures::prelude::*;
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::json;
+use std::sync::{atomic::AtomicBool, Arc};
 
 pub type Result<T> = std::result::Result<T, String>;
 
 #[async_trait]
 pub trait RequestHandler {
-    async fn handle_request(&self, request: Request) -> Response;
+    async fn handle_request(&self, request: Request, cancelled: Arc<AtomicBool>) -> Response;
 
     async fn handle_notification(&self, notification: Notification);
 }
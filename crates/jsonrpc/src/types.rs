@@ -3,6 +3,8 @@ use serde_repr::*;
 
 pub const PROTOCOL_VERSION: &str = "2.0";
 
+pub const CANCEL_REQUEST_METHOD: &str = "$/cancelRequest";
+
 #[derive(Debug, Eq, Hash, PartialEq, Clone, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum Id {
@@ -64,6 +66,14 @@ impl Error {
             data: None,
         }
     }
+
+    pub fn request_cancelled() -> Self {
+        Self {
+            code: ErrorCode::RequestCancelled,
+            message: "Request cancelled".to_owned(),
+            data: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -135,6 +145,11 @@ impl Notification {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CancelParams {
+    pub id: Id,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum Message {
@@ -67,7 +67,12 @@ pub fn jsonrpc_server(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
         #[async_trait::async_trait]
         impl #generics jsonrpc::RequestHandler for #self_ty {
-            async fn handle_request(&self, request: jsonrpc::Request) -> jsonrpc::Response {
+            #[allow(unused_variables)]
+            async fn handle_request(
+                &self,
+                request: jsonrpc::Request,
+                cancelled: std::sync::Arc<std::sync::atomic::AtomicBool>,
+            ) -> jsonrpc::Response {
                 use jsonrpc::*;
 
                 match request.method.as_str() {
@@ -148,6 +153,20 @@ fn generate_server_skeletons(items: &Vec<ImplItem>) -> (Vec<TokenStream2>, Vec<T
         let name = &meta.name.as_str();
 
         match meta.kind {
+            MethodKind::Request if has_cancellation_param(&method.sig) => {
+                requests.push(quote!(
+                    #name => {
+                        let handler = |param: #param_ty| {
+                            let cancelled = std::sync::Arc::clone(&cancelled);
+                            async move {
+                                self.#ident(param, cancelled).await
+                            }
+                        };
+
+                        jsonrpc::handle_request(request, handler).await
+                    }
+                ));
+            }
             MethodKind::Request => {
                 requests.push(quote!(
                     #name => {
@@ -176,6 +195,36 @@ fn generate_server_skeletons(items: &Vec<ImplItem>) -> (Vec<TokenStream2>, Vec<T
     (requests, notifications)
 }
 
+fn has_cancellation_param(sig: &Signature) -> bool {
+    sig.inputs.iter().skip(2).any(|input| match input {
+        FnArg::Typed(x) => is_atomic_bool_arc(&x.ty),
+        FnArg::Receiver(_) => false,
+    })
+}
+
+fn is_atomic_bool_arc(ty: &Type) -> bool {
+    let segment = match ty {
+        Type::Path(x) => match x.path.segments.last() {
+            Some(segment) if segment.ident == "Arc" => segment,
+            _ => return false,
+        },
+        _ => return false,
+    };
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(x) => x.args.iter().any(|arg| match arg {
+            GenericArgument::Type(Type::Path(x)) => x
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident == "AtomicBool")
+                .unwrap_or(false),
+            _ => false,
+        }),
+        _ => false,
+    }
+}
+
 fn generate_client_stubs(items: &Vec<TraitItem>) -> Vec<TokenStream2> {
     let mut stubs = Vec::new();
     for item in items {
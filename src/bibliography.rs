@@ -0,0 +1,172 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{Location, Range, RangeExt, TextDocumentPositionParams},
+    syntax::{LatexIncludeKind, SyntaxNode},
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct BibliographyProvider;
+
+impl BibliographyProvider {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for BibliographyProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Vec<Location>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut locations = Vec::new();
+        let table = match &req.current().content {
+            DocumentContent::Latex(table) => table,
+            DocumentContent::Bibtex(_) => return locations,
+        };
+
+        let pos = req.params.position;
+        if !table
+            .citations
+            .iter()
+            .any(|citation| table[citation.parent].range().contains(pos))
+        {
+            return locations;
+        }
+
+        for doc in req.related() {
+            if let DocumentContent::Latex(table) = &doc.content {
+                for include in table
+                    .includes
+                    .iter()
+                    .filter(|include| include.kind == LatexIncludeKind::Bibliography)
+                {
+                    let paths = include.paths(&table);
+                    for (path, targets) in paths.iter().zip(&include.all_targets) {
+                        let location = match req.snapshot().find_target(targets) {
+                            Some(bib_doc) => Location::new(
+                                bib_doc.uri.clone().into(),
+                                Range::new_simple(0, 0, 0, 0),
+                            ),
+                            None => Location::new(doc.uri.clone().into(), path.range()),
+                        };
+                        locations.push(location);
+                    }
+                }
+            }
+        }
+        locations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feature::FeatureTester;
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let actual_locations = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_position(BibliographyProvider)
+            .await;
+
+        assert!(actual_locations.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let actual_locations = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .position(0, 0)
+            .test_position(BibliographyProvider)
+            .await;
+
+        assert!(actual_locations.is_empty());
+    }
+
+    #[tokio::test]
+    async fn multiple_resources() {
+        let actual_locations = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \addbibresource{bar.bib}
+                        \addbibresource{baz.bib}
+                        \cite{foo}
+                    "#
+                ),
+            )
+            .file("bar.bib", "")
+            .main("foo.tex")
+            .position(2, 7)
+            .test_position(BibliographyProvider)
+            .await;
+
+        let expected_locations = vec![
+            Location::new(
+                FeatureTester::uri("bar.bib").into(),
+                Range::new_simple(0, 0, 0, 0),
+            ),
+            Location::new(
+                FeatureTester::uri("foo.tex").into(),
+                Range::new_simple(1, 16, 1, 23),
+            ),
+        ];
+        assert_eq!(actual_locations, expected_locations);
+    }
+
+    #[tokio::test]
+    async fn included_from_parent() {
+        let actual_locations = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \bibliography{baz}
+                        \include{bar}
+                    "#
+                ),
+            )
+            .file("bar.tex", r#"\cite{foo}"#)
+            .file("baz.bib", "")
+            .main("bar.tex")
+            .position(0, 7)
+            .test_position(BibliographyProvider)
+            .await;
+
+        let expected_locations = vec![Location::new(
+            FeatureTester::uri("baz.bib").into(),
+            Range::new_simple(0, 0, 0, 0),
+        )];
+        assert_eq!(actual_locations, expected_locations);
+    }
+
+    #[tokio::test]
+    async fn outside_of_citation() {
+        let actual_locations = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \addbibresource{bar.bib}
+                        \cite{foo}
+                    "#
+                ),
+            )
+            .file("bar.bib", "")
+            .main("foo.tex")
+            .position(0, 3)
+            .test_position(BibliographyProvider)
+            .await;
+
+        assert!(actual_locations.is_empty());
+    }
+}
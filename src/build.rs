@@ -1,7 +1,8 @@
 use crate::{
+    diagnostics::parse_build_diagnostics,
     feature::{FeatureProvider, FeatureRequest},
     protocol::{
-        BuildParams, BuildResult, BuildStatus, ClientCapabilitiesExt, LatexOptions,
+        BuildParams, BuildResult, BuildStatus, ClientCapabilitiesExt, Diagnostic, LatexOptions,
         LogMessageParams, LspClient, MessageType, ProgressParams, ProgressParamsValue,
         ProgressToken, Uri, WorkDoneProgress, WorkDoneProgressBegin, WorkDoneProgressCreateParams,
         WorkDoneProgressEnd,
@@ -10,23 +11,52 @@ use crate::{
 use async_trait::async_trait;
 use chashmap::CHashMap;
 use futures::{
-    future::{AbortHandle, Abortable, Aborted},
+    future::{AbortHandle, Abortable},
     lock::Mutex,
     prelude::*,
     stream,
 };
 use log::error;
-use std::{collections::HashMap, io, path::Path, process::Stdio, sync::Arc};
+use std::{collections::HashMap, io, mem, path::Path, process::Stdio, sync::Arc, time::Duration};
+use thiserror::Error;
 use tokio::{
+    fs,
     io::{AsyncBufReadExt, BufReader},
     process::Command,
+    time,
 };
 use uuid::Uuid;
 
+#[derive(Debug, Error)]
+pub enum BuildError {
+    #[error("the LaTeX engine `{0}` could not be found")]
+    EngineNotFound(String),
+    #[error("the compilation failed")]
+    CompilationFailed(HashMap<Uri, Vec<Diagnostic>>),
+    #[error("the build timed out")]
+    Timeout,
+    #[error("the build was cancelled")]
+    Cancelled,
+    #[error("an I/O error occurred: `{0}`")]
+    IO(#[from] io::Error),
+}
+
+impl BuildError {
+    pub fn status(&self) -> BuildStatus {
+        match self {
+            Self::EngineNotFound(_) | Self::IO(_) => BuildStatus::Failure,
+            Self::CompilationFailed(_) => BuildStatus::Error,
+            Self::Timeout => BuildStatus::Timeout,
+            Self::Cancelled => BuildStatus::Cancelled,
+        }
+    }
+}
+
 pub struct BuildProvider<C> {
     client: Arc<C>,
     handles_by_token: Mutex<HashMap<ProgressToken, AbortHandle>>,
     current_docs: CHashMap<Uri, ()>,
+    diagnostics_by_uri: Mutex<HashMap<Uri, Vec<Diagnostic>>>,
 }
 
 impl<C> BuildProvider<C> {
@@ -35,6 +65,7 @@ impl<C> BuildProvider<C> {
             client,
             handles_by_token: Mutex::new(HashMap::new()),
             current_docs: CHashMap::new(),
+            diagnostics_by_uri: Mutex::new(HashMap::new()),
         }
     }
 
@@ -42,6 +73,10 @@ impl<C> BuildProvider<C> {
         self.current_docs.len() > 0
     }
 
+    pub async fn take_diagnostics(&self) -> HashMap<Uri, Vec<Diagnostic>> {
+        mem::take(&mut *self.diagnostics_by_uri.lock().await)
+    }
+
     pub async fn cancel(&self, token: ProgressToken) {
         let handles_by_token = self.handles_by_token.lock().await;
         if let Some(handle) = handles_by_token.get(&token) {
@@ -71,8 +106,15 @@ where
         }
 
         let doc = req
-            .snapshot()
-            .parent(&req.current().uri, &req.options, &req.current_dir)
+            .config
+            .root_file
+            .as_ref()
+            .and_then(|root_file| Uri::from_file_path(req.current_dir.join(root_file)).ok())
+            .and_then(|uri| req.snapshot().find(&uri))
+            .or_else(|| {
+                req.snapshot()
+                    .parent(&req.current().uri, &req.options, &req.current_dir)
+            })
             .unwrap_or_else(|| Arc::clone(&req.view.current));
 
         if !doc.is_file() {
@@ -114,14 +156,27 @@ where
 
                 let latex_options = req.options.latex.clone().unwrap_or_default();
                 let client = Arc::clone(&self.client);
-                match Abortable::new(build(&path, &latex_options, client), reg).await {
-                    Ok(Ok(true)) => BuildStatus::Success,
-                    Ok(Ok(false)) => BuildStatus::Error,
-                    Ok(Err(why)) => {
-                        error!("Unable to build the document {}: {}", doc.uri, why);
-                        BuildStatus::Failure
+                let log_uris = req
+                    .snapshot()
+                    .resolve_aux_targets(&doc.uri, &req.options, &req.current_dir, "log")
+                    .unwrap_or_default();
+                let timeout = req.params.timeout.map(Duration::from_millis);
+                let task = Abortable::new(
+                    build(&path, &doc.uri, &log_uris, &latex_options, timeout, client),
+                    reg,
+                );
+                match task.await.unwrap_or(Err(BuildError::Cancelled)) {
+                    Ok(()) => BuildStatus::Success,
+                    Err(BuildError::CompilationFailed(diagnostics_by_uri)) => {
+                        *self.diagnostics_by_uri.lock().await = diagnostics_by_uri;
+                        BuildStatus::Error
+                    }
+                    Err(why) => {
+                        if let BuildError::EngineNotFound(_) | BuildError::IO(_) = why {
+                            error!("Unable to build the document {}: {}", doc.uri, why);
+                        }
+                        why.status()
                     }
-                    Err(Aborted) => BuildStatus::Cancelled,
                 }
             }
             Err(()) => {
@@ -149,7 +204,14 @@ where
     }
 }
 
-async fn build<C>(path: &Path, options: &LatexOptions, client: Arc<C>) -> io::Result<bool>
+async fn build<C>(
+    path: &Path,
+    uri: &Uri,
+    log_uris: &[Uri],
+    options: &LatexOptions,
+    timeout: Option<Duration>,
+    client: Arc<C>,
+) -> Result<(), BuildError>
 where
     C: LspClient + Send + Sync + 'static,
 {
@@ -167,14 +229,19 @@ where
         .map(|arg| replace_placeholder(arg, path))
         .collect();
 
-    let mut process = Command::new(build_options.executable())
+    let executable = build_options.executable();
+    let mut process = Command::new(&executable)
         .args(args)
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .current_dir(build_dir)
         .kill_on_drop(true)
-        .spawn()?;
+        .spawn()
+        .map_err(|why| match why.kind() {
+            io::ErrorKind::NotFound => BuildError::EngineNotFound(executable),
+            _ => BuildError::IO(why),
+        })?;
 
     let stdout = BufReader::new(process.stdout.take().unwrap()).lines();
     let stderr = BufReader::new(process.stderr.take().unwrap()).lines();
@@ -191,7 +258,30 @@ where
         }
     });
 
-    Ok(process.await?.success())
+    let status = match timeout {
+        Some(timeout) => time::timeout(timeout, process)
+            .await
+            .map_err(|_| BuildError::Timeout)??,
+        None => process.await?,
+    };
+
+    if status.success() {
+        Ok(())
+    } else {
+        let mut diagnostics_by_uri = HashMap::new();
+        for log_path in log_uris
+            .iter()
+            .filter(|uri| uri.scheme() == "file")
+            .filter_map(|uri| uri.to_file_path().ok())
+        {
+            if let Ok(log) = fs::read(&log_path).await {
+                let log = String::from_utf8_lossy(&log);
+                diagnostics_by_uri = parse_build_diagnostics(uri, &log);
+                break;
+            }
+        }
+        Err(BuildError::CompilationFailed(diagnostics_by_uri))
+    }
 }
 
 fn replace_placeholder(arg: String, file: &Path) -> String {
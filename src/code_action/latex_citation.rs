@@ -0,0 +1,278 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{
+        code_action_kind, CodeAction, CodeActionParams, Range, RangeExt, TextEdit, Uri,
+        WorkspaceEdit,
+    },
+    syntax::{latex, AstNodeIndex, CharStream, SyntaxNode},
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexCitationCodeActionProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexCitationCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut actions = Vec::new();
+        if let DocumentContent::Latex(table) = &req.current().content {
+            let pos = req.params.range.start;
+            if let Some(node) = table
+                .citations
+                .iter()
+                .map(|citation| citation.parent)
+                .find(|node| table[*node].range().contains(pos))
+            {
+                let uri = &req.current().uri;
+                actions.extend(split(table, &req.current().text, uri, node));
+                actions.extend(merge(table, uri, node));
+            }
+        }
+        actions
+    }
+}
+
+#[derive(Debug)]
+struct SimpleCitation<'a> {
+    name: &'a str,
+    keys: Vec<&'a latex::Token>,
+}
+
+impl<'a> SimpleCitation<'a> {
+    fn parse(table: &'a latex::SymbolTable, node: AstNodeIndex) -> Option<Self> {
+        let citation = table
+            .citations
+            .iter()
+            .find(|citation| citation.parent == node)?;
+        let cmd = table.as_command(node)?;
+
+        let mut children = table.children(node);
+        let group = table.as_group(children.next()?)?;
+        if children.next().is_some()
+            || group.kind != latex::GroupKind::Group
+            || group.right.is_none()
+        {
+            return None;
+        }
+
+        Some(Self {
+            name: cmd.name.text(),
+            keys: citation.keys(table),
+        })
+    }
+
+    fn print(name: &str, keys: &[&latex::Token]) -> String {
+        format!(
+            "{}{{{}}}",
+            name,
+            keys.iter().map(|key| key.text()).join(",")
+        )
+    }
+}
+
+fn split(
+    table: &latex::SymbolTable,
+    text: &str,
+    uri: &Uri,
+    node: AstNodeIndex,
+) -> Option<CodeAction> {
+    let citation = SimpleCitation::parse(table, node)?;
+    if citation.keys.len() < 2 {
+        return None;
+    }
+
+    let mut new_text = SimpleCitation::print(citation.name, &[citation.keys[0]]);
+    for (previous, key) in citation.keys.iter().tuple_windows() {
+        let separator: String = CharStream::extract(text, Range::new(previous.end(), key.start()))
+            .chars()
+            .filter(|c| c.is_whitespace())
+            .collect();
+        if separator.is_empty() {
+            new_text.push(' ');
+        } else {
+            new_text.push_str(&separator);
+        }
+        new_text.push_str(&SimpleCitation::print(citation.name, &[*key]));
+    }
+    Some(create_action(
+        "Split into individual citations",
+        uri,
+        table[node].range(),
+        new_text,
+    ))
+}
+
+fn merge(table: &latex::SymbolTable, uri: &Uri, node: AstNodeIndex) -> Option<CodeAction> {
+    let name = SimpleCitation::parse(table, node)?.name;
+    let parent = table.parent(node)?;
+    let siblings: Vec<_> = table.children(parent).collect();
+    let index = siblings.iter().position(|sibling| *sibling == node)?;
+
+    let is_mergeable = |sibling: &&AstNodeIndex| {
+        SimpleCitation::parse(table, **sibling)
+            .filter(|citation| citation.name == name)
+            .is_some()
+    };
+    let start = index
+        - siblings[..index]
+            .iter()
+            .rev()
+            .take_while(is_mergeable)
+            .count();
+    let end = index
+        + siblings[index + 1..]
+            .iter()
+            .take_while(is_mergeable)
+            .count();
+    if start == end {
+        return None;
+    }
+
+    let keys: Vec<_> = siblings[start..=end]
+        .iter()
+        .flat_map(|sibling| SimpleCitation::parse(table, *sibling).unwrap().keys)
+        .collect();
+    let range = Range::new(table[siblings[start]].start(), table[siblings[end]].end());
+    Some(create_action(
+        "Merge adjacent citations",
+        uri,
+        range,
+        SimpleCitation::print(name, &keys),
+    ))
+}
+
+fn create_action(title: &str, uri: &Uri, range: Range, text: String) -> CodeAction {
+    let mut changes = HashMap::new();
+    changes.insert(uri.clone().into(), vec![TextEdit::new(range, text)]);
+    CodeAction {
+        title: title.into(),
+        kind: Some(code_action_kind::REFACTOR_REWRITE.into()),
+        diagnostics: None,
+        edit: Some(WorkspaceEdit::new(changes)),
+        command: None,
+        is_preferred: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feature::FeatureTester;
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let actual_actions = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .test_code_action(
+                LatexCitationCodeActionProvider,
+                Range::new_simple(0, 0, 0, 0),
+            )
+            .await;
+
+        assert!(actual_actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let actual_actions = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .test_code_action(
+                LatexCitationCodeActionProvider,
+                Range::new_simple(0, 0, 0, 0),
+            )
+            .await;
+
+        assert!(actual_actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn split() {
+        let actual_actions = FeatureTester::new()
+            .file("main.tex", "foo \\cite{a, b,\n c} bar")
+            .main("main.tex")
+            .test_code_action(
+                LatexCitationCodeActionProvider,
+                Range::new_simple(0, 6, 0, 6),
+            )
+            .await;
+
+        let expected_actions = vec![create_action(
+            "Split into individual citations",
+            &FeatureTester::uri("main.tex"),
+            Range::new_simple(0, 4, 1, 3),
+            "\\cite{a} \\cite{b}\n \\cite{c}".into(),
+        )];
+        assert_eq!(actual_actions, expected_actions);
+    }
+
+    #[tokio::test]
+    async fn split_without_whitespace() {
+        let actual_actions = FeatureTester::new()
+            .file("main.tex", r#"\cite{a,b}"#)
+            .main("main.tex")
+            .test_code_action(
+                LatexCitationCodeActionProvider,
+                Range::new_simple(0, 1, 0, 1),
+            )
+            .await;
+
+        let expected_actions = vec![create_action(
+            "Split into individual citations",
+            &FeatureTester::uri("main.tex"),
+            Range::new_simple(0, 0, 0, 10),
+            "\\cite{a} \\cite{b}".into(),
+        )];
+        assert_eq!(actual_actions, expected_actions);
+    }
+
+    #[tokio::test]
+    async fn merge() {
+        let actual_actions = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \cite{a} \cite{b}
+                        \cite{c} \citet{d}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .test_code_action(
+                LatexCitationCodeActionProvider,
+                Range::new_simple(0, 10, 0, 10),
+            )
+            .await;
+
+        let expected_actions = vec![create_action(
+            "Merge adjacent citations",
+            &FeatureTester::uri("main.tex"),
+            Range::new_simple(0, 0, 1, 8),
+            "\\cite{a,b,c}".into(),
+        )];
+        assert_eq!(actual_actions, expected_actions);
+    }
+
+    #[tokio::test]
+    async fn optional_argument() {
+        let actual_actions = FeatureTester::new()
+            .file("main.tex", r#"\cite[p. 5]{a,b}"#)
+            .main("main.tex")
+            .test_code_action(
+                LatexCitationCodeActionProvider,
+                Range::new_simple(0, 1, 0, 1),
+            )
+            .await;
+
+        assert!(actual_actions.is_empty());
+    }
+}
@@ -0,0 +1,237 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{
+        code_action_kind, CodeAction, CodeActionParams, Range, RangeExt, TextEdit, Uri,
+        WorkspaceEdit,
+    },
+    syntax::{latex, AstNodeIndex, LatexLabelKind, SyntaxNode},
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+const SEPARATORS: &[&str] = &[",", "and"];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexCleverefCodeActionProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexCleverefCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut actions = Vec::new();
+        if let DocumentContent::Latex(table) = &req.current().content {
+            let pos = req.params.range.start;
+            if let Some(node) = table
+                .labels
+                .iter()
+                .filter(|label| label.kind == LatexLabelKind::Reference)
+                .map(|label| label.parent)
+                .find(|node| table[*node].range().contains(pos))
+            {
+                if is_cleveref_loaded(req) {
+                    actions.extend(merge(table, &req.current().uri, node));
+                }
+            }
+        }
+        actions
+    }
+}
+
+fn is_cleveref_loaded(req: &FeatureRequest<CodeActionParams>) -> bool {
+    req.related().iter().any(|doc| match &doc.content {
+        DocumentContent::Latex(table) => table.components.iter().any(|comp| comp == "cleveref.sty"),
+        DocumentContent::Bibtex(_) => false,
+    })
+}
+
+fn reference_names(table: &latex::SymbolTable, node: AstNodeIndex) -> Option<Vec<&latex::Token>> {
+    let label = table
+        .labels
+        .iter()
+        .find(|label| label.parent == node && label.kind == LatexLabelKind::Reference)?;
+    let cmd = table.as_command(node)?;
+    if cmd.name.text() != "\\ref" {
+        return None;
+    }
+
+    let mut children = table.children(node);
+    let group = table.as_group(children.next()?)?;
+    if children.next().is_some() || group.kind != latex::GroupKind::Group || group.right.is_none() {
+        return None;
+    }
+
+    Some(label.names(table))
+}
+
+fn is_separator(table: &latex::SymbolTable, node: AstNodeIndex) -> bool {
+    table
+        .as_text(node)
+        .filter(|text| {
+            text.words
+                .iter()
+                .all(|word| SEPARATORS.contains(&word.text()))
+        })
+        .is_some()
+}
+
+fn merge(table: &latex::SymbolTable, uri: &Uri, node: AstNodeIndex) -> Option<CodeAction> {
+    reference_names(table, node)?;
+    let parent = table.parent(node)?;
+    let siblings: Vec<_> = table.children(parent).collect();
+    let index = siblings.iter().position(|sibling| *sibling == node)?;
+
+    let is_reference = |i: usize| reference_names(table, siblings[i]).is_some();
+    let mut start = index;
+    loop {
+        if start >= 1 && is_reference(start - 1) {
+            start -= 1;
+        } else if start >= 2 && is_separator(table, siblings[start - 1]) && is_reference(start - 2)
+        {
+            start -= 2;
+        } else {
+            break;
+        }
+    }
+
+    let mut end = index;
+    loop {
+        if end + 1 < siblings.len() && is_reference(end + 1) {
+            end += 1;
+        } else if end + 2 < siblings.len()
+            && is_separator(table, siblings[end + 1])
+            && is_reference(end + 2)
+        {
+            end += 2;
+        } else {
+            break;
+        }
+    }
+
+    if start == end {
+        return None;
+    }
+
+    let names = siblings[start..=end]
+        .iter()
+        .filter_map(|sibling| reference_names(table, *sibling))
+        .flatten()
+        .map(|name| name.text())
+        .join(",");
+    let range = Range::new(table[siblings[start]].start(), table[siblings[end]].end());
+    Some(create_action(uri, range, format!("\\cref{{{}}}", names)))
+}
+
+fn create_action(uri: &Uri, range: Range, text: String) -> CodeAction {
+    let mut changes = HashMap::new();
+    changes.insert(uri.clone().into(), vec![TextEdit::new(range, text)]);
+    CodeAction {
+        title: "Merge into \\cref".into(),
+        kind: Some(code_action_kind::REFACTOR_REWRITE.into()),
+        diagnostics: None,
+        edit: Some(WorkspaceEdit::new(changes)),
+        command: None,
+        is_preferred: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feature::FeatureTester;
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let actual_actions = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .test_code_action(
+                LatexCleverefCodeActionProvider,
+                Range::new_simple(0, 0, 0, 0),
+            )
+            .await;
+
+        assert!(actual_actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let actual_actions = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .test_code_action(
+                LatexCleverefCodeActionProvider,
+                Range::new_simple(0, 0, 0, 0),
+            )
+            .await;
+
+        assert!(actual_actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn without_cleveref() {
+        let actual_actions = FeatureTester::new()
+            .file("main.tex", r#"\ref{a}, \ref{b}"#)
+            .main("main.tex")
+            .test_code_action(
+                LatexCleverefCodeActionProvider,
+                Range::new_simple(0, 2, 0, 2),
+            )
+            .await;
+
+        assert!(actual_actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn merge() {
+        let actual_actions = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \usepackage{cleveref}
+                        See \ref{a}, \ref{b} and \ref{c} for details.
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .test_code_action(
+                LatexCleverefCodeActionProvider,
+                Range::new_simple(1, 15, 1, 15),
+            )
+            .await;
+
+        let expected_actions = vec![create_action(
+            &FeatureTester::uri("main.tex"),
+            Range::new_simple(1, 4, 1, 32),
+            "\\cref{a,b,c}".into(),
+        )];
+        assert_eq!(actual_actions, expected_actions);
+    }
+
+    #[tokio::test]
+    async fn single_reference() {
+        let actual_actions = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \usepackage{cleveref}
+                        See \ref{a} for details.
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .test_code_action(
+                LatexCleverefCodeActionProvider,
+                Range::new_simple(1, 6, 1, 6),
+            )
+            .await;
+
+        assert!(actual_actions.is_empty());
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{
+        code_action_kind, CodeAction, CodeActionParams, Diagnostic, Range, RangeExt, TextEdit, Uri,
+        WorkspaceEdit,
+    },
+    syntax::{latex, AstNodeIndex, CharStream, SyntaxNode, LANGUAGE_DATA},
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexFontCodeActionProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexFontCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut actions = Vec::new();
+        if let DocumentContent::Latex(table) = &req.current().content {
+            let pos = req.params.range.start;
+            for node in table.commands.iter().filter(|node| {
+                table
+                    .as_command(**node)
+                    .filter(|cmd| cmd.name.range().contains(pos))
+                    .is_some()
+            }) {
+                actions.extend(replace(req, table, *node));
+            }
+        }
+        actions
+    }
+}
+
+fn replace(
+    req: &FeatureRequest<CodeActionParams>,
+    table: &latex::SymbolTable,
+    node: AstNodeIndex,
+) -> Option<CodeAction> {
+    let cmd = table.as_command(node)?;
+    let old_name = cmd.name.text();
+    let new_name = LANGUAGE_DATA
+        .command_replacement(&old_name[1..])
+        .filter(|name| is_font_command(name))?;
+
+    let parent = table.parent(node)?;
+    let group = table
+        .as_group(parent)
+        .filter(|group| group.kind == latex::GroupKind::Group)?;
+    let right = group.right.as_ref()?;
+
+    let content = CharStream::extract(
+        &req.current().text,
+        Range::new(cmd.name.end(), right.start()),
+    );
+    let range = if table.children(parent).next() == Some(node) {
+        group.range()
+    } else {
+        Range::new(cmd.start(), right.start())
+    };
+
+    let diagnostics: Vec<_> = req
+        .params
+        .context
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.range == cmd.name.range())
+        .cloned()
+        .collect();
+
+    Some(create_action(
+        format!("Replace {} with {}", old_name, new_name),
+        &req.current().uri,
+        range,
+        format!("{}{{{}}}", new_name, content.trim_start()),
+        diagnostics,
+    ))
+}
+
+fn is_font_command(name: &str) -> bool {
+    name.starts_with("\\text") || name.starts_with("\\math")
+}
+
+fn create_action(
+    title: String,
+    uri: &Uri,
+    range: Range,
+    text: String,
+    diagnostics: Vec<Diagnostic>,
+) -> CodeAction {
+    let mut changes = HashMap::new();
+    changes.insert(uri.clone().into(), vec![TextEdit::new(range, text)]);
+    CodeAction {
+        title,
+        kind: Some(code_action_kind::QUICKFIX.into()),
+        diagnostics: Some(diagnostics).filter(|diagnostics| !diagnostics.is_empty()),
+        edit: Some(WorkspaceEdit::new(changes)),
+        command: None,
+        is_preferred: Some(true),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::DiagnosticSeverity};
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let actual_actions = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .test_code_action(LatexFontCodeActionProvider, Range::new_simple(0, 0, 0, 0))
+            .await;
+
+        assert!(actual_actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let actual_actions = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .test_code_action(LatexFontCodeActionProvider, Range::new_simple(0, 0, 0, 0))
+            .await;
+
+        assert!(actual_actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn whole_group() {
+        let actual_actions = FeatureTester::new()
+            .file("main.tex", r#"foo {\bf bar baz} qux"#)
+            .main("main.tex")
+            .test_code_action(LatexFontCodeActionProvider, Range::new_simple(0, 6, 0, 6))
+            .await;
+
+        let expected_actions = vec![create_action(
+            "Replace \\bf with \\textbf".into(),
+            &FeatureTester::uri("main.tex"),
+            Range::new_simple(0, 4, 0, 17),
+            "\\textbf{bar baz}".into(),
+            Vec::new(),
+        )];
+        assert_eq!(actual_actions, expected_actions);
+    }
+
+    #[tokio::test]
+    async fn rest_of_group() {
+        let actual_actions = FeatureTester::new()
+            .file("main.tex", r#"{foo \it bar}"#)
+            .main("main.tex")
+            .test_code_action(LatexFontCodeActionProvider, Range::new_simple(0, 6, 0, 6))
+            .await;
+
+        let expected_actions = vec![create_action(
+            "Replace \\it with \\textit".into(),
+            &FeatureTester::uri("main.tex"),
+            Range::new_simple(0, 5, 0, 12),
+            "\\textit{bar}".into(),
+            Vec::new(),
+        )];
+        assert_eq!(actual_actions, expected_actions);
+    }
+
+    #[tokio::test]
+    async fn outside_of_group() {
+        let actual_actions = FeatureTester::new()
+            .file("main.tex", r#"\bf foo"#)
+            .main("main.tex")
+            .test_code_action(LatexFontCodeActionProvider, Range::new_simple(0, 1, 0, 1))
+            .await;
+
+        assert!(actual_actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn linked_diagnostic() {
+        let diagnostic = Diagnostic {
+            source: Some("latex".into()),
+            range: Range::new_simple(0, 1, 0, 4),
+            message: "\\bf is deprecated, use \\textbf instead".into(),
+            severity: Some(DiagnosticSeverity::Information),
+            code: None,
+            related_information: None,
+            tags: None,
+        };
+        let actual_actions = FeatureTester::new()
+            .file("main.tex", r#"{\bf foo}"#)
+            .main("main.tex")
+            .diagnostics(vec![diagnostic.clone()])
+            .test_code_action(LatexFontCodeActionProvider, Range::new_simple(0, 2, 0, 2))
+            .await;
+
+        let expected_actions = vec![create_action(
+            "Replace \\bf with \\textbf".into(),
+            &FeatureTester::uri("main.tex"),
+            Range::new_simple(0, 0, 0, 9),
+            "\\textbf{foo}".into(),
+            vec![diagnostic],
+        )];
+        assert_eq!(actual_actions, expected_actions);
+    }
+
+    #[tokio::test]
+    async fn non_font_replacement() {
+        let actual_actions = FeatureTester::new()
+            .file("main.tex", r#"{\centerline foo}"#)
+            .main("main.tex")
+            .test_code_action(LatexFontCodeActionProvider, Range::new_simple(0, 2, 0, 2))
+            .await;
+
+        assert!(actual_actions.is_empty());
+    }
+}
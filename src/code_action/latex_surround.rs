@@ -0,0 +1,133 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{
+        code_action_kind, CodeAction, CodeActionParams, Range, TextEdit, Uri, WorkspaceEdit,
+    },
+    syntax::CharStream,
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+const DELIMITERS: &[(&str, &str)] = &[("$", "$"), ("{", "}")];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexSurroundCodeActionProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexSurroundCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let is_enabled = req
+            .options
+            .latex
+            .as_ref()
+            .map(|opts| opts.surround_selection())
+            .unwrap_or(false);
+
+        let range = req.params.range;
+        if !is_enabled || range.start == range.end {
+            return Vec::new();
+        }
+
+        match &req.current().content {
+            DocumentContent::Latex(_) => {
+                let text = CharStream::extract(&req.current().text, range);
+                DELIMITERS
+                    .iter()
+                    .map(|(left, right)| {
+                        create_action(&req.current().uri, range, left, right, &text)
+                    })
+                    .collect()
+            }
+            DocumentContent::Bibtex(_) => Vec::new(),
+        }
+    }
+}
+
+fn create_action(uri: &Uri, range: Range, left: &str, right: &str, text: &str) -> CodeAction {
+    let mut changes = HashMap::new();
+    let new_text = format!("{}{}{}", left, text, right);
+    changes.insert(uri.clone().into(), vec![TextEdit::new(range, new_text)]);
+    CodeAction {
+        title: format!("Surround with {}...{}", left, right),
+        kind: Some(code_action_kind::REFACTOR_REWRITE.into()),
+        diagnostics: None,
+        edit: Some(WorkspaceEdit::new(changes)),
+        command: None,
+        is_preferred: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::RangeExt};
+
+    #[tokio::test]
+    async fn disabled() {
+        let actual_actions = FeatureTester::new()
+            .file("main.tex", "foo bar")
+            .main("main.tex")
+            .test_code_action(
+                LatexSurroundCodeActionProvider,
+                Range::new_simple(0, 4, 0, 7),
+            )
+            .await;
+
+        assert!(actual_actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_selection() {
+        let actual_actions = FeatureTester::new()
+            .file("main.tex", "foo bar")
+            .main("main.tex")
+            .surround_selection(true)
+            .test_code_action(
+                LatexSurroundCodeActionProvider,
+                Range::new_simple(0, 4, 0, 4),
+            )
+            .await;
+
+        assert!(actual_actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn bibtex_document() {
+        let actual_actions = FeatureTester::new()
+            .file("main.bib", "@article{foo, title = {bar}}")
+            .main("main.bib")
+            .surround_selection(true)
+            .test_code_action(
+                LatexSurroundCodeActionProvider,
+                Range::new_simple(0, 23, 0, 26),
+            )
+            .await;
+
+        assert!(actual_actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn selection() {
+        let actual_actions = FeatureTester::new()
+            .file("main.tex", "foo x^2\n+ 1 bar")
+            .main("main.tex")
+            .surround_selection(true)
+            .test_code_action(
+                LatexSurroundCodeActionProvider,
+                Range::new_simple(0, 4, 1, 3),
+            )
+            .await;
+
+        let uri = FeatureTester::uri("main.tex");
+        let range = Range::new_simple(0, 4, 1, 3);
+        let expected_actions = vec![
+            create_action(&uri, range, "$", "$", "x^2\n+ 1"),
+            create_action(&uri, range, "{", "}", "x^2\n+ 1"),
+        ];
+        assert_eq!(actual_actions, expected_actions);
+    }
+}
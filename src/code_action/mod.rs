@@ -0,0 +1,48 @@
+mod latex_citation;
+mod latex_cleveref;
+mod latex_font;
+mod latex_surround;
+
+use self::{
+    latex_citation::LatexCitationCodeActionProvider,
+    latex_cleveref::LatexCleverefCodeActionProvider, latex_font::LatexFontCodeActionProvider,
+    latex_surround::LatexSurroundCodeActionProvider,
+};
+use crate::{
+    feature::{ConcatProvider, FeatureProvider, FeatureRequest},
+    protocol::{CodeAction, CodeActionParams},
+};
+use async_trait::async_trait;
+
+pub struct CodeActionProvider {
+    provider: ConcatProvider<CodeActionParams, CodeAction>,
+}
+
+impl CodeActionProvider {
+    pub fn new() -> Self {
+        Self {
+            provider: ConcatProvider::new(vec![
+                Box::new(LatexCitationCodeActionProvider),
+                Box::new(LatexCleverefCodeActionProvider),
+                Box::new(LatexFontCodeActionProvider),
+                Box::new(LatexSurroundCodeActionProvider),
+            ]),
+        }
+    }
+}
+
+impl Default for CodeActionProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for CodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        self.provider.execute(req).await
+    }
+}
@@ -0,0 +1,347 @@
+use super::model::{builtin_color, ColorModel};
+use crate::{
+    feature::{DocumentView, FeatureProvider, FeatureRequest},
+    protocol::{
+        Color, ColorInformation, ColorPresentation, ColorPresentationParams, DocumentColorParams,
+        Range, TextEdit,
+    },
+    syntax::{latex, SyntaxNode, LANGUAGE_DATA},
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+const MAX_ALIAS_DEPTH: usize = 8;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexColorProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexColorProvider {
+    type Params = DocumentColorParams;
+    type Output = Vec<ColorInformation>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut colors = Vec::new();
+        if let DocumentContent::Latex(table) = &req.current().content {
+            for spec in find_specs(table) {
+                colors.push(ColorInformation {
+                    range: spec.range,
+                    color: spec.color,
+                });
+            }
+
+            let definitions = collect_definitions(&req.view);
+            for name in find_color_names(table) {
+                if let Some(color) = resolve(&definitions, name.text(), 0) {
+                    colors.push(ColorInformation {
+                        range: name.range(),
+                        color,
+                    });
+                }
+            }
+        }
+        colors
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexColorPresentationProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexColorPresentationProvider {
+    type Params = ColorPresentationParams;
+    type Output = Vec<ColorPresentation>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut presentations = Vec::new();
+        if let DocumentContent::Latex(table) = &req.current().content {
+            if let Some(spec) = find_specs(table)
+                .into_iter()
+                .find(|spec| spec.range == req.params.range)
+            {
+                let models = std::iter::once(spec.model).chain(
+                    ColorModel::ALL
+                        .iter()
+                        .copied()
+                        .filter(|model| *model != spec.model),
+                );
+
+                for model in models {
+                    let value = model.encode(&req.params.color);
+                    let additional_text_edits = if model == spec.model {
+                        None
+                    } else {
+                        Some(vec![TextEdit::new(spec.model_range, model.name().into())])
+                    };
+
+                    presentations.push(ColorPresentation {
+                        label: format!("{{{}}}{{{}}}", model.name(), value),
+                        text_edit: Some(TextEdit::new(spec.range, value)),
+                        additional_text_edits,
+                    });
+                }
+            }
+        }
+        presentations
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ColorSpec {
+    model: ColorModel,
+    model_range: Range,
+    range: Range,
+    color: Color,
+}
+
+fn find_specs(table: &latex::SymbolTable) -> Vec<ColorSpec> {
+    let definitions = table.color_definitions.iter().filter_map(|def| {
+        let model = def.model(table)?;
+        parse_spec(table, def.parent, model, def.spec_index)
+    });
+
+    let usages = find_color_commands(table).filter_map(|(parent, index)| {
+        let model = table.extract_word(parent, latex::GroupKind::Options, 0)?;
+        parse_spec(table, parent, model, index)
+    });
+
+    definitions.chain(usages).collect()
+}
+
+fn parse_spec(
+    table: &latex::SymbolTable,
+    parent: latex::AstNodeIndex,
+    model_token: &latex::Token,
+    spec_index: usize,
+) -> Option<ColorSpec> {
+    let model = ColorModel::parse(model_token.text())?;
+    let components: Vec<_> = table
+        .extract_comma_separated_words(parent, latex::GroupKind::Group, spec_index)?
+        .into_iter()
+        .map(latex::Token::text)
+        .collect();
+    let color = model.decode(&components)?;
+
+    let group = table.extract_group(parent, latex::GroupKind::Group, spec_index)?;
+    let group = table.as_group(group)?;
+    Some(ColorSpec {
+        model,
+        model_range: model_token.range(),
+        range: Range::new(group.left.end(), group.right.as_ref()?.start()),
+        color,
+    })
+}
+
+fn find_color_commands<'a>(
+    table: &'a latex::SymbolTable,
+) -> impl Iterator<Item = (latex::AstNodeIndex, usize)> + 'a {
+    table.commands.iter().filter_map(move |parent| {
+        let name = table.as_command(*parent)?.name.text();
+        LANGUAGE_DATA
+            .color_commands
+            .iter()
+            .find(|cmd| cmd.name == name)
+            .map(|cmd| (*parent, cmd.index))
+    })
+}
+
+fn find_color_names(table: &latex::SymbolTable) -> Vec<&latex::Token> {
+    find_color_commands(table)
+        .filter(|(parent, _)| {
+            table
+                .extract_group(*parent, latex::GroupKind::Options, 0)
+                .is_none()
+        })
+        .filter_map(|(parent, index)| table.extract_word(parent, latex::GroupKind::Group, index))
+        .collect()
+}
+
+fn collect_definitions(
+    view: &DocumentView,
+) -> HashMap<&str, (&latex::SymbolTable, latex::ColorDefinition)> {
+    let mut definitions = HashMap::new();
+    for document in &view.related {
+        if let DocumentContent::Latex(table) = &document.content {
+            for def in &table.color_definitions {
+                definitions
+                    .entry(def.name(table).text())
+                    .or_insert((table.as_ref(), *def));
+            }
+        }
+    }
+    definitions
+}
+
+fn resolve(
+    definitions: &HashMap<&str, (&latex::SymbolTable, latex::ColorDefinition)>,
+    name: &str,
+    depth: usize,
+) -> Option<Color> {
+    match definitions.get(name) {
+        Some((table, def)) => match def.model(table) {
+            Some(model) => {
+                parse_spec(table, def.parent, model, def.spec_index).map(|spec| spec.color)
+            }
+            None if depth < MAX_ALIAS_DEPTH => {
+                let alias =
+                    table.extract_word(def.parent, latex::GroupKind::Group, def.spec_index)?;
+                resolve(definitions, alias.text(), depth + 1)
+            }
+            None => None,
+        },
+        None => builtin_color(name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::RangeExt};
+    use indoc::indoc;
+
+    fn rgb(red: f64, green: f64, blue: f64) -> Color {
+        Color {
+            red,
+            green,
+            blue,
+            alpha: 1.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let actual_colors = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .test_color(LatexColorProvider)
+            .await;
+
+        assert!(actual_colors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let actual_colors = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .test_color(LatexColorProvider)
+            .await;
+
+        assert!(actual_colors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn definition() {
+        let actual_colors = FeatureTester::new()
+            .file("main.tex", r#"\definecolor{foo}{RGB}{255,0,0}"#)
+            .main("main.tex")
+            .test_color(LatexColorProvider)
+            .await;
+
+        let expected_colors = vec![ColorInformation {
+            range: Range::new_simple(0, 23, 0, 30),
+            color: rgb(1.0, 0.0, 0.0),
+        }];
+        assert_eq!(actual_colors, expected_colors);
+    }
+
+    #[tokio::test]
+    async fn usage() {
+        let actual_colors = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \include{bar}
+                        \color{baz}
+                        \textcolor{red}{qux}
+                        \color[gray]{0.5}
+                        \color{qux}
+                    "#
+                ),
+            )
+            .file(
+                "bar.tex",
+                indoc!(
+                    r#"
+                        \definecolor{foo}{HTML}{0000FF}
+                        \colorlet{baz}{foo}
+                    "#
+                ),
+            )
+            .main("foo.tex")
+            .test_color(LatexColorProvider)
+            .await;
+
+        let expected_colors = vec![
+            ColorInformation {
+                range: Range::new_simple(3, 13, 3, 16),
+                color: rgb(0.5, 0.5, 0.5),
+            },
+            ColorInformation {
+                range: Range::new_simple(1, 7, 1, 10),
+                color: rgb(0.0, 0.0, 1.0),
+            },
+            ColorInformation {
+                range: Range::new_simple(2, 11, 2, 14),
+                color: rgb(1.0, 0.0, 0.0),
+            },
+        ];
+        assert_eq!(actual_colors, expected_colors);
+    }
+
+    #[tokio::test]
+    async fn presentation() {
+        let actual_presentations = FeatureTester::new()
+            .file("main.tex", r#"\definecolor{foo}{RGB}{255,0,0}"#)
+            .main("main.tex")
+            .test_color_presentation(
+                LatexColorPresentationProvider,
+                Range::new_simple(0, 23, 0, 30),
+                rgb(0.0, 1.0, 0.0),
+            )
+            .await;
+
+        let spec_range = Range::new_simple(0, 23, 0, 30);
+        let model_range = Range::new_simple(0, 18, 0, 21);
+        let expected_presentations = vec![
+            ColorPresentation {
+                label: "{RGB}{0,255,0}".into(),
+                text_edit: Some(TextEdit::new(spec_range, "0,255,0".into())),
+                additional_text_edits: None,
+            },
+            ColorPresentation {
+                label: "{rgb}{0,1,0}".into(),
+                text_edit: Some(TextEdit::new(spec_range, "0,1,0".into())),
+                additional_text_edits: Some(vec![TextEdit::new(model_range, "rgb".into())]),
+            },
+            ColorPresentation {
+                label: "{HTML}{00FF00}".into(),
+                text_edit: Some(TextEdit::new(spec_range, "00FF00".into())),
+                additional_text_edits: Some(vec![TextEdit::new(model_range, "HTML".into())]),
+            },
+            ColorPresentation {
+                label: "{gray}{0.59}".into(),
+                text_edit: Some(TextEdit::new(spec_range, "0.59".into())),
+                additional_text_edits: Some(vec![TextEdit::new(model_range, "gray".into())]),
+            },
+        ];
+        assert_eq!(actual_presentations, expected_presentations);
+    }
+
+    #[tokio::test]
+    async fn presentation_unknown_range() {
+        let actual_presentations = FeatureTester::new()
+            .file("main.tex", r#"\definecolor{foo}{RGB}{255,0,0}"#)
+            .main("main.tex")
+            .test_color_presentation(
+                LatexColorPresentationProvider,
+                Range::new_simple(0, 13, 0, 16),
+                rgb(0.0, 1.0, 0.0),
+            )
+            .await;
+
+        assert!(actual_presentations.is_empty());
+    }
+}
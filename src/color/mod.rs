@@ -0,0 +1,67 @@
+mod latex_color;
+mod model;
+
+pub use self::model::{builtin_color, ColorModel};
+
+use self::latex_color::{LatexColorPresentationProvider, LatexColorProvider};
+use crate::{
+    feature::{ConcatProvider, FeatureProvider, FeatureRequest},
+    protocol::{ColorInformation, ColorPresentation, ColorPresentationParams, DocumentColorParams},
+};
+use async_trait::async_trait;
+
+pub struct ColorProvider {
+    provider: ConcatProvider<DocumentColorParams, ColorInformation>,
+}
+
+impl ColorProvider {
+    pub fn new() -> Self {
+        Self {
+            provider: ConcatProvider::new(vec![Box::new(LatexColorProvider)]),
+        }
+    }
+}
+
+impl Default for ColorProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for ColorProvider {
+    type Params = DocumentColorParams;
+    type Output = Vec<ColorInformation>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        self.provider.execute(req).await
+    }
+}
+
+pub struct ColorPresentationProvider {
+    provider: ConcatProvider<ColorPresentationParams, ColorPresentation>,
+}
+
+impl ColorPresentationProvider {
+    pub fn new() -> Self {
+        Self {
+            provider: ConcatProvider::new(vec![Box::new(LatexColorPresentationProvider)]),
+        }
+    }
+}
+
+impl Default for ColorPresentationProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for ColorPresentationProvider {
+    type Params = ColorPresentationParams;
+    type Output = Vec<ColorPresentation>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        self.provider.execute(req).await
+    }
+}
@@ -0,0 +1,169 @@
+use crate::protocol::Color;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ColorModel {
+    Rgb,
+    RgbInteger,
+    Html,
+    Gray,
+}
+
+impl ColorModel {
+    pub const ALL: [Self; 4] = [Self::Rgb, Self::RgbInteger, Self::Html, Self::Gray];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "rgb" => Some(Self::Rgb),
+            "RGB" => Some(Self::RgbInteger),
+            "HTML" => Some(Self::Html),
+            "gray" => Some(Self::Gray),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Rgb => "rgb",
+            Self::RgbInteger => "RGB",
+            Self::Html => "HTML",
+            Self::Gray => "gray",
+        }
+    }
+
+    pub fn decode(self, components: &[&str]) -> Option<Color> {
+        match (self, components) {
+            (Self::Rgb, [red, green, blue]) => {
+                Some(rgb(parse_unit(red)?, parse_unit(green)?, parse_unit(blue)?))
+            }
+            (Self::RgbInteger, [red, green, blue]) => Some(rgb(
+                f64::from(red.parse::<u8>().ok()?) / 255.0,
+                f64::from(green.parse::<u8>().ok()?) / 255.0,
+                f64::from(blue.parse::<u8>().ok()?) / 255.0,
+            )),
+            (Self::Html, [hex]) => {
+                if hex.len() != 6 {
+                    return None;
+                }
+
+                let value = u32::from_str_radix(hex, 16).ok()?;
+                Some(rgb(
+                    f64::from((value >> 16) & 0xFF) / 255.0,
+                    f64::from((value >> 8) & 0xFF) / 255.0,
+                    f64::from(value & 0xFF) / 255.0,
+                ))
+            }
+            (Self::Gray, [gray]) => {
+                let gray = parse_unit(gray)?;
+                Some(rgb(gray, gray, gray))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn encode(self, color: &Color) -> String {
+        match self {
+            Self::Rgb => format!(
+                "{},{},{}",
+                format_unit(color.red),
+                format_unit(color.green),
+                format_unit(color.blue)
+            ),
+            Self::RgbInteger => format!(
+                "{},{},{}",
+                to_byte(color.red),
+                to_byte(color.green),
+                to_byte(color.blue)
+            ),
+            Self::Html => format!(
+                "{:02X}{:02X}{:02X}",
+                to_byte(color.red),
+                to_byte(color.green),
+                to_byte(color.blue)
+            ),
+            Self::Gray => format_unit(0.3 * color.red + 0.59 * color.green + 0.11 * color.blue),
+        }
+    }
+}
+
+pub fn builtin_color(name: &str) -> Option<Color> {
+    let (red, green, blue) = match name {
+        "red" => (1.0, 0.0, 0.0),
+        "green" => (0.0, 1.0, 0.0),
+        "blue" => (0.0, 0.0, 1.0),
+        "cyan" => (0.0, 1.0, 1.0),
+        "magenta" => (1.0, 0.0, 1.0),
+        "yellow" => (1.0, 1.0, 0.0),
+        "black" => (0.0, 0.0, 0.0),
+        "white" => (1.0, 1.0, 1.0),
+        "gray" => (0.5, 0.5, 0.5),
+        "darkgray" => (0.25, 0.25, 0.25),
+        "lightgray" => (0.75, 0.75, 0.75),
+        "brown" => (0.75, 0.5, 0.25),
+        "lime" => (0.75, 1.0, 0.0),
+        "olive" => (0.5, 0.5, 0.0),
+        "orange" => (1.0, 0.5, 0.0),
+        "pink" => (1.0, 0.75, 0.75),
+        "purple" => (0.75, 0.0, 0.25),
+        "teal" => (0.0, 0.5, 0.5),
+        "violet" => (0.5, 0.0, 0.5),
+        _ => return None,
+    };
+    Some(rgb(red, green, blue))
+}
+
+fn rgb(red: f64, green: f64, blue: f64) -> Color {
+    Color {
+        red,
+        green,
+        blue,
+        alpha: 1.0,
+    }
+}
+
+fn parse_unit(text: &str) -> Option<f64> {
+    text.parse()
+        .ok()
+        .filter(|value| (0.0..=1.0).contains(value))
+}
+
+fn format_unit(value: f64) -> String {
+    let text = format!("{:.3}", value.max(0.0).min(1.0));
+    text.trim_end_matches('0').trim_end_matches('.').into()
+}
+
+fn to_byte(value: f64) -> u8 {
+    (value.max(0.0).min(1.0) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_rgb_integer() {
+        let color = ColorModel::RgbInteger.decode(&["255", "0", "51"]).unwrap();
+        assert_eq!(color, rgb(1.0, 0.0, 0.2));
+    }
+
+    #[test]
+    fn decode_html() {
+        let color = ColorModel::Html.decode(&["FF8000"]).unwrap();
+        assert_eq!(color, rgb(1.0, 128.0 / 255.0, 0.0));
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(ColorModel::Rgb.decode(&["2", "0", "0"]), None);
+        assert_eq!(ColorModel::Gray.decode(&["0.5", "0.5"]), None);
+        assert_eq!(ColorModel::Html.decode(&["FF80"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let color = rgb(1.0, 0.5, 0.0);
+        assert_eq!(ColorModel::Rgb.encode(&color), "1,0.5,0");
+        assert_eq!(ColorModel::RgbInteger.encode(&color), "255,128,0");
+        assert_eq!(ColorModel::Html.encode(&color), "FF8000");
+        assert_eq!(ColorModel::Gray.encode(&color), "0.595");
+    }
+}
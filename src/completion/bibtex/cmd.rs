@@ -27,7 +27,6 @@ pub async fn complete_bibtex_commands<'a>(
                         range,
                         ItemData::ComponentCommand {
                             name: &cmd.name,
-                            image: cmd.image.as_deref(),
                             glyph: cmd.glyph.as_deref(),
                             file_names: &[],
                         },
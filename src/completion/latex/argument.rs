@@ -13,7 +13,7 @@ pub async fn complete_latex_arguments<'a>(
     for comp in req.view.components() {
         for cmd in &comp.commands {
             for (i, param) in cmd.parameters.iter().enumerate() {
-                complete_internal(req, items, &cmd.name, i, param).await;
+                complete_internal(req, items, &comp.file_names, &cmd.name, i, param).await;
             }
         }
     }
@@ -22,6 +22,7 @@ pub async fn complete_latex_arguments<'a>(
 async fn complete_internal<'a>(
     req: &'a FeatureRequest<CompletionParams>,
     items: &mut Vec<Item<'a>>,
+    file_names: &'a [String],
     name: &'a str,
     index: usize,
     param: &'a crate::components::Parameter,
@@ -35,7 +36,9 @@ async fn complete_internal<'a>(
                     ctx.range,
                     ItemData::Argument {
                         name: &arg.name,
-                        image: arg.image.as_deref(),
+                        command: name,
+                        index,
+                        file_names,
                     },
                 );
                 items.push(item);
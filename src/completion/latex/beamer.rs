@@ -0,0 +1,157 @@
+use super::combinators::{self, Parameter};
+use crate::{
+    completion::types::{Item, ItemData},
+    feature::FeatureRequest,
+    protocol::CompletionParams,
+    syntax::{latex, SyntaxNode, LANGUAGE_DATA},
+    workspace::DocumentContent,
+};
+use std::iter;
+
+pub async fn complete_latex_beamer_frame_options<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    if !is_beamer_document(req) {
+        return;
+    }
+
+    let param = Parameter {
+        name: "begin",
+        index: 0,
+    };
+    combinators::options_argument(req, iter::once(param), |ctx| async move {
+        let table = req.current().content.as_latex().unwrap();
+        let is_frame = table
+            .extract_word(ctx.node, latex::GroupKind::Group, 0)
+            .filter(|name| name.text() == "frame")
+            .is_some();
+
+        if is_frame {
+            for name in &LANGUAGE_DATA.beamer_frame_options {
+                let item = Item::new(ctx.range, ItemData::BeamerFrameOption { name });
+                items.push(item);
+            }
+        }
+    })
+    .await;
+}
+
+fn is_beamer_document(req: &FeatureRequest<CompletionParams>) -> bool {
+    req.related().iter().any(|doc| match &doc.content {
+        DocumentContent::Latex(table) => table.components.iter().any(|comp| comp == "beamer.cls"),
+        DocumentContent::Bibtex(_) => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        feature::FeatureTester,
+        protocol::{Range, RangeExt},
+    };
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let req = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_beamer_frame_options(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let req = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_beamer_frame_options(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn inside_frame_options() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \documentclass{beamer}
+                        \begin{frame}[fr]
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(1, 16)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_beamer_frame_options(&req, &mut actual_items).await;
+
+        assert!(actual_items
+            .iter()
+            .any(|item| item.data.label() == "fragile"));
+        assert_eq!(actual_items[0].range, Range::new_simple(1, 14, 1, 16));
+    }
+
+    #[tokio::test]
+    async fn inside_other_environment_options() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \documentclass{beamer}
+                        \begin{block}[]
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(1, 14)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_beamer_frame_options(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn outside_of_beamer() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \documentclass{article}
+                        \begin{frame}[]
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(1, 14)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_beamer_frame_options(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+}
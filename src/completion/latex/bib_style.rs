@@ -0,0 +1,124 @@
+use super::combinators::{self, Parameter};
+use crate::{
+    completion::types::{Item, ItemData},
+    feature::FeatureRequest,
+    protocol::CompletionParams,
+    syntax::latex,
+};
+use std::{collections::HashSet, iter};
+use tokio::fs;
+
+pub async fn complete_latex_bibliography_styles<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    let parameter = Parameter {
+        name: "bibliographystyle",
+        index: 0,
+    };
+
+    combinators::argument(req, iter::once(parameter), |ctx| async move {
+        let mut style_names = HashSet::new();
+        for name in find_local_styles(req).await.unwrap_or_default() {
+            if style_names.insert(name.clone()) {
+                let item = Item::new(ctx.range, ItemData::BibliographyStyle { name: name.into() });
+                items.push(item);
+            }
+        }
+
+        let resolver = req.distro.resolver().await;
+        for name in &resolver.bibliography_styles {
+            if style_names.insert(name.clone()) {
+                let item = Item::new(
+                    ctx.range,
+                    ItemData::BibliographyStyle {
+                        name: name.clone().into(),
+                    },
+                );
+                items.push(item);
+            }
+        }
+    })
+    .await;
+}
+
+async fn find_local_styles(req: &FeatureRequest<CompletionParams>) -> Option<Vec<String>> {
+    if !req.current().is_file() {
+        return None;
+    }
+
+    let mut path = latex::include_base_url(&req.current().uri, &req.options, &req.current_dir)?
+        .to_file_path()
+        .ok()?;
+    path.pop();
+
+    let mut names = Vec::new();
+    let mut entries = fs::read_dir(path).await.ok()?;
+    while let Some(entry) = entries.next_entry().await.ok()? {
+        let file_name = entry.file_name();
+        if let Some(name) = file_name.to_str().and_then(style_name) {
+            names.push(name.to_owned());
+        }
+    }
+    Some(names)
+}
+
+fn style_name(file_name: &str) -> Option<&str> {
+    if file_name.ends_with(".bst") {
+        Some(&file_name[0..file_name.len() - 4])
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, tex::Resolver};
+    use std::{collections::HashMap, path::PathBuf};
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let req = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_bibliography_styles(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let req = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_bibliography_styles(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[test]
+    fn resolver_styles() {
+        let mut files_by_name = HashMap::new();
+        files_by_name.insert("plainnat.bst".into(), PathBuf::from("plainnat.bst"));
+        files_by_name.insert("natbib.sty".into(), PathBuf::from("natbib.sty"));
+        let resolver = Resolver::new(files_by_name);
+        assert_eq!(resolver.bibliography_styles, vec!["plainnat"]);
+    }
+
+    #[test]
+    fn style_name_extension() {
+        assert_eq!(style_name("plainnat.bst"), Some("plainnat"));
+        assert_eq!(style_name("plainnat.sty"), None);
+    }
+}
@@ -2,37 +2,85 @@ use super::combinators::{self, ArgumentContext, Parameter};
 use crate::{
     completion::types::{Item, ItemData},
     feature::FeatureRequest,
-    protocol::{BibtexFormattingOptions, CompletionParams},
-    syntax::{bibtex, BibtexEntryTypeCategory, Structure, LANGUAGE_DATA},
+    protocol::CompletionParams,
+    syntax::{bibtex, latex, BibtexEntryTypeCategory, Structure, LANGUAGE_DATA},
     workspace::{Document, DocumentContent},
 };
 use once_cell::sync::Lazy;
 use petgraph::graph::NodeIndex;
 use regex::Regex;
+use std::collections::HashMap;
 
 pub async fn complete_latex_citations<'a>(
     req: &'a FeatureRequest<CompletionParams>,
     items: &mut Vec<Item<'a>>,
 ) {
-    let parameters = LANGUAGE_DATA.citation_commands.iter().map(|cmd| Parameter {
-        name: &cmd.name[1..],
+    let configured_commands = req.config.citation_commands.iter().map(|cmd| Parameter {
+        name: cmd.name.as_str(),
         index: cmd.index,
     });
 
+    let parameters = LANGUAGE_DATA
+        .citation_commands
+        .iter()
+        .map(|cmd| Parameter {
+            name: &cmd.name[1..],
+            index: cmd.index,
+        })
+        .chain(configured_commands);
+
     combinators::argument(req, parameters, |ctx| async move {
+        let mut citations = Vec::new();
         for doc in req.related() {
-            if let DocumentContent::Bibtex(tree) = &doc.content {
-                for entry_node in tree.children(tree.root) {
-                    if let Some(item) = make_item(ctx, doc, tree, entry_node) {
-                        items.push(item);
+            match &doc.content {
+                DocumentContent::Bibtex(tree) => {
+                    for entry_node in tree.children(tree.root) {
+                        if let Some(item) = make_item(ctx, doc, tree, entry_node) {
+                            citations.push(item);
+                        }
+                    }
+                }
+                DocumentContent::Latex(table) => {
+                    for bib_item in &table.bib_items {
+                        citations.push(make_bib_item(ctx, doc, table, *bib_item));
                     }
                 }
             }
         }
+        merge_duplicates(citations, items);
     })
     .await;
 }
 
+fn merge_duplicates<'a>(citations: Vec<Item<'a>>, items: &mut Vec<Item<'a>>) {
+    let mut indices_by_key = HashMap::new();
+    for item in citations {
+        let (key, uri) = match &item.data {
+            ItemData::Citation { key, uri, .. } => (*key, *uri),
+            _ => unreachable!(),
+        };
+
+        match indices_by_key.get(key) {
+            Some(&index) => {
+                if let ItemData::Citation {
+                    uri: original_uri,
+                    conflicts,
+                    ..
+                } = &mut items[index].data
+                {
+                    if *original_uri != uri && !conflicts.contains(&uri) {
+                        conflicts.push(uri);
+                    }
+                }
+            }
+            None => {
+                indices_by_key.insert(key, items.len());
+                items.push(item);
+            }
+        }
+    }
+}
+
 fn make_item<'a>(
     ctx: ArgumentContext,
     doc: &'a Document,
@@ -45,19 +93,17 @@ fn make_item<'a>(
     }
 
     let key = entry.key.as_ref()?.text();
-    let options = BibtexFormattingOptions::default();
-    let params = bibtex::FormattingParams {
-        insert_spaces: true,
-        tab_size: 4,
-        options: &options,
-    };
-    let entry_code = bibtex::format(tree, entry_node, params);
+    let mut builder = EntryTextBuilder::default();
+    builder.push(entry.ty.text().to_lowercase().as_ref());
+    builder.push(key);
+    tree.walk(&mut builder, entry_node);
     let text = format!(
         "{} {}",
         &key,
         WHITESPACE_REGEX
             .replace_all(
-                &entry_code
+                &builder
+                    .text
                     .replace('{', "")
                     .replace('}', "")
                     .replace(',', " ")
@@ -79,11 +125,93 @@ fn make_item<'a>(
             key,
             text,
             ty,
+            conflicts: Vec::new(),
         },
     );
     Some(item)
 }
 
+pub fn citation_detail(tree: &bibtex::Tree, key: &str) -> Option<String> {
+    let entry_node = tree.entry_by_key(key)?;
+    let ty = &tree.as_entry(entry_node)?.ty.text()[1..];
+    let desc = LANGUAGE_DATA.find_citation_detail(ty)?;
+    let values: Vec<_> = desc
+        .fields
+        .iter()
+        .filter_map(|name| field_text(tree, entry_node, name))
+        .collect();
+
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.join(", "))
+    }
+}
+
+fn field_text(tree: &bibtex::Tree, entry_node: NodeIndex, name: &str) -> Option<String> {
+    let field = tree.field_by_name(entry_node, name)?;
+    let mut builder = EntryTextBuilder::default();
+    tree.walk(&mut builder, field);
+    let text = WHITESPACE_REGEX
+        .replace_all(&builder.text.replace('{', "").replace('}', ""), " ")
+        .trim()
+        .to_owned();
+
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+fn make_bib_item<'a>(
+    ctx: ArgumentContext,
+    doc: &'a Document,
+    table: &'a latex::SymbolTable,
+    bib_item: latex::BibItem,
+) -> Item<'a> {
+    let key = bib_item.key(table).text();
+    let text = match table.print_group_content(bib_item.parent, latex::GroupKind::Options, 0) {
+        Some(label) => format!("{} {}", key, label),
+        None => key.into(),
+    };
+
+    Item::new(
+        ctx.range,
+        ItemData::Citation {
+            uri: &doc.uri,
+            key,
+            text,
+            ty: Structure::Entry(BibtexEntryTypeCategory::Misc),
+            conflicts: Vec::new(),
+        },
+    )
+}
+
+#[derive(Debug, Default)]
+struct EntryTextBuilder {
+    text: String,
+}
+
+impl EntryTextBuilder {
+    fn push(&mut self, text: &str) {
+        self.text.push(' ');
+        self.text.push_str(text);
+    }
+}
+
+impl<'a> bibtex::Visitor<'a> for EntryTextBuilder {
+    fn visit(&mut self, tree: &'a bibtex::Tree, node: NodeIndex) {
+        match &tree.graph[node] {
+            bibtex::Node::Field(field) => self.push(field.name.text().to_lowercase().as_ref()),
+            bibtex::Node::Word(word) => self.push(word.token.text()),
+            bibtex::Node::Command(cmd) => self.push(cmd.token.text()),
+            _ => (),
+        }
+        tree.walk(self, node);
+    }
+}
+
 static WHITESPACE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new("\\s+").unwrap());
 
 #[cfg(test)]
@@ -91,7 +219,7 @@ mod tests {
     use super::*;
     use crate::{
         feature::FeatureTester,
-        protocol::{Range, RangeExt},
+        protocol::{CitationCommand, LatexCompletionOptions, Range, RangeExt},
     };
     use indoc::indoc;
 
@@ -153,6 +281,30 @@ mod tests {
         assert_eq!(actual_items[0].range, Range::new_simple(1, 6, 1, 6));
     }
 
+    #[tokio::test]
+    async fn configured_command() {
+        let req = FeatureTester::new()
+            .file("main.tex", "\\addbibresource{main.bib}\n\\foocite{p. 1}{}")
+            .file("main.bib", "@article{foo,}")
+            .main("main.tex")
+            .position(1, 15)
+            .latex_completion(LatexCompletionOptions {
+                citation_commands: Some(vec![CitationCommand {
+                    name: "foocite".into(),
+                    index: 1,
+                }]),
+                ..LatexCompletionOptions::default()
+            })
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_citations(&req, &mut actual_items).await;
+
+        assert_eq!(actual_items.len(), 1);
+        assert_eq!(actual_items[0].data.label(), "foo");
+    }
+
     #[tokio::test]
     async fn empty_key() {
         let req = FeatureTester::new()
@@ -180,6 +332,54 @@ mod tests {
         assert_eq!(actual_items[0].range, Range::new_simple(1, 6, 1, 6));
     }
 
+    #[tokio::test]
+    async fn parent_directory_bibliography() {
+        let req = FeatureTester::new()
+            .file(
+                "chapters/foo.tex",
+                indoc!(
+                    r#"
+                        \addbibresource{../refs/bar.bib}
+                        \cite{}
+                    "#
+                ),
+            )
+            .file("refs/bar.bib", "@article{foo,}")
+            .file("bar.bib", "@article{bar,}")
+            .main("chapters/foo.tex")
+            .position(1, 6)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_citations(&req, &mut actual_items).await;
+
+        assert_eq!(actual_items.len(), 1);
+        assert_eq!(actual_items[0].data.label(), "foo");
+    }
+
+    #[tokio::test]
+    async fn absolute_bibliography() {
+        let path = FeatureTester::uri("refs/bar.bib").to_file_path().unwrap();
+        let req = FeatureTester::new()
+            .file(
+                "chapters/foo.tex",
+                format!("\\addbibresource{{{}}}\n\\cite{{}}", path.to_string_lossy()),
+            )
+            .file("refs/bar.bib", "@article{foo,}")
+            .file("bar.bib", "@article{bar,}")
+            .main("chapters/foo.tex")
+            .position(1, 6)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_citations(&req, &mut actual_items).await;
+
+        assert_eq!(actual_items.len(), 1);
+        assert_eq!(actual_items[0].data.label(), "foo");
+    }
+
     #[tokio::test]
     async fn single_key() {
         let req = FeatureTester::new()
@@ -234,6 +434,127 @@ mod tests {
         assert_eq!(actual_items[0].range, Range::new_simple(1, 10, 1, 10));
     }
 
+    #[tokio::test]
+    async fn bib_item() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \cite{}
+                        \begin{thebibliography}{9}
+                        \bibitem[Foo]{foo} Foo Bar
+                        \bibitem{bar} Baz Qux
+                        \end{thebibliography}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(0, 6)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_citations(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items.iter().map(|item| item.data.label()).collect();
+        assert_eq!(actual_labels, vec!["foo", "bar"]);
+        assert_eq!(actual_items[0].range, Range::new_simple(0, 6, 0, 6));
+    }
+
+    #[tokio::test]
+    async fn duplicate_key() {
+        let req = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \addbibresource{bar.bib}
+                        \addbibresource{baz.bib}
+                        \cite{}
+                    "#
+                ),
+            )
+            .file("bar.bib", "@article{foo,}\n@article{bar,}")
+            .file("baz.bib", "@book{foo,}")
+            .main("foo.tex")
+            .position(2, 6)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_citations(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items.iter().map(|item| item.data.label()).collect();
+        assert_eq!(actual_labels, vec!["foo", "bar"]);
+        match &actual_items[0].data {
+            ItemData::Citation { conflicts, .. } => {
+                assert_eq!(*conflicts, vec![&FeatureTester::uri("baz.bib")]);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn entry_type_detail() {
+        let tree = bibtex::open(indoc!(
+            r#"
+                @article{foo, author = {Foo Bar}, journal = {Baz}, year = 2020}
+                @book{bar, author = {Qux}, publisher = {{Foo Press}}}
+            "#
+        ));
+
+        assert_eq!(
+            citation_detail(&tree, "foo"),
+            Some("Foo Bar, 2020, Baz".into())
+        );
+        assert_eq!(citation_detail(&tree, "bar"), Some("Qux, Foo Press".into()));
+        assert_eq!(citation_detail(&tree, "baz"), None);
+    }
+
+    #[tokio::test]
+    async fn large_bibliography() {
+        let bibliography: String = (0..50_000)
+            .map(|i| {
+                format!(
+                    "@article{{foo{}, author = {{Foo Bar}}, title = {{Baz Qux}}, year = 2020}}\n",
+                    i
+                )
+            })
+            .collect();
+
+        let req = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \addbibresource{bar.bib}
+                        \cite{}
+                    "#
+                ),
+            )
+            .file("bar.bib", bibliography)
+            .main("foo.tex")
+            .position(1, 6)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_citations(&req, &mut actual_items).await;
+
+        assert_eq!(actual_items.len(), 50_000);
+        assert_eq!(actual_items[0].data.label(), "foo0");
+        match &actual_items[0].data {
+            ItemData::Citation { text, .. } => {
+                assert_eq!(
+                    text,
+                    "foo0 @article foo0 author Foo Bar title Baz Qux year 2020"
+                )
+            }
+            _ => unreachable!(),
+        }
+    }
+
     #[tokio::test]
     async fn outside_cite() {
         let req = FeatureTester::new()
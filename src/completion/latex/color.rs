@@ -1,10 +1,13 @@
 use super::combinators::{self, Parameter};
 use crate::{
+    color::{builtin_color, ColorModel},
     completion::types::{Item, ItemData},
     feature::FeatureRequest,
     protocol::CompletionParams,
-    syntax::LANGUAGE_DATA,
+    syntax::{latex, LANGUAGE_DATA},
+    workspace::DocumentContent,
 };
+use std::collections::HashSet;
 
 pub async fn complete_latex_colors<'a>(
     req: &'a FeatureRequest<CompletionParams>,
@@ -16,14 +19,52 @@ pub async fn complete_latex_colors<'a>(
     });
 
     combinators::argument(req, parameters, |ctx| async move {
+        if let DocumentContent::Latex(table) = &req.current().content {
+            if table
+                .extract_group(ctx.node, latex::GroupKind::Options, 0)
+                .is_some()
+            {
+                return;
+            }
+        }
+
+        let mut names: HashSet<&str> = HashSet::new();
+        for doc in req.related() {
+            if let DocumentContent::Latex(table) = &doc.content {
+                for def in &table.color_definitions {
+                    let name = def.name(&table).text();
+                    if names.insert(name) {
+                        let detail = definition_detail(&table, *def);
+                        let item = Item::new(ctx.range, ItemData::Color { name, detail });
+                        items.push(item);
+                    }
+                }
+            }
+        }
+
         for name in &LANGUAGE_DATA.colors {
-            let item = Item::new(ctx.range, ItemData::Color { name });
-            items.push(item);
+            if names.insert(name) {
+                let detail = builtin_color(name)
+                    .map(|color| format!("{{rgb}}{{{}}}", ColorModel::Rgb.encode(&color)));
+                let item = Item::new(ctx.range, ItemData::Color { name, detail });
+                items.push(item);
+            }
         }
     })
     .await;
 }
 
+fn definition_detail(table: &latex::SymbolTable, def: latex::ColorDefinition) -> Option<String> {
+    let spec = table.print_group_content(def.parent, latex::GroupKind::Group, def.spec_index)?;
+    match def.model_index {
+        Some(index) => {
+            let model = table.print_group_content(def.parent, latex::GroupKind::Group, index)?;
+            Some(format!("{{{}}}{{{}}}", model, spec))
+        }
+        None => Some(spec),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -31,6 +72,7 @@ mod tests {
         feature::FeatureTester,
         protocol::{Range, RangeExt},
     };
+    use indoc::indoc;
 
     #[tokio::test]
     async fn empty_latex_document() {
@@ -88,4 +130,73 @@ mod tests {
 
         assert!(actual_items.is_empty());
     }
+
+    #[tokio::test]
+    async fn inside_color_with_model() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\color[rgb]{}"#)
+            .main("main.tex")
+            .position(0, 12)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+        complete_latex_colors(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn defined_colors() {
+        let req = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \include{bar}
+                        \textcolor{}{qux}
+                    "#
+                ),
+            )
+            .file(
+                "bar.tex",
+                indoc!(
+                    r#"
+                        \definecolor{foo}{RGB}{255, 0, 0}
+                        \colorlet{baz}{foo!50}
+                        \definecolor{red}{HTML}{FF0000}
+                    "#
+                ),
+            )
+            .main("foo.tex")
+            .position(1, 11)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+        complete_latex_colors(&req, &mut actual_items).await;
+
+        let details: Vec<_> = actual_items
+            .iter()
+            .take(4)
+            .map(|item| match &item.data {
+                ItemData::Color { name, detail } => (*name, detail.as_deref()),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(
+            details,
+            vec![
+                ("foo", Some("{RGB}{255, 0, 0}")),
+                ("baz", Some("foo!50")),
+                ("red", Some("{HTML}{FF0000}")),
+                ("black", Some("{rgb}{0,0,0}")),
+            ]
+        );
+        assert_eq!(
+            actual_items
+                .iter()
+                .filter(|item| item.data.label() == "red")
+                .count(),
+            1
+        );
+    }
 }
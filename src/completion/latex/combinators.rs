@@ -33,8 +33,33 @@ pub struct ArgumentContext<'a> {
 }
 
 pub async fn argument<'a, I, E, F>(
+    req: &'a FeatureRequest<CompletionParams>,
+    parameters: I,
+    execute: E,
+) where
+    I: Iterator<Item = Parameter<'a>>,
+    E: FnOnce(ArgumentContext<'a>) -> F,
+    F: Future<Output = ()>,
+{
+    group_argument(req, parameters, latex::GroupKind::Group, execute).await;
+}
+
+pub async fn options_argument<'a, I, E, F>(
+    req: &'a FeatureRequest<CompletionParams>,
+    parameters: I,
+    execute: E,
+) where
+    I: Iterator<Item = Parameter<'a>>,
+    E: FnOnce(ArgumentContext<'a>) -> F,
+    F: Future<Output = ()>,
+{
+    group_argument(req, parameters, latex::GroupKind::Options, execute).await;
+}
+
+async fn group_argument<'a, I, E, F>(
     req: &'a FeatureRequest<CompletionParams>,
     mut parameters: I,
+    group_kind: latex::GroupKind,
     execute: E,
 ) where
     I: Iterator<Item = Parameter<'a>>,
@@ -49,9 +74,7 @@ pub async fn argument<'a, I, E, F>(
                 .by_ref()
                 .filter(|param| param.name == &cmd.name.text()[1..])
             {
-                if let Some(args_node) =
-                    table.extract_group(node, latex::GroupKind::Group, parameter.index)
-                {
+                if let Some(args_node) = table.extract_group(node, group_kind, parameter.index) {
                     let args = table.as_group(args_node).unwrap();
                     if args.right.is_some() && !args.range().contains_exclusive(pos) {
                         continue;
@@ -3,6 +3,7 @@ use crate::{
     completion::types::{Item, ItemData},
     feature::FeatureRequest,
     protocol::CompletionParams,
+    syntax::{SyntaxNode, LANGUAGE_DATA},
 };
 
 pub async fn complete_latex_component_commands<'a>(
@@ -13,24 +14,48 @@ pub async fn complete_latex_component_commands<'a>(
         let table = req.current().content.as_latex().unwrap();
         let cmd = table.as_command(cmd_node).unwrap();
         let range = cmd.short_name_range();
+        let math_mode = table.is_math_mode(cmd.start());
 
         for comp in req.view.components() {
-            for cmd in &comp.commands {
+            for cmd in comp
+                .commands
+                .iter()
+                .filter(|cmd| !is_spacing_command(&cmd.name, math_mode))
+            {
                 items.push(Item::new(
                     range,
                     ItemData::ComponentCommand {
                         name: &cmd.name,
-                        image: cmd.image.as_deref(),
                         glyph: cmd.glyph.as_deref(),
                         file_names: &comp.file_names,
                     },
                 ));
             }
         }
+
+        if table.is_at_letter(cmd.start()) {
+            for name in &LANGUAGE_DATA.internal_commands {
+                items.push(Item::new(
+                    range,
+                    ItemData::ComponentCommand {
+                        name,
+                        glyph: None,
+                        file_names: &[],
+                    },
+                ));
+            }
+        }
     })
     .await;
 }
 
+fn is_spacing_command(name: &str, math_mode: bool) -> bool {
+    LANGUAGE_DATA
+        .spacing_commands
+        .iter()
+        .any(|cmd| cmd.name == name && cmd.math == math_mode)
+}
+
 pub async fn complete_latex_component_environments<'a>(
     req: &'a FeatureRequest<CompletionParams>,
     items: &mut Vec<Item<'a>>,
@@ -151,6 +176,101 @@ mod tests {
         assert_eq!(actual_items[0].range, Range::new_simple(0, 1, 0, 4));
     }
 
+    #[tokio::test]
+    async fn spacing_command_inside_math() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"$a \q b$"#)
+            .main("main.tex")
+            .position(0, 5)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_component_commands(&req, &mut actual_items).await;
+
+        assert!(actual_items.iter().all(|item| item.data.label() != "quad"));
+    }
+
+    #[tokio::test]
+    async fn spacing_command_outside_math() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"a \q b"#)
+            .main("main.tex")
+            .position(0, 4)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_component_commands(&req, &mut actual_items).await;
+
+        assert!(actual_items.iter().any(|item| item.data.label() == "quad"));
+    }
+
+    #[tokio::test]
+    async fn command_internal_package() {
+        let req = FeatureTester::new()
+            .file("foo.sty", r#"\g@add"#)
+            .main("foo.sty")
+            .position(0, 6)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_component_commands(&req, &mut actual_items).await;
+
+        let item = actual_items
+            .iter()
+            .find(|item| item.data.label() == "g@addto@macro")
+            .unwrap();
+        assert_eq!(item.range, Range::new_simple(0, 1, 0, 6));
+    }
+
+    #[tokio::test]
+    async fn command_internal_make_at_letter() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \makeatletter
+                        \@name
+                        \makeatother
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(1, 5)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_component_commands(&req, &mut actual_items).await;
+
+        let item = actual_items
+            .iter()
+            .find(|item| item.data.label() == "@namedef")
+            .unwrap();
+        assert_eq!(item.range, Range::new_simple(1, 1, 1, 6));
+    }
+
+    #[tokio::test]
+    async fn command_internal_document() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\g"#)
+            .main("main.tex")
+            .position(0, 2)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_component_commands(&req, &mut actual_items).await;
+
+        assert!(!actual_items.is_empty());
+        assert!(actual_items
+            .iter()
+            .all(|item| item.data.label() != "g@addto@macro"));
+    }
+
     #[tokio::test]
     async fn command_word() {
         let req = FeatureTester::new()
@@ -216,6 +336,31 @@ mod tests {
             .any(|item| item.data.label() == "lipsum"));
     }
 
+    #[tokio::test]
+    async fn command_required_package() {
+        let req = FeatureTester::new()
+            .file(
+                "foo.sty",
+                indoc!(
+                    r#"
+                        \RequirePackage[foo]{lipsum}
+                        \lips
+                    "#
+                ),
+            )
+            .main("foo.sty")
+            .position(1, 2)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_component_commands(&req, &mut actual_items).await;
+
+        assert!(actual_items
+            .iter()
+            .any(|item| item.data.label() == "lipsum"));
+    }
+
     #[tokio::test]
     async fn command_class() {
         let req = FeatureTester::new()
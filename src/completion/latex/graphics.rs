@@ -0,0 +1,101 @@
+use super::combinators::{self, Parameter};
+use crate::{
+    completion::types::{Item, ItemData},
+    feature::FeatureRequest,
+    protocol::CompletionParams,
+    syntax::{CharStream, LANGUAGE_DATA},
+};
+use std::iter;
+
+pub async fn complete_latex_graphics_options<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    let param = Parameter {
+        name: "includegraphics",
+        index: 0,
+    };
+    combinators::options_argument(req, iter::once(param), |ctx| async move {
+        let word = CharStream::extract(&req.current().text, ctx.range);
+        if word.contains('=') {
+            return;
+        }
+
+        for name in &LANGUAGE_DATA.graphics_options {
+            let item = Item::new(ctx.range, ItemData::GraphicsOption { name });
+            items.push(item);
+        }
+    })
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        feature::FeatureTester,
+        protocol::{Range, RangeExt},
+    };
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let req = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_graphics_options(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let req = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_graphics_options(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn inside_options() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\includegraphics[wi]{foo}"#)
+            .main("main.tex")
+            .position(0, 19)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_graphics_options(&req, &mut actual_items).await;
+
+        assert!(actual_items.iter().any(|item| item.data.label() == "width"));
+        assert!(actual_items.iter().any(|item| item.data.label() == "clip"));
+        assert_eq!(actual_items[0].range, Range::new_simple(0, 17, 0, 19));
+    }
+
+    #[tokio::test]
+    async fn inside_path() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\includegraphics[]{foo}"#)
+            .main("main.tex")
+            .position(0, 20)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_graphics_options(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+}
@@ -185,4 +185,21 @@ mod tests {
             .iter()
             .any(|item| item.data.label() == "amsmath"));
     }
+
+    #[tokio::test]
+    async fn require_package_with_options() {
+        let req = FeatureTester::new()
+            .file("foo.sty", r#"\RequirePackage[foo]{}"#)
+            .main("foo.sty")
+            .position(0, 21)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_packages(&req, &mut actual_items).await;
+
+        assert!(actual_items
+            .iter()
+            .any(|item| item.data.label() == "amsmath"));
+    }
 }
@@ -80,19 +80,10 @@ fn current_dir(
     table: &latex::SymbolTable,
     cmd_node: AstNodeIndex,
 ) -> Option<PathBuf> {
-    let mut path = req
-        .options
-        .latex
-        .as_ref()
-        .and_then(|latex| latex.root_directory.as_ref())
-        .map_or_else(
-            || {
-                let mut path = req.current().uri.to_file_path().unwrap();
-                path.pop();
-                path
-            },
-            Clone::clone,
-        );
+    let mut path = latex::include_base_url(&req.current().uri, &req.options, &req.current_dir)?
+        .to_file_path()
+        .ok()?;
+    path.pop();
 
     path = PathBuf::from(path.to_str()?.replace('\\', "/"));
     if let Some(include) = table.extract_word(cmd_node, latex::GroupKind::Group, 0) {
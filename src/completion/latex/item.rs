@@ -0,0 +1,175 @@
+use super::combinators::{self, Parameter};
+use crate::{
+    completion::types::{Item, ItemData},
+    feature::FeatureRequest,
+    protocol::{CompletionParams, Position, RangeExt},
+    syntax::{latex, SyntaxNode, LANGUAGE_DATA},
+    workspace::DocumentContent,
+};
+use std::iter;
+
+pub async fn complete_latex_item_labels<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    if !is_enumitem_document(req) {
+        return;
+    }
+
+    let param = Parameter {
+        name: "item",
+        index: 0,
+    };
+    combinators::options_argument(req, iter::once(param), |ctx| async move {
+        let table = req.current().content.as_latex().unwrap();
+        if is_list_item(req, table, table[ctx.node].start()) {
+            for name in &LANGUAGE_DATA.item_labels {
+                let item = Item::new(ctx.range, ItemData::ItemLabel { name });
+                items.push(item);
+            }
+        }
+    })
+    .await;
+}
+
+fn is_enumitem_document(req: &FeatureRequest<CompletionParams>) -> bool {
+    req.related().iter().any(|doc| match &doc.content {
+        DocumentContent::Latex(table) => table.components.iter().any(|comp| comp == "enumitem.sty"),
+        DocumentContent::Bibtex(_) => false,
+    })
+}
+
+fn is_list_item(
+    req: &FeatureRequest<CompletionParams>,
+    table: &latex::SymbolTable,
+    pos: Position,
+) -> bool {
+    let name = match table
+        .environments
+        .iter()
+        .filter(|env| env.range(&table).contains_exclusive(pos))
+        .max_by_key(|env| env.range(&table).start)
+        .and_then(|env| env.left.name(&table))
+    {
+        Some(name) => name.text(),
+        None => return false,
+    };
+
+    LANGUAGE_DATA
+        .enum_environments
+        .iter()
+        .chain(&LANGUAGE_DATA.list_environments)
+        .any(|env| env == name)
+        || req
+            .related()
+            .iter()
+            .filter_map(|doc| doc.content.as_latex())
+            .flat_map(|table| {
+                table
+                    .list_definitions
+                    .iter()
+                    .map(move |def| def.name(&table).text())
+            })
+            .any(|def| def == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        feature::FeatureTester,
+        protocol::{Range, RangeExt},
+    };
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let req = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_item_labels(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn inside_custom_list() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \usepackage{enumitem}
+                        \newlist{steps}{enumerate}{1}
+                        \begin{steps}
+                            \item[]
+                        \end{steps}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(3, 10)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_item_labels(&req, &mut actual_items).await;
+
+        assert!(!actual_items.is_empty());
+        assert_eq!(actual_items[0].range, Range::new_simple(3, 10, 3, 10));
+    }
+
+    #[tokio::test]
+    async fn outside_brackets() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \usepackage{enumitem}
+                        \begin{itemize}
+                            \item[] Foo
+                        \end{itemize}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(2, 13)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_item_labels(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn without_enumitem() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \begin{itemize}
+                            \item[]
+                        \end{itemize}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(1, 10)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_item_labels(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+}
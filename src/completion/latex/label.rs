@@ -1,96 +1,345 @@
-use super::combinators::{self, ArgumentContext, Parameter};
+use super::{
+    combinators::{self, ArgumentContext, Parameter},
+    label_name::{ENVIRONMENT_PREFIXES, SEPARATORS},
+};
 use crate::{
-    completion::types::{Item, ItemData},
+    completion::{
+        types::{Item, ItemData},
+        util::current_word,
+    },
     feature::{DocumentView, FeatureRequest},
-    outline::{Outline, OutlineContext, OutlineContextItem},
-    protocol::{CompletionParams, RangeExt},
+    outline::{
+        cleveref_capitalization, find_label_numbering, CrefNames, Outline, OutlineCaptionKind,
+        OutlineContext, OutlineContextItem,
+    },
+    protocol::{CompletionParams, LabelSortOrder, Position, Range, RangeExt, TextEdit, Uri},
     syntax::{
-        latex, LatexLabelKind, LatexLabelReferenceSource, Structure, SyntaxNode, LANGUAGE_DATA,
+        latex, CharStream, LatexLabelKind, LatexLabelReferenceSource, Structure, SyntaxNode,
+        LANGUAGE_DATA,
     },
-    workspace::DocumentContent,
+    workspace::{Document, DocumentContent},
 };
-use std::sync::Arc;
+use fuzzy_matcher::skim::fuzzy_match;
+use std::{collections::HashMap, sync::Arc};
 
 pub async fn complete_latex_labels<'a>(
     req: &'a FeatureRequest<CompletionParams>,
     items: &mut Vec<Item<'a>>,
 ) {
-    let parameters = LANGUAGE_DATA
+    combinators::argument(req, label_parameters(req, false), |ctx| {
+        complete_labels(req, ctx, false, &mut *items)
+    })
+    .await;
+
+    combinators::options_argument(req, label_parameters(req, true), |ctx| {
+        complete_labels(req, ctx, true, &mut *items)
+    })
+    .await;
+}
+
+fn label_parameters<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    optional: bool,
+) -> impl Iterator<Item = Parameter<'a>> {
+    let configured_commands = req
+        .config
+        .reference_commands
+        .iter()
+        .filter(move |_| !optional)
+        .map(|name| Parameter {
+            name: name.as_str(),
+            index: 0,
+        });
+
+    LANGUAGE_DATA
         .label_commands
         .iter()
-        .filter(|cmd| cmd.kind.is_reference())
+        .filter(move |cmd| cmd.kind.is_reference() && cmd.optional == optional)
         .map(|cmd| Parameter {
             name: &cmd.name[1..],
             index: cmd.index,
-        });
+        })
+        .chain(configured_commands)
+}
 
-    combinators::argument(req, parameters, |ctx| async move {
-        let source = find_source(ctx);
-        for doc in req.related() {
+async fn complete_labels<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    ctx: ArgumentContext<'a>,
+    optional: bool,
+    items: &mut Vec<Item<'a>>,
+) {
+    let config = &req.config.completion;
+    let sort_by_proximity = config.sort_labels_by_proximity;
+    let group_by_file = config.group_labels_by_file;
+    let sort_by_document = config.label_sort_order == LabelSortOrder::Document;
+
+    let source = find_source(ctx, optional);
+    let cleveref = cleveref_capitalization(ctx.parameter.name);
+    let pos = req.params.text_document_position.position;
+    let current_table = req.current().content.as_latex().unwrap();
+    let cmd_start = current_table[ctx.node].start();
+    let zref = current_table
+        .as_command(ctx.node)
+        .map_or(false, |cmd| latex::is_zref_command(cmd.name.text()));
+    let insert_prefix = config.insert_reference_prefix
+        && ctx.parameter.name == "ref"
+        && !has_reference_prefix(&req.current().text, cmd_start);
+    let cref_names = CrefNames::analyze(&req.view);
+    let typed_prefix = find_typed_prefix(req, ctx);
+    let fallback_docs = find_fallback_documents(req, source);
+    let document_order = if sort_by_document {
+        find_document_order(req)
+    } else {
+        HashMap::new()
+    };
+    for (doc, fallback) in req
+        .related()
+        .iter()
+        .map(|doc| (doc, false))
+        .chain(fallback_docs.into_iter().map(|doc| (doc, true)))
+    {
+        // Unrelated documents are not analyzed to keep the fallback cheap.
+        let structure = if fallback {
+            None
+        } else {
             let snapshot = Arc::clone(&req.view.snapshot);
             let view =
                 DocumentView::analyze(snapshot, Arc::clone(&doc), &req.options, &req.current_dir);
             let outline = Outline::analyze(&view, &req.options, &req.current_dir);
+            Some((view, outline))
+        };
+        let file = Some(&doc.uri).filter(|_| group_by_file || fallback);
+
+        if let DocumentContent::Latex(table) = &doc.content {
+            for label in table
+                .label_definitions()
+                .filter(|label| label.is_zref(&table) == zref)
+                .filter(|label| is_included(&table, label, source))
+            {
+                let outline_ctx = structure
+                    .as_ref()
+                    .and_then(|(view, outline)| OutlineContext::parse(view, outline, *label));
 
-            if let DocumentContent::Latex(table) = &doc.content {
-                for label in table
-                    .labels
-                    .iter()
-                    .filter(|label| label.kind == LatexLabelKind::Definition)
-                    .filter(|label| is_included(&table, label, source))
-                {
-                    let outline_ctx = OutlineContext::parse(&view, &outline, *label);
-
-                    let kind = match outline_ctx.as_ref().map(|ctx| &ctx.item) {
-                        Some(OutlineContextItem::Section { .. }) => Structure::Section,
-                        Some(OutlineContextItem::Caption { .. }) => Structure::Float,
-                        Some(OutlineContextItem::Theorem { .. }) => Structure::Theorem,
-                        Some(OutlineContextItem::Equation) => Structure::Equation,
-                        Some(OutlineContextItem::Item) => Structure::Item,
-                        None => Structure::Label,
+                let kind = match outline_ctx.as_ref().map(|ctx| &ctx.item) {
+                    Some(OutlineContextItem::Section { .. }) => Structure::Section,
+                    Some(OutlineContextItem::Caption { .. }) => Structure::Float,
+                    Some(OutlineContextItem::Theorem { .. }) => Structure::Theorem,
+                    Some(OutlineContextItem::Equation) => Structure::Equation,
+                    Some(OutlineContextItem::Item) => Structure::Item,
+                    None => Structure::Label,
+                };
+
+                let preferred = match (&typed_prefix, &outline_ctx) {
+                    (Some(prefix), Some(context)) => has_prefix_type(prefix, &context.item),
+                    _ => false,
+                };
+
+                let proximity = if sort_by_proximity && doc.uri == req.current().uri {
+                    Some(table[label.parent].start().line.abs_diff(pos.line))
+                } else {
+                    None
+                };
+
+                for name in label.names(&table) {
+                    let number = structure
+                        .as_ref()
+                        .filter(|_| outline_ctx.is_none())
+                        .and_then(|(view, _)| find_label_numbering(view, name.text()))
+                        .map(|numbering| numbering.number.clone());
+
+                    let header = outline_ctx
+                        .as_ref()
+                        .and_then(|ctx| match cleveref {
+                            Some(capitalize) => ctx.cleveref_detail(capitalize, &cref_names),
+                            None => ctx.detail(),
+                        })
+                        .or_else(|| number.clone());
+                    let footer = outline_ctx.as_ref().and_then(|ctx| match &ctx.item {
+                        OutlineContextItem::Caption { text, .. } => Some(text.clone()),
+                        _ => None,
+                    });
+
+                    let text = match (&outline_ctx, &number) {
+                        (Some(ctx), _) => format!("{} {}", name.text(), ctx.reference()),
+                        (None, Some(number)) => format!("{} {}", name.text(), number),
+                        (None, None) => name.text().into(),
                     };
 
-                    for name in label.names(&table) {
-                        let header = outline_ctx.as_ref().and_then(|ctx| ctx.detail());
-                        let footer = outline_ctx.as_ref().and_then(|ctx| match &ctx.item {
-                            OutlineContextItem::Caption { text, .. } => Some(text.clone()),
-                            _ => None,
+                    let prefix_edit = outline_ctx
+                        .as_ref()
+                        .and_then(OutlineContext::reference_prefix)
+                        .filter(|_| insert_prefix)
+                        .map(|prefix| {
+                            let range = Range::new(cmd_start, cmd_start);
+                            TextEdit::new(range, format!("{}~", prefix))
                         });
 
-                        let text = outline_ctx
-                            .as_ref()
-                            .map(|ctx| format!("{} {}", name.text(), ctx.reference()))
-                            .unwrap_or_else(|| name.text().into());
-
-                        let item = Item::new(
-                            ctx.range,
-                            ItemData::Label {
-                                name: name.text(),
-                                kind,
-                                header,
-                                footer,
-                                text,
-                            },
-                        );
-                        items.push(item);
-                    }
+                    let item = Item::new(
+                        ctx.range,
+                        ItemData::Label {
+                            name: name.text(),
+                            kind,
+                            header,
+                            footer,
+                            text,
+                            proximity,
+                            prefix_edit,
+                            file,
+                            preferred,
+                            order: document_order.get(&(&doc.uri, name.start())).copied(),
+                            fallback,
+                        },
+                    );
+                    items.push(item);
                 }
             }
         }
-    })
-    .await;
+    }
+}
+
+fn find_document_order<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+) -> HashMap<(&'a Uri, Position), usize> {
+    let include_order = req.view.include_order();
+    let mut keys = Vec::new();
+    for doc in req.related() {
+        if let DocumentContent::Latex(table) = &doc.content {
+            for label in table.label_definitions() {
+                for name in label.names(&table) {
+                    let key = include_order.key(&doc.uri, name.start());
+                    keys.push((key, &doc.uri, name.start()));
+                }
+            }
+        }
+    }
+
+    keys.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+    keys.into_iter()
+        .enumerate()
+        .map(|(order, (_, uri, pos))| ((uri, pos), order))
+        .collect()
 }
 
-fn find_source(ctx: ArgumentContext) -> LatexLabelReferenceSource {
+fn find_fallback_documents<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    source: LatexLabelReferenceSource,
+) -> Vec<&'a Arc<Document>> {
+    let pattern = current_word(req).unwrap_or_default();
+    let has_related_match = req
+        .related()
+        .iter()
+        .filter_map(|doc| doc.content.as_latex())
+        .any(|table| {
+            table
+                .label_definitions()
+                .filter(|label| is_included(&table, label, source))
+                .flat_map(|label| label.names(&table))
+                .any(|name| fuzzy_match(name.text(), &pattern).is_some())
+        });
+
+    if has_related_match {
+        return Vec::new();
+    }
+
+    let project_dir = find_project_dir(req);
+    req.snapshot()
+        .0
+        .iter()
+        .filter(|doc| {
+            project_dir
+                .as_ref()
+                .map_or(true, |dir| doc.uri.as_str().starts_with(dir))
+        })
+        .filter(|doc| req.related().iter().all(|related| related.uri != doc.uri))
+        .collect()
+}
+
+fn find_project_dir(req: &FeatureRequest<CompletionParams>) -> Option<String> {
+    let dir = match &req.config.root_file {
+        Some(root_file) => req.current_dir.join(root_file).parent()?.to_owned(),
+        None => req.root_dir.clone()?,
+    };
+    let uri = Uri::from_file_path(dir).ok()?;
+    Some(format!("{}/", uri.as_str().trim_end_matches('/')))
+}
+
+fn find_typed_prefix(
+    req: &FeatureRequest<CompletionParams>,
+    ctx: ArgumentContext,
+) -> Option<String> {
+    let pos = req.params.text_document_position.position;
+    let typed = CharStream::extract(&req.current().text, Range::new(ctx.range.start, pos));
+    let index = typed.find(SEPARATORS)?;
+    Some(typed[..index].to_owned())
+}
+
+fn has_prefix_type(prefix: &str, item: &OutlineContextItem) -> bool {
+    match (prefix, item) {
+        (
+            _,
+            OutlineContextItem::Caption {
+                kind: Some(kind), ..
+            },
+        ) => ENVIRONMENT_PREFIXES.iter().any(|(env_name, env_prefix)| {
+            *env_prefix == prefix && OutlineCaptionKind::parse(env_name) == Some(*kind)
+        }),
+        ("part", OutlineContextItem::Section { .. })
+        | ("chap", OutlineContextItem::Section { .. })
+        | ("sec", OutlineContextItem::Section { .. }) => true,
+        ("thm", OutlineContextItem::Theorem { .. })
+        | ("lem", OutlineContextItem::Theorem { .. })
+        | ("def", OutlineContextItem::Theorem { .. }) => true,
+        ("eq", OutlineContextItem::Equation) => true,
+        ("item", OutlineContextItem::Item) => true,
+        _ => false,
+    }
+}
+
+const REFERENCE_WORDS: &[&str] = &[
+    "fig",
+    "figure",
+    "tab",
+    "table",
+    "lst",
+    "listing",
+    "alg",
+    "algorithm",
+    "eq",
+    "equation",
+    "sec",
+    "section",
+    "chap",
+    "chapter",
+    "thm",
+    "theorem",
+];
+
+fn has_reference_prefix(text: &str, cmd_start: Position) -> bool {
+    let start = Position::new(cmd_start.line.saturating_sub(1), 0);
+    let prefix = CharStream::extract(text, Range::new(start, cmd_start));
+    let prefix = prefix.trim_end().trim_end_matches('\\').trim_end();
+    if prefix.ends_with('~') {
+        return true;
+    }
+
+    prefix
+        .split_whitespace()
+        .last()
+        .map(|word| word.trim_end_matches('.').to_lowercase())
+        .map_or(false, |word| REFERENCE_WORDS.contains(&word.as_str()))
+}
+
+fn find_source(ctx: ArgumentContext, optional: bool) -> LatexLabelReferenceSource {
     match LANGUAGE_DATA
         .label_commands
         .iter()
+        .filter(|cmd| cmd.optional == optional)
         .find(|cmd| &cmd.name[1..] == ctx.parameter.name && cmd.index == ctx.parameter.index)
         .map(|cmd| cmd.kind)
-        .unwrap()
     {
-        LatexLabelKind::Definition => unreachable!(),
-        LatexLabelKind::Reference(source) => source,
+        Some(LatexLabelKind::Definition) => unreachable!(),
+        Some(LatexLabelKind::Reference(source)) => source,
+        None => LatexLabelReferenceSource::Everything,
     }
 }
 
@@ -113,7 +362,7 @@ fn is_included(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::feature::FeatureTester;
+    use crate::{feature::FeatureTester, protocol::LatexCompletionOptions};
     use indoc::indoc;
 
     #[tokio::test]
@@ -176,6 +425,29 @@ mod tests {
         assert_eq!(actual_labels, vec!["foo", "bar"]);
     }
 
+    #[tokio::test]
+    async fn configured_reference_command() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\label{foo}\fooref{}"#)
+            .main("main.tex")
+            .position(0, 19)
+            .latex_completion(LatexCompletionOptions {
+                reference_commands: Some(vec!["\\fooref".into()]),
+                ..LatexCompletionOptions::default()
+            })
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_labels(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items
+            .into_iter()
+            .map(|item| item.data.label().to_owned())
+            .collect();
+        assert_eq!(actual_labels, vec!["foo"]);
+    }
+
     #[tokio::test]
     async fn outside_of_ref() {
         let req = FeatureTester::new()
@@ -200,6 +472,56 @@ mod tests {
         assert!(actual_items.is_empty());
     }
 
+    #[tokio::test]
+    async fn inside_of_hyperref_options() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \label{foo}\label{bar}
+                        \hyperref[]{baz}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(1, 10)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_labels(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items
+            .into_iter()
+            .map(|item| item.data.label().to_owned())
+            .collect();
+        assert_eq!(actual_labels, vec!["foo", "bar"]);
+    }
+
+    #[tokio::test]
+    async fn inside_of_hyperref_text() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \label{foo}
+                        \hyperref[foo]{}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(1, 16)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_labels(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
     #[tokio::test]
     async fn eqref() {
         let req = FeatureTester::new()
@@ -227,4 +549,544 @@ mod tests {
 
         assert_eq!(actual_labels, vec!["foo"]);
     }
+
+    #[tokio::test]
+    async fn cleveref() {
+        let text = indoc!(
+            r#"
+                \begin{figure}\caption{Foo}\label{fig:foo}\end{figure}
+                \begin{equation}\label{eq:bar}\end{equation}
+                \cref{}
+                \Cref{}
+            "#
+        );
+
+        let mut actual_details = Vec::new();
+        for line in &[2, 3] {
+            let req = FeatureTester::new()
+                .file("main.tex", text)
+                .main("main.tex")
+                .position(*line, 6)
+                .test_completion_request()
+                .await;
+            let mut actual_items = Vec::new();
+
+            complete_latex_labels(&req, &mut actual_items).await;
+
+            for item in actual_items {
+                if let ItemData::Label { header, .. } = item.data {
+                    actual_details.push(header);
+                }
+            }
+        }
+
+        assert_eq!(
+            actual_details,
+            vec![
+                Some("fig.".to_owned()),
+                Some("eq.".to_owned()),
+                Some("Figure".to_owned()),
+                Some("Equation".to_owned()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn cleveref_page_and_name() {
+        let text = indoc!(
+            r#"
+                \begin{figure}\caption{Foo}\label{fig:foo}\end{figure}
+                \cpageref{}
+                \namecref{}
+                \nameCref{}
+                \lcnamecref{}
+            "#
+        );
+
+        let mut actual_details = Vec::new();
+        for (line, character) in &[(1, 10), (2, 10), (3, 10), (4, 12)] {
+            let req = FeatureTester::new()
+                .file("main.tex", text)
+                .main("main.tex")
+                .position(*line, *character)
+                .test_completion_request()
+                .await;
+            let mut actual_items = Vec::new();
+
+            complete_latex_labels(&req, &mut actual_items).await;
+
+            for item in actual_items {
+                if let ItemData::Label { header, .. } = item.data {
+                    actual_details.push(header);
+                }
+            }
+        }
+
+        assert_eq!(
+            actual_details,
+            vec![
+                Some("fig.".to_owned()),
+                Some("fig.".to_owned()),
+                Some("Figure".to_owned()),
+                Some("fig.".to_owned()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn custom_cref_names() {
+        let text = indoc!(
+            r#"
+                \include{names}
+                \begin{figure}\caption{Foo}\label{fig:foo}\end{figure}
+                \begin{equation}\label{eq:bar}\end{equation}
+                \cref{}
+                \Cref{}
+            "#
+        );
+
+        let mut actual_details = Vec::new();
+        for line in &[3, 4] {
+            let req = FeatureTester::new()
+                .file("main.tex", text)
+                .file(
+                    "names.tex",
+                    r#"\crefname{figure}{Abb.}{Abb.}\Crefname{equation}{Gleichung}{Gleichungen}"#,
+                )
+                .main("main.tex")
+                .position(*line, 6)
+                .test_completion_request()
+                .await;
+            let mut actual_items = Vec::new();
+
+            complete_latex_labels(&req, &mut actual_items).await;
+
+            for item in actual_items {
+                if let ItemData::Label { header, .. } = item.data {
+                    actual_details.push(header);
+                }
+            }
+        }
+
+        assert_eq!(
+            actual_details,
+            vec![
+                Some("Abb.".to_owned()),
+                Some("gleichung".to_owned()),
+                Some("Abb.".to_owned()),
+                Some("Gleichung".to_owned()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn subref() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \begin{figure}
+                            \begin{subfigure}\subcaption{Foo}\label{fig:foo}\end{subfigure}
+                            \subcaptionbox{Bar\label{fig:bar}}{}
+                            \caption{Baz}\label{fig:baz}
+                        \end{figure}
+                        \subref{}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(5, 8)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_labels(&req, &mut actual_items).await;
+
+        let actual_footers: Vec<_> = actual_items
+            .into_iter()
+            .map(|item| match item.data {
+                ItemData::Label { name, footer, .. } => (name, footer),
+                _ => unreachable!(),
+            })
+            .collect();
+
+        assert_eq!(
+            actual_footers,
+            vec![
+                ("fig:foo", Some("Foo".to_owned())),
+                ("fig:bar", Some("Bar".to_owned())),
+                ("fig:baz", Some("Baz".to_owned())),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reference_prefix() {
+        let text = indoc!(
+            r#"
+                \begin{figure}\caption{Foo}\label{fig:foo}\end{figure}
+                \begin{equation}\label{eq:bar}\end{equation}
+                See \ref{}
+                See Figure~\ref{}
+            "#
+        );
+
+        let mut actual_edits = Vec::new();
+        for (line, character) in &[(2, 9), (3, 16)] {
+            let req = FeatureTester::new()
+                .file("main.tex", text)
+                .main("main.tex")
+                .position(*line, *character)
+                .latex_completion(LatexCompletionOptions {
+                    insert_reference_prefix: Some(true),
+                    ..LatexCompletionOptions::default()
+                })
+                .test_completion_request()
+                .await;
+            let mut actual_items = Vec::new();
+
+            complete_latex_labels(&req, &mut actual_items).await;
+
+            for item in actual_items {
+                if let ItemData::Label { prefix_edit, .. } = item.data {
+                    actual_edits.push(prefix_edit);
+                }
+            }
+        }
+
+        assert_eq!(
+            actual_edits,
+            vec![
+                Some(TextEdit::new(
+                    Range::new_simple(2, 4, 2, 4),
+                    "Figure~".into()
+                )),
+                None,
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reference_prefix_forms() {
+        let text = indoc!(
+            r#"
+                \begin{figure}\caption{Foo}\label{fig:foo}\end{figure}
+                Größe in Fig. \ref{}
+                Größe in Abbildung~\ref{}
+                See figure
+                \ref{}
+            "#
+        );
+
+        for (line, character) in &[(1, 19), (2, 24), (4, 5)] {
+            let req = FeatureTester::new()
+                .file("main.tex", text)
+                .main("main.tex")
+                .position(*line, *character)
+                .latex_completion(LatexCompletionOptions {
+                    insert_reference_prefix: Some(true),
+                    ..LatexCompletionOptions::default()
+                })
+                .test_completion_request()
+                .await;
+            let mut actual_items = Vec::new();
+
+            complete_latex_labels(&req, &mut actual_items).await;
+
+            assert_eq!(actual_items.len(), 1);
+            match &actual_items[0].data {
+                ItemData::Label { prefix_edit, .. } => assert_eq!(*prefix_edit, None),
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn reference_prefix_disabled() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \begin{figure}\caption{Foo}\label{fig:foo}\end{figure}
+                        \ref{}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(1, 5)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_labels(&req, &mut actual_items).await;
+
+        assert_eq!(actual_items.len(), 1);
+        match &actual_items[0].data {
+            ItemData::Label { prefix_edit, .. } => assert_eq!(*prefix_edit, None),
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn shared_label_index() {
+        let text = indoc!(
+            r#"
+                \label{foo}\label[lemma]{bar}
+                \ref{}
+                \cref{}
+                \cpageref{}
+                \labelcref{}
+            "#
+        );
+
+        for (line, character) in &[(1, 5), (2, 6), (3, 10), (4, 11)] {
+            let req = FeatureTester::new()
+                .file("main.tex", text)
+                .main("main.tex")
+                .position(*line, *character)
+                .test_completion_request()
+                .await;
+            let mut actual_items = Vec::new();
+
+            complete_latex_labels(&req, &mut actual_items).await;
+
+            let actual_labels: Vec<_> = actual_items
+                .iter()
+                .map(|item| item.data.label().to_owned())
+                .collect();
+            assert_eq!(actual_labels, vec!["foo", "bar"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn inside_of_tabular() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \begin{tabular}{cc}
+                            \multicolumn{2}{c}{\label{foo}Foo}\\
+                            Bar&\label{bar}Baz\\
+                            \ref{fo&Qux\\
+                        \end{tabular}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(3, 11)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_labels(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items
+            .iter()
+            .map(|item| item.data.label().to_owned())
+            .collect();
+        assert_eq!(actual_labels, vec!["foo", "bar"]);
+        assert_eq!(actual_items[0].range, Range::new_simple(3, 9, 3, 11));
+    }
+
+    #[tokio::test]
+    async fn name_with_separators() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\label{fig:my_plot-1}\ref{fig:my_p}"#)
+            .main("main.tex")
+            .position(0, 32)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_labels(&req, &mut actual_items).await;
+
+        assert_eq!(actual_items.len(), 1);
+        assert_eq!(actual_items[0].data.label(), "fig:my_plot-1");
+        assert_eq!(actual_items[0].range, Range::new_simple(0, 26, 0, 34));
+    }
+
+    #[tokio::test]
+    async fn zref_label() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\zlabel{foo}\label{bar}\zref{}"#)
+            .main("main.tex")
+            .position(0, 29)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_labels(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items.iter().map(|item| item.data.label()).collect();
+        assert_eq!(actual_labels, vec!["foo"]);
+        assert_eq!(actual_items[0].range, Range::new_simple(0, 29, 0, 29));
+    }
+
+    #[tokio::test]
+    async fn inside_of_listing_caption() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \label{x}
+                        \begin{lstlisting}[caption={See \ref{}}]
+                        foo
+                        \end{lstlisting}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(1, 37)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_labels(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items.iter().map(|item| item.data.label()).collect();
+        assert_eq!(actual_labels, vec!["x"]);
+        assert_eq!(actual_items[0].range, Range::new_simple(1, 37, 1, 37));
+    }
+
+    #[tokio::test]
+    async fn unrelated_fallback() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\ref{}"#)
+            .file("other.tex", r#"\label{foo}"#)
+            .main("main.tex")
+            .position(0, 5)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_labels(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items
+            .iter()
+            .map(|item| match &item.data {
+                ItemData::Label {
+                    name,
+                    file,
+                    fallback,
+                    ..
+                } => (*name, file.cloned(), *fallback),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(
+            actual_labels,
+            vec![("foo", Some(FeatureTester::uri("other.tex")), true)]
+        );
+    }
+
+    #[tokio::test]
+    async fn unrelated_other_directory() {
+        let req = FeatureTester::new()
+            .file("project/chapters/foo.tex", r#"\ref{}"#)
+            .file("project/figures/bar.tex", r#"\label{bar}"#)
+            .file("other/baz.tex", r#"\label{baz}"#)
+            .main("project/chapters/foo.tex")
+            .workspace_directory("project")
+            .position(0, 5)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_labels(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items.iter().map(|item| item.data.label()).collect();
+        assert_eq!(actual_labels, vec!["bar"]);
+    }
+
+    #[tokio::test]
+    async fn unrelated_without_fallback() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\label{bar}\ref{}"#)
+            .file("other.tex", r#"\label{foo}"#)
+            .main("main.tex")
+            .position(0, 16)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_labels(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items.iter().map(|item| item.data.label()).collect();
+        assert_eq!(actual_labels, vec!["bar"]);
+    }
+
+    #[tokio::test]
+    async fn number_from_aux() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \begin{equation}\label{eq:foo}\end{equation}
+                        \label{bar}
+                        \label{baz}
+                        \ref{}
+                    "#
+                ),
+            )
+            .file(
+                "main.aux",
+                r#"\newlabel{eq:foo}{{3.2}{1}}\newlabel{bar}{{4}{2}}"#,
+            )
+            .main("main.tex")
+            .position(3, 5)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_labels(&req, &mut actual_items).await;
+
+        let actual_headers: Vec<_> = actual_items
+            .iter()
+            .map(|item| match &item.data {
+                ItemData::Label { name, header, .. } => (*name, header.as_deref()),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(
+            actual_headers,
+            vec![
+                ("eq:foo", Some("Equation (3.2)")),
+                ("bar", Some("4")),
+                ("baz", None)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn commented_label() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        % \label{foo}
+                        \label{bar} % \label{baz}
+                        50\% \label{qux}
+                        \ref{}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(3, 5)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_labels(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items.iter().map(|item| item.data.label()).collect();
+        assert_eq!(actual_labels, vec!["bar", "qux"]);
+    }
 }
@@ -0,0 +1,274 @@
+use super::combinators::{self, Parameter};
+use crate::{
+    completion::types::{Item, ItemData},
+    feature::FeatureRequest,
+    protocol::{CompletionParams, Position, RangeExt},
+    syntax::{latex, LatexLabelKind, SyntaxNode, LANGUAGE_DATA},
+    workspace::DocumentContent,
+};
+
+pub const ENVIRONMENT_PREFIXES: &[(&str, &str)] = &[
+    ("figure", "fig"),
+    ("figure*", "fig"),
+    ("subfigure", "fig"),
+    ("wrapfigure", "fig"),
+    ("table", "tab"),
+    ("table*", "tab"),
+    ("subtable", "tab"),
+    ("wraptable", "tab"),
+    ("lstlisting", "lst"),
+    ("listing", "lst"),
+    ("algorithm", "alg"),
+];
+
+pub const SEPARATORS: &[char] = &[':', '-', '_', '.'];
+
+pub async fn complete_latex_label_names<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    let parameters = LANGUAGE_DATA
+        .label_commands
+        .iter()
+        .filter(|cmd| cmd.kind == LatexLabelKind::Definition && !cmd.optional)
+        .map(|cmd| Parameter {
+            name: &cmd.name[1..],
+            index: cmd.index,
+        });
+
+    combinators::argument(req, parameters, |ctx| async move {
+        let table = req.current().content.as_latex().unwrap();
+        if let Some(prefix) = find_prefix(table, table[ctx.node].start()) {
+            let separator = find_separator(req);
+            let name = format!("{}{}", prefix, separator);
+            items.push(Item::new(ctx.range, ItemData::LabelSuggestion { name }));
+        }
+    })
+    .await;
+}
+
+fn find_prefix(table: &latex::SymbolTable, pos: Position) -> Option<&'static str> {
+    let env_prefix = table
+        .environments
+        .iter()
+        .filter(|env| env.range(&table).contains_exclusive(pos))
+        .filter_map(|env| Some((env, environment_prefix(table, *env)?)))
+        .max_by_key(|(env, _)| env.range(&table).start)
+        .map(|(_, prefix)| prefix);
+
+    env_prefix.or_else(|| section_prefix(table, pos))
+}
+
+fn environment_prefix(table: &latex::SymbolTable, env: latex::Environment) -> Option<&'static str> {
+    if env.left.is_math(&table) {
+        return Some("eq");
+    }
+
+    let name = env.left.name(&table)?.text();
+    ENVIRONMENT_PREFIXES
+        .iter()
+        .find(|(env_name, _)| *env_name == name)
+        .map(|(_, prefix)| *prefix)
+}
+
+fn section_prefix(table: &latex::SymbolTable, pos: Position) -> Option<&'static str> {
+    let section = table
+        .sections
+        .iter()
+        .filter(|section| table[section.parent].end() <= pos)
+        .max_by_key(|section| table[section.parent].end())?;
+
+    if pos.line - table[section.parent].end().line > 1 {
+        return None;
+    }
+
+    let prefix = match section.prefix.as_ref() {
+        "Part" => "part",
+        "Chapter" => "chap",
+        _ => "sec",
+    };
+    Some(prefix)
+}
+
+fn find_separator(req: &FeatureRequest<CompletionParams>) -> char {
+    let mut counts = vec![0; SEPARATORS.len()];
+    for doc in req.related() {
+        if let DocumentContent::Latex(table) = &doc.content {
+            for label in table.label_definitions() {
+                for name in label.names(&table) {
+                    if let Some(index) = name
+                        .text()
+                        .chars()
+                        .find_map(|c| SEPARATORS.iter().position(|sep| *sep == c))
+                    {
+                        counts[index] += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    let (index, _) = counts
+        .iter()
+        .enumerate()
+        .rev()
+        .max_by_key(|(_, count)| **count)
+        .unwrap();
+    SEPARATORS[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        feature::FeatureTester,
+        protocol::{Range, RangeExt},
+    };
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let req = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_label_names(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let req = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_label_names(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn inside_figure() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \begin{figure}
+                            \begin{center}
+                                \label{}
+                            \end{center}
+                        \end{figure}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(2, 15)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_label_names(&req, &mut actual_items).await;
+
+        assert_eq!(actual_items.len(), 1);
+        assert_eq!(actual_items[0].data.label(), "fig:");
+        assert_eq!(actual_items[0].range, Range::new_simple(2, 15, 2, 15));
+    }
+
+    #[tokio::test]
+    async fn inside_equation() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \begin{table}
+                            \begin{equation}
+                                \label{}
+                            \end{equation}
+                        \end{table}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(2, 15)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_label_names(&req, &mut actual_items).await;
+
+        assert_eq!(actual_items.len(), 1);
+        assert_eq!(actual_items[0].data.label(), "eq:");
+    }
+
+    #[tokio::test]
+    async fn after_section() {
+        let req = FeatureTester::new()
+            .file("main.tex", "\\section{Foo}\n\\label{}")
+            .main("main.tex")
+            .position(1, 7)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_label_names(&req, &mut actual_items).await;
+
+        assert_eq!(actual_items.len(), 1);
+        assert_eq!(actual_items[0].data.label(), "sec:");
+    }
+
+    #[tokio::test]
+    async fn far_from_section() {
+        let req = FeatureTester::new()
+            .file("main.tex", "\\section{Foo}\n\nFoo\n\n\\label{}")
+            .main("main.tex")
+            .position(4, 7)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_label_names(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn existing_separator() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \include{foo}
+                        \begin{figure}
+                            \label{}
+                        \end{figure}
+                    "#
+                ),
+            )
+            .file(
+                "foo.tex",
+                r#"\label{sec-foo}\label{tab-bar}\label{fig:baz}"#,
+            )
+            .main("main.tex")
+            .position(2, 11)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_label_names(&req, &mut actual_items).await;
+
+        assert_eq!(actual_items.len(), 1);
+        assert_eq!(actual_items[0].data.label(), "fig-");
+    }
+}
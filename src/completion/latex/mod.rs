@@ -1,14 +1,25 @@
 pub mod argument;
+pub mod beamer;
 pub mod begin_cmd;
+pub mod bib_style;
 pub mod citation;
 pub mod color;
 pub mod color_model;
 mod combinators;
 pub mod component;
 pub mod glossary;
+pub mod graphics;
 pub mod import;
 pub mod include;
+pub mod item;
 pub mod label;
+pub mod label_name;
+pub mod page_style;
+pub mod siunitx;
+pub mod snippet;
+pub mod spacing;
+pub mod tabular;
 pub mod theorem;
 pub mod tikz_lib;
+pub mod url;
 pub mod user;
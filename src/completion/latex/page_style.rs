@@ -0,0 +1,139 @@
+use super::combinators::{self, Parameter};
+use crate::{
+    completion::types::{Item, ItemData},
+    feature::FeatureRequest,
+    protocol::CompletionParams,
+    syntax::LANGUAGE_DATA,
+    workspace::DocumentContent,
+};
+
+const PAGE_STYLE_COMMANDS: &[&str] = &["pagestyle", "thispagestyle"];
+
+pub async fn complete_latex_page_styles<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    let parameters = PAGE_STYLE_COMMANDS
+        .iter()
+        .map(|&name| Parameter { name, index: 0 });
+
+    combinators::argument(req, parameters, |ctx| async move {
+        for style in &LANGUAGE_DATA.page_styles {
+            if style
+                .component
+                .as_ref()
+                .map_or(true, |component| is_loaded(req, component))
+            {
+                let item = Item::new(ctx.range, ItemData::PageStyle { name: &style.name });
+                items.push(item);
+            }
+        }
+    })
+    .await;
+}
+
+fn is_loaded(req: &FeatureRequest<CompletionParams>, component: &str) -> bool {
+    req.related().iter().any(|doc| match &doc.content {
+        DocumentContent::Latex(table) => table.components.iter().any(|comp| comp == component),
+        DocumentContent::Bibtex(_) => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        feature::FeatureTester,
+        protocol::{Range, RangeExt},
+    };
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let req = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_page_styles(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let req = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_page_styles(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn inside_pagestyle() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\pagestyle{pl}"#)
+            .main("main.tex")
+            .position(0, 13)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_page_styles(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items.iter().map(|item| item.data.label()).collect();
+        assert_eq!(
+            actual_labels,
+            vec!["empty", "plain", "headings", "myheadings"]
+        );
+        assert_eq!(actual_items[0].range, Range::new_simple(0, 11, 0, 13));
+    }
+
+    #[tokio::test]
+    async fn inside_thispagestyle_with_fancyhdr() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \usepackage{fancyhdr}
+                        \thispagestyle{}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(1, 15)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_page_styles(&req, &mut actual_items).await;
+
+        assert!(actual_items.iter().any(|item| item.data.label() == "fancy"));
+        assert_eq!(actual_items[0].range, Range::new_simple(1, 15, 1, 15));
+    }
+
+    #[tokio::test]
+    async fn outside_pagestyle() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\pagestyle{}"#)
+            .main("main.tex")
+            .position(0, 5)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_page_styles(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+}
@@ -0,0 +1,143 @@
+use super::combinators;
+use crate::{
+    completion::types::{Item, ItemData},
+    feature::FeatureRequest,
+    protocol::{CompletionParams, Position, RangeExt},
+    syntax::{latex, SyntaxNode, LANGUAGE_DATA},
+};
+
+pub async fn complete_latex_units<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    combinators::command(req, |cmd_node| async move {
+        let table = req.current().content.as_latex().unwrap();
+        let cmd = table.as_command(cmd_node).unwrap();
+        if !is_unit_argument(table, cmd.start()) {
+            return;
+        }
+
+        for name in &LANGUAGE_DATA.units {
+            let item = Item::new(cmd.short_name_range(), ItemData::Unit { name });
+            items.push(item);
+        }
+    })
+    .await;
+}
+
+fn is_unit_argument(table: &latex::SymbolTable, pos: Position) -> bool {
+    table.commands.iter().any(|node| {
+        let cmd = table.as_command(*node).unwrap();
+        LANGUAGE_DATA
+            .unit_commands
+            .iter()
+            .filter(|desc| desc.name == cmd.name.text())
+            .filter_map(|desc| table.extract_group(*node, latex::GroupKind::Group, desc.index))
+            .filter_map(|group_node| table.as_group(group_node))
+            .any(|group| {
+                if group.right.is_some() {
+                    group.range().contains_exclusive(pos)
+                } else {
+                    group.range().contains(pos)
+                }
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::Range};
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let req = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_units(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let req = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_units(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn inside_si() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\si{\me}"#)
+            .main("main.tex")
+            .position(0, 7)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_units(&req, &mut actual_items).await;
+
+        assert!(actual_items.iter().any(|item| item.data.label() == "metre"));
+        assert_eq!(actual_items[0].range, Range::new_simple(0, 5, 0, 7));
+    }
+
+    #[tokio::test]
+    async fn inside_si_unit_argument() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\SI{3}{\kilo\me}"#)
+            .main("main.tex")
+            .position(0, 15)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_units(&req, &mut actual_items).await;
+
+        assert!(!actual_items.is_empty());
+        assert_eq!(actual_items[0].range, Range::new_simple(0, 13, 0, 15));
+    }
+
+    #[tokio::test]
+    async fn inside_si_number_argument() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\SI{\me}{}"#)
+            .main("main.tex")
+            .position(0, 7)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_units(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn outside_unit_command() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\me"#)
+            .main("main.tex")
+            .position(0, 3)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_units(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+}
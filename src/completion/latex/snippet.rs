@@ -0,0 +1,242 @@
+use super::combinators::{self, Parameter};
+use crate::{
+    completion::types::{Item, ItemData},
+    feature::FeatureRequest,
+    protocol::{CompletionParams, LatexSnippet, Position, Range, RangeExt},
+    syntax::{latex, SyntaxNode},
+};
+
+pub async fn complete_latex_snippets<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    let snippets = &req.config.completion.snippets;
+
+    if snippets.is_empty() {
+        return;
+    }
+
+    let parameters = snippets
+        .iter()
+        .flat_map(|snippet| snippet.commands())
+        .map(|name| Parameter {
+            name: name.trim_start_matches('\\'),
+            index: 0,
+        });
+
+    let count = items.len();
+    let argument_items = &mut *items;
+    combinators::argument(req, parameters, |ctx| async move {
+        for snippet in snippets.iter().filter(|snippet| {
+            snippet
+                .commands()
+                .iter()
+                .any(|name| name.trim_start_matches('\\') == ctx.parameter.name)
+        }) {
+            argument_items.push(make_item(ctx.range, snippet));
+        }
+    })
+    .await;
+
+    // Command snippets take precedence over environment snippets at the same position.
+    if items.len() == count {
+        if let Some(table) = req.current().content.as_latex() {
+            let pos = req.params.text_document_position.position;
+            if let Some(range) = text_range(table, pos) {
+                for snippet in snippets
+                    .iter()
+                    .filter(|snippet| is_inside_environment(table, pos, snippet))
+                {
+                    items.push(make_item(range, snippet));
+                }
+            }
+        }
+    }
+}
+
+fn make_item<'a>(range: Range, snippet: &'a LatexSnippet) -> Item<'a> {
+    Item::new(
+        range,
+        ItemData::Snippet {
+            label: &snippet.label,
+            body: &snippet.body,
+        },
+    )
+}
+
+fn text_range(table: &latex::SymbolTable, pos: Position) -> Option<Range> {
+    match &table[table.find(pos).into_iter().last()?] {
+        latex::Node::Root(_) | latex::Node::Group(_) => Some(Range::new(pos, pos)),
+        latex::Node::Text(text) => Some(
+            text.words
+                .iter()
+                .find(|word| word.range().contains(pos))
+                .map(|word| word.range())
+                .unwrap_or_else(|| Range::new(pos, pos)),
+        ),
+        latex::Node::Command(_) | latex::Node::Comma(_) | latex::Node::Math(_) => None,
+    }
+}
+
+fn is_inside_environment(
+    table: &latex::SymbolTable,
+    pos: Position,
+    snippet: &LatexSnippet,
+) -> bool {
+    table
+        .environments
+        .iter()
+        .filter(|env| env.range(&table).contains_exclusive(pos))
+        .filter_map(|env| env.left.name(&table))
+        .any(|name| {
+            snippet
+                .environments()
+                .iter()
+                .any(|env_name| env_name == name.text())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::LatexCompletionOptions};
+    use indoc::indoc;
+
+    fn options() -> LatexCompletionOptions {
+        LatexCompletionOptions {
+            snippets: Some(vec![
+                LatexSnippet {
+                    label: "source".into(),
+                    body: "Source: ${1:author}".into(),
+                    commands: Some(vec!["caption".into()]),
+                    environments: None,
+                },
+                LatexSnippet {
+                    label: "funding".into(),
+                    body: "This work was funded by $1.".into(),
+                    commands: None,
+                    environments: Some(vec!["abstract".into()]),
+                },
+            ]),
+            ..LatexCompletionOptions::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let req = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .latex_completion(options())
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_snippets(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let req = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .position(0, 0)
+            .latex_completion(options())
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_snippets(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn inside_caption() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\caption{Foo. So}"#)
+            .main("main.tex")
+            .position(0, 16)
+            .latex_completion(options())
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_snippets(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items.iter().map(|item| item.data.label()).collect();
+        assert_eq!(actual_labels, vec!["source"]);
+    }
+
+    #[tokio::test]
+    async fn inside_abstract() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \begin{abstract}
+                            fun
+                        \end{abstract}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(1, 6)
+            .latex_completion(options())
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_snippets(&req, &mut actual_items).await;
+
+        assert_eq!(actual_items.len(), 1);
+        assert_eq!(actual_items[0].data.label(), "funding");
+        assert_eq!(actual_items[0].range, Range::new_simple(1, 4, 1, 7));
+    }
+
+    #[tokio::test]
+    async fn outside_of_context() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\section{So}"#)
+            .main("main.tex")
+            .position(0, 11)
+            .latex_completion(options())
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_snippets(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn caption_inside_abstract() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \begin{abstract}
+                            \caption{So}
+                        \end{abstract}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(1, 15)
+            .latex_completion(options())
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_snippets(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items.iter().map(|item| item.data.label()).collect();
+        assert_eq!(actual_labels, vec!["source"]);
+    }
+}
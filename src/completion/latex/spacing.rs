@@ -0,0 +1,91 @@
+use super::combinators;
+use crate::{
+    completion::types::{Item, ItemData},
+    feature::FeatureRequest,
+    protocol::CompletionParams,
+    syntax::{SyntaxNode, LANGUAGE_DATA},
+};
+
+pub async fn complete_latex_spacing_commands<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    combinators::command(req, |cmd_node| async move {
+        let table = req.current().content.as_latex().unwrap();
+        let cmd = table.as_command(cmd_node).unwrap();
+        let range = cmd.short_name_range();
+        let math_mode = table.is_math_mode(cmd.start());
+
+        for cmd in LANGUAGE_DATA
+            .spacing_commands
+            .iter()
+            .filter(|cmd| cmd.math == math_mode)
+        {
+            items.push(Item::new(
+                range,
+                ItemData::ComponentCommand {
+                    name: &cmd.name,
+                    glyph: None,
+                    file_names: &[],
+                },
+            ));
+        }
+    })
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feature::FeatureTester;
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let req = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_spacing_commands(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn inside_math() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"$a \q b$"#)
+            .main("main.tex")
+            .position(0, 5)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_spacing_commands(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items.iter().map(|item| item.data.label()).collect();
+        assert_eq!(actual_labels, vec!["quad", "qquad", ",", "!", ";"]);
+    }
+
+    #[tokio::test]
+    async fn outside_math() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"a \q b"#)
+            .main("main.tex")
+            .position(0, 4)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_spacing_commands(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items.iter().map(|item| item.data.label()).collect();
+        assert_eq!(
+            actual_labels,
+            vec!["thinspace", "negthinspace", "enspace", "enskip"]
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use super::combinators::{self, Parameter};
+use crate::{
+    completion::types::{Item, ItemData},
+    feature::FeatureRequest,
+    protocol::{CompletionParams, Range},
+    syntax::latex,
+};
+
+const TABULAR_ENVIRONMENTS: &[(&str, usize)] = &[
+    ("tabular", 1),
+    ("array", 1),
+    ("longtable", 1),
+    ("tabular*", 2),
+    ("tabularx", 2),
+    ("tabulary", 2),
+];
+
+const COLUMN_SPECIFIERS: &[(&str, &str, &str)] = &[
+    ("l", "l", "Left-aligned column"),
+    ("c", "c", "Centered column"),
+    ("r", "r", "Right-aligned column"),
+    ("p{}", "p{${1:width}}", "Top-aligned paragraph column"),
+    ("@{}", "@{$1}", "Inter-column text"),
+    ("|", "|", "Vertical rule"),
+];
+
+pub async fn complete_latex_column_specifiers<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    let parameters = [1, 2].iter().map(|index| Parameter {
+        name: "begin",
+        index: *index,
+    });
+
+    combinators::argument(req, parameters, |ctx| async move {
+        let table = req.current().content.as_latex().unwrap();
+        let is_column_spec = table
+            .extract_word(ctx.node, latex::GroupKind::Group, 0)
+            .filter(|name| {
+                TABULAR_ENVIRONMENTS
+                    .iter()
+                    .any(|(env, index)| *env == name.text() && *index == ctx.parameter.index)
+            })
+            .is_some();
+
+        if is_column_spec {
+            let pos = req.params.text_document_position.position;
+            for &(name, snippet, detail) in COLUMN_SPECIFIERS {
+                let item = Item::new(
+                    Range::new(pos, pos),
+                    ItemData::ColumnSpecifier {
+                        name,
+                        snippet,
+                        detail,
+                    },
+                );
+                items.push(item);
+            }
+        }
+    })
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::RangeExt};
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let req = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_column_specifiers(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let req = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_column_specifiers(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn inside_tabular() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\begin{tabular}{lc}"#)
+            .main("main.tex")
+            .position(0, 18)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_column_specifiers(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items.iter().map(|item| item.data.label()).collect();
+        assert_eq!(actual_labels, vec!["l", "c", "r", "p{}", "@{}", "|"]);
+        assert_eq!(actual_items[0].range, Range::new_simple(0, 18, 0, 18));
+    }
+
+    #[tokio::test]
+    async fn inside_tabularx() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\begin{tabularx}{\linewidth}{}"#)
+            .main("main.tex")
+            .position(0, 29)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_column_specifiers(&req, &mut actual_items).await;
+
+        assert_eq!(actual_items.len(), COLUMN_SPECIFIERS.len());
+    }
+
+    #[tokio::test]
+    async fn tabularx_width() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\begin{tabularx}{}{l}"#)
+            .main("main.tex")
+            .position(0, 17)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_column_specifiers(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn other_environment() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\begin{minipage}{}"#)
+            .main("main.tex")
+            .position(0, 17)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_column_specifiers(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+}
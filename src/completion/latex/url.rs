@@ -0,0 +1,120 @@
+use super::combinators::{self, Parameter};
+use crate::{
+    completion::types::{Item, ItemData},
+    feature::FeatureRequest,
+    protocol::{CompletionParams, Range},
+    syntax::{latex, CharStream, SyntaxNode, LANGUAGE_DATA},
+};
+
+const URL_SCHEMES: &[&str] = &["https://", "http://", "mailto:", "doi:"];
+
+pub async fn complete_latex_url_schemes<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    let parameters = LANGUAGE_DATA.url_commands.iter().map(|cmd| Parameter {
+        name: &cmd.name[1..],
+        index: cmd.index,
+    });
+
+    combinators::argument(req, parameters, |ctx| async move {
+        let table = req.current().content.as_latex().unwrap();
+        let group = table
+            .extract_group(ctx.node, latex::GroupKind::Group, ctx.parameter.index)
+            .and_then(|node| table.as_group(node))
+            .unwrap();
+        let pos = req.params.text_document_position.position;
+        let prefix = CharStream::extract(&req.current().text, Range::new(group.left.end(), pos));
+        if prefix.contains(':') {
+            return;
+        }
+
+        for &name in URL_SCHEMES {
+            items.push(Item::new(ctx.range, ItemData::UrlScheme { name }));
+        }
+    })
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::RangeExt};
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let req = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_url_schemes(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let req = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_url_schemes(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_url() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\url{}"#)
+            .main("main.tex")
+            .position(0, 5)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_url_schemes(&req, &mut actual_items).await;
+
+        assert_eq!(actual_items.len(), URL_SCHEMES.len());
+        assert_eq!(actual_items[0].data.label(), "https://");
+        assert_eq!(actual_items[0].range, Range::new_simple(0, 5, 0, 5));
+    }
+
+    #[tokio::test]
+    async fn existing_scheme() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\url{https://foo}"#)
+            .main("main.tex")
+            .position(0, 16)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_url_schemes(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn href_text_argument() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\href{}{}"#)
+            .main("main.tex")
+            .position(0, 8)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_url_schemes(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+}
@@ -3,7 +3,10 @@ mod latex;
 mod types;
 mod util;
 
-pub use self::types::{CompletionItemData, Item, ItemData};
+pub use self::{
+    latex::citation::citation_detail,
+    types::{CompletionItemData, Item, ItemData},
+};
 
 use self::{
     bibtex::{
@@ -12,32 +15,46 @@ use self::{
     },
     latex::{
         argument::complete_latex_arguments,
+        beamer::complete_latex_beamer_frame_options,
         begin_cmd::complete_latex_begin_command,
+        bib_style::complete_latex_bibliography_styles,
         citation::complete_latex_citations,
         color::complete_latex_colors,
         color_model::complete_latex_color_models,
         component::{complete_latex_component_commands, complete_latex_component_environments},
         glossary::complete_latex_glossary_entries,
+        graphics::complete_latex_graphics_options,
         import::{complete_latex_classes, complete_latex_packages},
         include::complete_latex_includes,
+        item::complete_latex_item_labels,
         label::complete_latex_labels,
+        label_name::complete_latex_label_names,
+        page_style::complete_latex_page_styles,
+        siunitx::complete_latex_units,
+        snippet::complete_latex_snippets,
+        spacing::complete_latex_spacing_commands,
+        tabular::complete_latex_column_specifiers,
         theorem::complete_latex_theorem_environments,
         tikz_lib::{complete_latex_pgf_libraries, complete_latex_tikz_libraries},
+        url::complete_latex_url_schemes,
         user::{complete_latex_user_commands, complete_latex_user_environments},
     },
     util::{adjust_kind, component_detail, current_word, image_documentation},
 };
 use crate::{
+    components::COMPONENT_DATABASE,
     feature::{FeatureProvider, FeatureRequest},
     protocol::{
-        CompletionItem, CompletionParams, CompletionTextEdit, Documentation, InsertTextFormat,
-        MarkupContent, MarkupKind, RangeExt, TextEdit,
+        ClientCapabilities, ClientCapabilitiesExt, CompletionItem, CompletionItemTag,
+        CompletionParams, CompletionTextEdit, CompletionTriggerKind, Documentation,
+        InsertTextFormat, MarkupContent, MarkupKind, RangeExt, TextEdit,
     },
-    syntax::{self, Structure, SyntaxNode},
+    syntax::{self, Structure, SyntaxNode, LANGUAGE_DATA},
     workspace::DocumentContent,
 };
 use async_trait::async_trait;
 use fuzzy_matcher::skim::fuzzy_match;
+use once_cell::sync::Lazy;
 use std::collections::HashSet;
 
 pub const COMPLETION_LIMIT: usize = 50;
@@ -57,12 +74,34 @@ impl FeatureProvider for CompletionProvider {
     type Output = Vec<CompletionItem>;
 
     async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        if req.current().is_large(&req.options) {
+            return Vec::new();
+        }
+
         let items = complete_all(req).await;
+        if req.cancellation.is_cancelled() {
+            return Vec::new();
+        }
+
         let mut items = dedup(items);
         preselect(req, &mut items);
         score(req, &mut items);
 
-        items.sort_by_key(|item| (!item.preselect, -item.score.unwrap_or(std::i64::MIN + 1)));
+        // Inside math mode, math symbols win over other equally good matches.
+        let math_mode = is_math_mode(req);
+        items.sort_by_key(|item| {
+            (
+                !item.preselect,
+                is_fallback_label(item),
+                !is_preferred_label(item),
+                label_order(item),
+                label_file(item),
+                -item.score.unwrap_or(std::i64::MIN + 1),
+                math_mode && !is_math_symbol(item),
+                is_deprecated(item),
+                proximity(item),
+            )
+        });
         items
             .into_iter()
             .take(COMPLETION_LIMIT)
@@ -75,12 +114,15 @@ impl FeatureProvider for CompletionProvider {
 }
 
 async fn complete_all<'a>(req: &'a FeatureRequest<CompletionParams>) -> Vec<Item<'a>> {
+    let commands = is_command_completion_enabled(req);
     let mut items = Vec::new();
     complete_bibtex_commands(req, &mut items).await;
     complete_bibtex_entry_types(req, &mut items).await;
     complete_bibtex_fields(req, &mut items).await;
     complete_latex_arguments(req, &mut items).await;
-    complete_latex_begin_command(req, &mut items).await;
+    if commands {
+        complete_latex_begin_command(req, &mut items).await;
+    }
     complete_latex_colors(req, &mut items).await;
     complete_latex_color_models(req, &mut items).await;
     complete_latex_glossary_entries(req, &mut items).await;
@@ -89,16 +131,36 @@ async fn complete_all<'a>(req: &'a FeatureRequest<CompletionParams>) -> Vec<Item
     complete_latex_packages(req, &mut items).await;
     complete_latex_includes(req, &mut items).await;
     complete_latex_labels(req, &mut items).await;
+    complete_latex_label_names(req, &mut items).await;
+    complete_latex_page_styles(req, &mut items).await;
+    complete_latex_bibliography_styles(req, &mut items).await;
     complete_latex_pgf_libraries(req, &mut items).await;
     complete_latex_tikz_libraries(req, &mut items).await;
+    complete_latex_beamer_frame_options(req, &mut items).await;
+    complete_latex_graphics_options(req, &mut items).await;
+    complete_latex_item_labels(req, &mut items).await;
+    complete_latex_column_specifiers(req, &mut items).await;
+    complete_latex_snippets(req, &mut items).await;
+    complete_latex_url_schemes(req, &mut items).await;
     complete_latex_component_environments(req, &mut items).await;
     complete_latex_theorem_environments(req, &mut items).await;
     complete_latex_user_environments(req, &mut items).await;
-    complete_latex_component_commands(req, &mut items).await;
-    complete_latex_user_commands(req, &mut items).await;
+    complete_latex_units(req, &mut items).await;
+    if commands {
+        complete_latex_component_commands(req, &mut items).await;
+        complete_latex_spacing_commands(req, &mut items).await;
+        complete_latex_user_commands(req, &mut items).await;
+    }
     items
 }
 
+fn is_command_completion_enabled(req: &FeatureRequest<CompletionParams>) -> bool {
+    req.config.completion.trigger_on_backslash
+        || req.params.context.as_ref().map_or(true, |ctx| {
+            ctx.trigger_kind != CompletionTriggerKind::TriggerCharacter
+        })
+}
+
 fn dedup<'a>(items: Vec<Item<'a>>) -> Vec<Item<'a>> {
     let mut labels = HashSet::new();
     let mut insert = vec![false; items.len()];
@@ -152,17 +214,27 @@ fn score(req: &FeatureRequest<CompletionParams>, items: &mut Vec<Item>) {
             ItemData::UserCommand { name } => fuzzy_match(name, pattern),
             ItemData::UserEnvironment { name } => fuzzy_match(name, pattern),
             ItemData::Label { text, .. } => fuzzy_match(&text, pattern),
+            ItemData::LabelSuggestion { name } => fuzzy_match(&name, pattern),
             ItemData::Class { name } => fuzzy_match(&name, pattern),
             ItemData::Package { name } => fuzzy_match(&name, pattern),
             ItemData::PgfLibrary { name } => fuzzy_match(name, pattern),
             ItemData::TikzLibrary { name } => fuzzy_match(name, pattern),
+            ItemData::BeamerFrameOption { name } => fuzzy_match(name, pattern),
+            ItemData::GraphicsOption { name } => fuzzy_match(name, pattern),
+            ItemData::ItemLabel { name } => fuzzy_match(name, pattern),
+            ItemData::ColumnSpecifier { .. } => Some(0),
+            ItemData::Snippet { label, .. } => fuzzy_match(label, pattern),
+            ItemData::UrlScheme { name } => fuzzy_match(name, pattern),
+            ItemData::PageStyle { name } => fuzzy_match(name, pattern),
+            ItemData::BibliographyStyle { name } => fuzzy_match(name, pattern),
             ItemData::File { name } => fuzzy_match(name, pattern),
             ItemData::Directory { name } => fuzzy_match(name, pattern),
             ItemData::Citation { text, .. } => fuzzy_match(&text, pattern),
             ItemData::Argument { name, .. } => fuzzy_match(&name, pattern),
             ItemData::BeginCommand => fuzzy_match("begin", pattern),
-            ItemData::Color { name } => fuzzy_match(name, pattern),
+            ItemData::Color { name, .. } => fuzzy_match(name, pattern),
             ItemData::ColorModel { name } => fuzzy_match(name, pattern),
+            ItemData::Unit { name } => fuzzy_match(name, pattern),
             ItemData::GlossaryEntry { name } => fuzzy_match(name, pattern),
             ItemData::EntryType { ty } => fuzzy_match(&ty.name, pattern),
             ItemData::Field { field } => fuzzy_match(&field.name, pattern),
@@ -170,11 +242,81 @@ fn score(req: &FeatureRequest<CompletionParams>, items: &mut Vec<Item>) {
     }
 }
 
+fn is_math_mode(req: &FeatureRequest<CompletionParams>) -> bool {
+    let pos = req.params.text_document_position.position;
+    req.current()
+        .content
+        .as_latex()
+        .map_or(false, |table| table.is_math_mode(pos))
+}
+
+fn is_math_symbol(item: &Item) -> bool {
+    match item.data {
+        ItemData::ComponentCommand { glyph, .. } => glyph.is_some(),
+        _ => false,
+    }
+}
+
+static DEPRECATED_COMMANDS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
+    LANGUAGE_DATA
+        .deprecated_commands
+        .iter()
+        .map(|cmd| cmd.as_str())
+        .collect()
+});
+
+fn is_deprecated(item: &Item) -> bool {
+    match item.data {
+        ItemData::ComponentCommand { name, .. } => DEPRECATED_COMMANDS.contains(name),
+        _ => false,
+    }
+}
+
+fn proximity(item: &Item) -> (bool, u64) {
+    match item.data {
+        ItemData::Label {
+            proximity: Some(distance),
+            ..
+        } => (false, distance),
+        _ => (true, 0),
+    }
+}
+
+fn is_fallback_label(item: &Item) -> bool {
+    match item.data {
+        ItemData::Label { fallback, .. } => fallback,
+        _ => false,
+    }
+}
+
+fn is_preferred_label(item: &Item) -> bool {
+    match item.data {
+        ItemData::Label { preferred, .. } => preferred,
+        _ => false,
+    }
+}
+
+fn label_order(item: &Item) -> (bool, usize) {
+    match item.data {
+        ItemData::Label {
+            order: Some(order), ..
+        } => (false, order),
+        _ => (true, 0),
+    }
+}
+
+fn label_file<'a>(item: &Item<'a>) -> Option<&'a str> {
+    match item.data {
+        ItemData::Label { file, .. } => file.map(|uri| uri.as_str()),
+        _ => None,
+    }
+}
+
 fn convert(req: &FeatureRequest<CompletionParams>, item: Item) -> CompletionItem {
+    let deprecated = is_deprecated(&item);
     let mut new_item = match item.data {
         ItemData::ComponentCommand {
             name,
-            image,
             glyph,
             file_names,
         } => {
@@ -182,12 +324,13 @@ fn convert(req: &FeatureRequest<CompletionParams>, item: Item) -> CompletionItem
                 || component_detail(file_names),
                 |glyph| format!("{}, {}", glyph, component_detail(file_names)),
             );
-            let documentation = image.and_then(|img| image_documentation(&req, &name, img));
+            let data = CompletionItemData::ComponentCommand {
+                file_names: file_names.to_vec(),
+            };
             let text_edit = TextEdit::new(item.range, name.into());
             CompletionItem {
                 kind: Some(adjust_kind(req, Structure::Command.completion_kind())),
-                data: Some(CompletionItemData::Command.into()),
-                documentation,
+                data: Some(data.into()),
                 text_edit: Some(CompletionTextEdit::Edit(text_edit)),
                 ..CompletionItem::new_simple(name.into(), detail)
             }
@@ -227,8 +370,27 @@ fn convert(req: &FeatureRequest<CompletionParams>, item: Item) -> CompletionItem
             header,
             footer,
             text,
+            prefix_edit,
+            file,
+            fallback,
+            ..
         } => {
             let text_edit = TextEdit::new(item.range, name.into());
+            let file = file
+                .and_then(|uri| uri.path_segments())
+                .and_then(|mut segments| segments.last())
+                .map(|file| {
+                    if fallback {
+                        format!("{}, not included", file)
+                    } else {
+                        file.to_owned()
+                    }
+                });
+            let detail = match (header, file) {
+                (Some(header), Some(file)) => Some(format!("{} ({})", header, file)),
+                (None, Some(file)) => Some(file),
+                (header, None) => header,
+            };
             CompletionItem {
                 label: name.into(),
                 kind: Some(adjust_kind(req, kind.completion_kind())),
@@ -236,8 +398,20 @@ fn convert(req: &FeatureRequest<CompletionParams>, item: Item) -> CompletionItem
                 text_edit: Some(CompletionTextEdit::Edit(text_edit)),
                 filter_text: Some(text.clone()),
                 sort_text: Some(text),
-                detail: header,
+                detail,
                 documentation: footer.map(Documentation::String),
+                additional_text_edits: prefix_edit.map(|edit| vec![edit]),
+                ..CompletionItem::default()
+            }
+        }
+        ItemData::LabelSuggestion { name } => {
+            let text_edit = TextEdit::new(item.range, format!("{}$0", name));
+            CompletionItem {
+                label: name.clone(),
+                kind: Some(adjust_kind(req, Structure::Snippet.completion_kind())),
+                data: Some(CompletionItemData::LabelSuggestion.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                insert_text_format: Some(InsertTextFormat::Snippet),
                 ..CompletionItem::default()
             }
         }
@@ -281,6 +455,93 @@ fn convert(req: &FeatureRequest<CompletionParams>, item: Item) -> CompletionItem
                 ..CompletionItem::default()
             }
         }
+        ItemData::BeamerFrameOption { name } => {
+            let text_edit = TextEdit::new(item.range, name.into());
+            CompletionItem {
+                label: name.into(),
+                kind: Some(adjust_kind(req, Structure::Argument.completion_kind())),
+                data: Some(CompletionItemData::BeamerFrameOption.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::default()
+            }
+        }
+        ItemData::GraphicsOption { name } => {
+            let text_edit = TextEdit::new(item.range, name.into());
+            CompletionItem {
+                label: name.into(),
+                kind: Some(adjust_kind(req, Structure::Argument.completion_kind())),
+                data: Some(CompletionItemData::GraphicsOption.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::default()
+            }
+        }
+        ItemData::ItemLabel { name } => {
+            let text_edit = TextEdit::new(item.range, name.into());
+            CompletionItem {
+                label: name.into(),
+                kind: Some(adjust_kind(req, Structure::Argument.completion_kind())),
+                data: Some(CompletionItemData::ItemLabel.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::default()
+            }
+        }
+        ItemData::ColumnSpecifier {
+            name,
+            snippet,
+            detail,
+        } => {
+            let text_edit = TextEdit::new(item.range, snippet.into());
+            CompletionItem {
+                label: name.into(),
+                detail: Some(detail.into()),
+                kind: Some(adjust_kind(req, Structure::Argument.completion_kind())),
+                data: Some(CompletionItemData::ColumnSpecifier.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                insert_text_format: Some(InsertTextFormat::Snippet),
+                ..CompletionItem::default()
+            }
+        }
+        ItemData::Snippet { label, body } => {
+            let text_edit = TextEdit::new(item.range, body.into());
+            CompletionItem {
+                label: label.into(),
+                kind: Some(adjust_kind(req, Structure::Snippet.completion_kind())),
+                data: Some(CompletionItemData::Snippet.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                insert_text_format: Some(InsertTextFormat::Snippet),
+                ..CompletionItem::default()
+            }
+        }
+        ItemData::UrlScheme { name } => {
+            let text_edit = TextEdit::new(item.range, name.into());
+            CompletionItem {
+                label: name.into(),
+                kind: Some(adjust_kind(req, Structure::Argument.completion_kind())),
+                data: Some(CompletionItemData::UrlScheme.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::default()
+            }
+        }
+        ItemData::PageStyle { name } => {
+            let text_edit = TextEdit::new(item.range, name.into());
+            CompletionItem {
+                label: name.into(),
+                kind: Some(adjust_kind(req, Structure::Argument.completion_kind())),
+                data: Some(CompletionItemData::PageStyle.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::default()
+            }
+        }
+        ItemData::BibliographyStyle { name } => {
+            let text_edit = TextEdit::new(item.range, name.as_ref().into());
+            CompletionItem {
+                label: name.into_owned(),
+                kind: Some(adjust_kind(req, Structure::Argument.completion_kind())),
+                data: Some(CompletionItemData::BibliographyStyle.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::default()
+            }
+        }
         ItemData::File { name } => {
             let text_edit = TextEdit::new(item.range, name.clone());
             CompletionItem {
@@ -301,11 +562,27 @@ fn convert(req: &FeatureRequest<CompletionParams>, item: Item) -> CompletionItem
                 ..CompletionItem::default()
             }
         }
-        ItemData::Citation { uri, key, text, ty } => {
+        ItemData::Citation {
+            uri,
+            key,
+            text,
+            ty,
+            conflicts,
+        } => {
             let text_edit = TextEdit::new(item.range, key.into());
+            let detail = if conflicts.is_empty() {
+                None
+            } else {
+                let file_names: Vec<_> = conflicts
+                    .iter()
+                    .filter_map(|uri| uri.path_segments()?.last())
+                    .collect();
+                Some(format!("Also defined in {}", file_names.join(", ")))
+            };
             CompletionItem {
                 label: key.into(),
                 kind: Some(adjust_kind(req, ty.completion_kind())),
+                detail,
                 filter_text: Some(text.clone()),
                 sort_text: Some(text),
                 data: Some(
@@ -319,14 +596,23 @@ fn convert(req: &FeatureRequest<CompletionParams>, item: Item) -> CompletionItem
                 ..CompletionItem::default()
             }
         }
-        ItemData::Argument { name, image } => {
+        ItemData::Argument {
+            name,
+            command,
+            index,
+            file_names,
+        } => {
+            let data = CompletionItemData::Argument {
+                file_names: file_names.to_vec(),
+                command: command.into(),
+                index,
+            };
             let text_edit = TextEdit::new(item.range, name.into());
             CompletionItem {
                 label: name.into(),
                 kind: Some(adjust_kind(req, Structure::Argument.completion_kind())),
-                data: Some(CompletionItemData::Argument.into()),
+                data: Some(data.into()),
                 text_edit: Some(CompletionTextEdit::Edit(text_edit)),
-                documentation: image.and_then(|image| image_documentation(&req, &name, image)),
                 ..CompletionItem::default()
             }
         }
@@ -337,10 +623,11 @@ fn convert(req: &FeatureRequest<CompletionParams>, item: Item) -> CompletionItem
             insert_text_format: Some(InsertTextFormat::Snippet),
             ..CompletionItem::new_simple("begin".into(), component_detail(&[]))
         },
-        ItemData::Color { name } => {
+        ItemData::Color { name, detail } => {
             let text_edit = TextEdit::new(item.range, name.into());
             CompletionItem {
                 label: name.into(),
+                detail,
                 kind: Some(adjust_kind(req, Structure::Color.completion_kind())),
                 data: Some(CompletionItemData::Color.into()),
                 text_edit: Some(CompletionTextEdit::Edit(text_edit)),
@@ -357,6 +644,15 @@ fn convert(req: &FeatureRequest<CompletionParams>, item: Item) -> CompletionItem
                 ..CompletionItem::default()
             }
         }
+        ItemData::Unit { name } => {
+            let text_edit = TextEdit::new(item.range, name.into());
+            CompletionItem {
+                kind: Some(adjust_kind(req, Structure::Command.completion_kind())),
+                data: Some(CompletionItemData::Command.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::new_simple(name.into(), "siunitx".into())
+            }
+        }
         ItemData::GlossaryEntry { name } => {
             let text_edit = TextEdit::new(item.range, name.into());
             CompletionItem {
@@ -375,12 +671,6 @@ fn convert(req: &FeatureRequest<CompletionParams>, item: Item) -> CompletionItem
                 kind: Some(adjust_kind(req, kind)),
                 data: Some(CompletionItemData::EntryType.into()),
                 text_edit: Some(CompletionTextEdit::Edit(text_edit)),
-                documentation: ty.documentation.as_ref().map(|doc| {
-                    Documentation::MarkupContent(MarkupContent {
-                        kind: MarkupKind::Markdown,
-                        value: doc.into(),
-                    })
-                }),
                 ..CompletionItem::default()
             }
         }
@@ -391,15 +681,18 @@ fn convert(req: &FeatureRequest<CompletionParams>, item: Item) -> CompletionItem
                 kind: Some(adjust_kind(req, Structure::Field.completion_kind())),
                 data: Some(CompletionItemData::FieldName.into()),
                 text_edit: Some(CompletionTextEdit::Edit(text_edit)),
-                documentation: Some(Documentation::MarkupContent(MarkupContent {
-                    kind: MarkupKind::Markdown,
-                    value: (&field.documentation).into(),
-                })),
                 ..CompletionItem::default()
             }
         }
     };
     new_item.preselect = Some(item.preselect);
+    if deprecated
+        && req
+            .client_capabilities
+            .has_completion_deprecated_tag_support()
+    {
+        new_item.tags = Some(vec![CompletionItemTag::Deprecated]);
+    }
     new_item
 }
 
@@ -415,3 +708,412 @@ fn append_sort_text(mut item: CompletionItem, index: usize) -> CompletionItem {
     };
     item
 }
+
+pub fn resolve_documentation(
+    client_capabilities: &ClientCapabilities,
+    label: &str,
+    data: &CompletionItemData,
+) -> Option<Documentation> {
+    match data {
+        CompletionItemData::ComponentCommand { file_names } => {
+            let image = COMPONENT_DATABASE
+                .find_by_file_names(file_names)?
+                .commands
+                .iter()
+                .find(|cmd| cmd.name == label)?
+                .image
+                .as_ref()?;
+            image_documentation(client_capabilities, label, image)
+        }
+        CompletionItemData::Argument {
+            file_names,
+            command,
+            index,
+        } => {
+            let image = COMPONENT_DATABASE
+                .find_by_file_names(file_names)?
+                .commands
+                .iter()
+                .find(|cmd| &cmd.name == command)?
+                .parameters
+                .get(*index)?
+                .0
+                .iter()
+                .find(|arg| arg.name == label)?
+                .image
+                .as_ref()?;
+            image_documentation(client_capabilities, label, image)
+        }
+        CompletionItemData::EntryType => {
+            let doc = LANGUAGE_DATA.entry_type_documentation(label)?;
+            Some(Documentation::MarkupContent(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: doc.into(),
+            }))
+        }
+        CompletionItemData::FieldName => {
+            let doc = LANGUAGE_DATA.field_documentation(label)?;
+            Some(Documentation::MarkupContent(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: doc.into(),
+            }))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        feature::{CompletionItemSpec, FeatureTester},
+        protocol::{
+            CompletionCapability, CompletionContext, CompletionItemKind,
+            CompletionItemKindCapability, LabelSortOrder, LatexCompletionOptions,
+            TextDocumentClientCapabilities,
+        },
+    };
+    use indoc::indoc;
+
+    fn backslash_trigger() -> CompletionContext {
+        CompletionContext {
+            trigger_kind: CompletionTriggerKind::TriggerCharacter,
+            trigger_character: Some("\\".into()),
+        }
+    }
+
+    fn brace_trigger() -> CompletionContext {
+        CompletionContext {
+            trigger_kind: CompletionTriggerKind::TriggerCharacter,
+            trigger_character: Some("{".into()),
+        }
+    }
+
+    fn client_capabilities() -> ClientCapabilities {
+        ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                completion: Some(CompletionCapability {
+                    completion_item_kind: Some(CompletionItemKindCapability {
+                        value_set: Some(vec![
+                            CompletionItemKind::Method,
+                            CompletionItemKind::Property,
+                        ]),
+                    }),
+                    ..CompletionCapability::default()
+                }),
+                ..TextDocumentClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn label_item() {
+        let actual_items = FeatureTester::new()
+            .file(
+                "main.tex",
+                r#"\begin{figure}\caption{Foo}\label{fig:foo}\end{figure}\ref{}"#,
+            )
+            .main("main.tex")
+            .position(0, 59)
+            .client_capabilities(client_capabilities())
+            .test_completion(CompletionProvider)
+            .await;
+
+        CompletionItemSpec::new("fig:foo")
+            .kind(CompletionItemKind::Method)
+            .detail("Figure")
+            .sort_text("00 fig:foo Figure: Foo")
+            .insert_text("fig:foo")
+            .assert_matches(&actual_items);
+    }
+
+    #[tokio::test]
+    async fn label_definition() {
+        let actual_items = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \label{foo}\ref{foo}
+                        \section{Bar}
+                        \label{}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(2, 7)
+            .test_completion(CompletionProvider)
+            .await;
+
+        let actual_labels: Vec<_> = actual_items.iter().map(|item| &item.label).collect();
+        assert_eq!(actual_labels, vec!["sec:"]);
+    }
+
+    #[tokio::test]
+    async fn group_labels_by_file() {
+        let actual_items = FeatureTester::new()
+            .file("main.tex", r#"\include{foo}\include{bar}\label{qux}\ref{}"#)
+            .file("foo.tex", r#"\label{foo}"#)
+            .file("bar.tex", r#"\section{Bar}\label{bar}"#)
+            .main("main.tex")
+            .position(0, 43)
+            .latex_completion(LatexCompletionOptions {
+                group_labels_by_file: Some(true),
+                ..LatexCompletionOptions::default()
+            })
+            .test_completion(CompletionProvider)
+            .await;
+
+        let actual_details: Vec<_> = actual_items
+            .iter()
+            .map(|item| (item.label.as_str(), item.detail.as_deref()))
+            .collect();
+        assert_eq!(
+            actual_details,
+            vec![
+                ("bar", Some("Section (Bar) (bar.tex)")),
+                ("foo", Some("foo.tex")),
+                ("qux", Some("main.tex")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn group_labels_by_path() {
+        let actual_items = FeatureTester::new()
+            .file("main.tex", r#"\include{sub/foo}\include{foo}\ref{}"#)
+            .file("sub/foo.tex", r#"\label{a}"#)
+            .file("foo.tex", r#"\label{b}"#)
+            .main("main.tex")
+            .position(0, 35)
+            .latex_completion(LatexCompletionOptions {
+                group_labels_by_file: Some(true),
+                ..LatexCompletionOptions::default()
+            })
+            .test_completion(CompletionProvider)
+            .await;
+
+        let actual_labels: Vec<_> = actual_items.iter().map(|item| &item.label).collect();
+        assert_eq!(actual_labels, vec!["b", "a"]);
+    }
+
+    #[tokio::test]
+    async fn label_document_order() {
+        let actual_items = FeatureTester::new()
+            .file("main.tex", r#"\label{foo_bar}\label{fb}\ref{fb}"#)
+            .main("main.tex")
+            .position(0, 32)
+            .latex_completion(LatexCompletionOptions {
+                label_sort_order: Some(LabelSortOrder::Document),
+                ..LatexCompletionOptions::default()
+            })
+            .test_completion(CompletionProvider)
+            .await;
+
+        let actual_labels: Vec<_> = actual_items.iter().map(|item| &item.label).collect();
+        assert_eq!(actual_labels, vec!["foo_bar", "fb"]);
+    }
+
+    #[tokio::test]
+    async fn label_include_order() {
+        let actual_items = FeatureTester::new()
+            .file("main.tex", r#"\label{a}\include{foo}\label{c}\ref{}"#)
+            .file("foo.tex", r#"\label{b}"#)
+            .main("main.tex")
+            .position(0, 36)
+            .latex_completion(LatexCompletionOptions {
+                label_sort_order: Some(LabelSortOrder::Document),
+                ..LatexCompletionOptions::default()
+            })
+            .test_completion(CompletionProvider)
+            .await;
+
+        let actual_labels: Vec<_> = actual_items.iter().map(|item| &item.label).collect();
+        assert_eq!(actual_labels, vec!["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn label_name_with_separators() {
+        let actual_items = FeatureTester::new()
+            .file("main.tex", r#"\label{fig:my_plot-1}\ref{fig:my_p}"#)
+            .main("main.tex")
+            .position(0, 34)
+            .test_completion(CompletionProvider)
+            .await;
+
+        CompletionItemSpec::new("fig:my_plot-1")
+            .insert_text("fig:my_plot-1")
+            .assert_matches(&actual_items);
+    }
+
+    #[tokio::test]
+    async fn empty_label_argument() {
+        let actual_items = FeatureTester::new()
+            .file("main.tex", r#"\label{foo}\ref{}"#)
+            .main("main.tex")
+            .position(0, 16)
+            .completion_context(brace_trigger())
+            .test_completion(CompletionProvider)
+            .await;
+
+        CompletionItemSpec::new("foo")
+            .insert_text("foo")
+            .assert_matches(&actual_items);
+    }
+
+    #[tokio::test]
+    async fn empty_citation_argument() {
+        let actual_items = FeatureTester::new()
+            .file("main.tex", r#"\addbibresource{main.bib}\cite{}"#)
+            .file("main.bib", "@article{foo,}")
+            .main("main.tex")
+            .position(0, 31)
+            .completion_context(brace_trigger())
+            .test_completion(CompletionProvider)
+            .await;
+
+        CompletionItemSpec::new("foo")
+            .insert_text("foo")
+            .assert_matches(&actual_items);
+    }
+
+    #[tokio::test]
+    async fn unsupported_kind() {
+        let actual_items = FeatureTester::new()
+            .file("main.tex", r#"\usetikzlibrary{}"#)
+            .main("main.tex")
+            .position(0, 16)
+            .test_completion(CompletionProvider)
+            .await;
+
+        CompletionItemSpec::new("arrows")
+            .kind(CompletionItemKind::Text)
+            .insert_text("arrows")
+            .assert_matches(&actual_items);
+    }
+
+    #[tokio::test]
+    async fn large_file() {
+        let actual_items = FeatureTester::new()
+            .file("main.tex", "foo\n\\\nbar")
+            .main("main.tex")
+            .position(1, 1)
+            .max_file_lines(2)
+            .test_completion(CompletionProvider)
+            .await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn math_symbol_inside_ensuremath() {
+        let actual_items = FeatureTester::new()
+            .file("main.tex", r#"\ensuremath{\alp}"#)
+            .main("main.tex")
+            .position(0, 16)
+            .test_completion(CompletionProvider)
+            .await;
+
+        assert_eq!(actual_items[0].label, "alpha");
+    }
+
+    #[tokio::test]
+    async fn trigger_on_backslash() {
+        let actual_items = FeatureTester::new()
+            .file("main.tex", r#"\"#)
+            .main("main.tex")
+            .position(0, 1)
+            .completion_context(backslash_trigger())
+            .test_completion(CompletionProvider)
+            .await;
+
+        assert!(!actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn trigger_on_backslash_disabled() {
+        let actual_items = FeatureTester::new()
+            .file("main.tex", r#"\"#)
+            .main("main.tex")
+            .position(0, 1)
+            .completion_context(backslash_trigger())
+            .latex_completion(LatexCompletionOptions {
+                trigger_on_backslash: Some(false),
+                ..LatexCompletionOptions::default()
+            })
+            .test_completion(CompletionProvider)
+            .await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn trigger_on_backslash_disabled_invoked() {
+        let actual_items = FeatureTester::new()
+            .file("main.tex", r#"\"#)
+            .main("main.tex")
+            .position(0, 1)
+            .completion_context(CompletionContext {
+                trigger_kind: CompletionTriggerKind::Invoked,
+                trigger_character: None,
+            })
+            .latex_completion(LatexCompletionOptions {
+                trigger_on_backslash: Some(false),
+                ..LatexCompletionOptions::default()
+            })
+            .test_completion(CompletionProvider)
+            .await;
+
+        assert!(!actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn label_type_from_prefix() {
+        let actual_items = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \begin{table}\caption{Bar}\label{fig:bar}\end{table}
+                        \begin{figure}\caption{Foo}\label{fig:foo}\end{figure}
+                        \ref{fig:}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(2, 9)
+            .test_completion(CompletionProvider)
+            .await;
+
+        let actual_labels: Vec<_> = actual_items
+            .iter()
+            .map(|item| item.label.as_str())
+            .collect();
+        assert_eq!(actual_labels, vec!["fig:foo", "fig:bar"]);
+    }
+
+    #[tokio::test]
+    async fn truncated_document() {
+        let text = concat!(
+            "\\documentclass{article}\n",
+            "\\usepackage{amsmath}\n",
+            "\\begin{document}\n",
+            "\\section{Foo}\\label{sec:foo}\n",
+            "\\begin{itemize}\\item[a] $\\frac{1}{2$ \\ref{sec:\n",
+            "\\end{document}",
+        );
+
+        for end in 0..=text.len() {
+            let prefix = text[..end].trim();
+            let line = prefix.matches('\n').count() as u64;
+            let character = prefix.lines().last().unwrap_or_default().chars().count() as u64;
+            FeatureTester::new()
+                .file("main.tex", prefix)
+                .main("main.tex")
+                .position(line, character)
+                .test_completion(CompletionProvider)
+                .await;
+        }
+    }
+}
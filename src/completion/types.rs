@@ -1,5 +1,5 @@
 use crate::{
-    protocol::{Range, Uri},
+    protocol::{Range, TextEdit, Uri},
     syntax::{BibtexEntryTypeDoc, BibtexFieldDoc, Structure},
 };
 use serde::{Deserialize, Serialize};
@@ -28,7 +28,6 @@ impl<'a> Item<'a> {
 pub enum ItemData<'a> {
     ComponentCommand {
         name: &'a str,
-        image: Option<&'a str>,
         glyph: Option<&'a str>,
         file_names: &'a [String],
     },
@@ -48,6 +47,15 @@ pub enum ItemData<'a> {
         header: Option<String>,
         footer: Option<String>,
         text: String,
+        proximity: Option<u64>,
+        prefix_edit: Option<TextEdit>,
+        file: Option<&'a Uri>,
+        preferred: bool,
+        order: Option<usize>,
+        fallback: bool,
+    },
+    LabelSuggestion {
+        name: String,
     },
     PgfLibrary {
         name: &'a str,
@@ -55,12 +63,39 @@ pub enum ItemData<'a> {
     TikzLibrary {
         name: &'a str,
     },
+    BeamerFrameOption {
+        name: &'a str,
+    },
+    GraphicsOption {
+        name: &'a str,
+    },
+    ItemLabel {
+        name: &'a str,
+    },
+    ColumnSpecifier {
+        name: &'a str,
+        snippet: &'a str,
+        detail: &'a str,
+    },
+    Snippet {
+        label: &'a str,
+        body: &'a str,
+    },
+    UrlScheme {
+        name: &'a str,
+    },
+    PageStyle {
+        name: &'a str,
+    },
     Package {
         name: Cow<'a, str>,
     },
     Class {
         name: Cow<'a, str>,
     },
+    BibliographyStyle {
+        name: Cow<'a, str>,
+    },
     File {
         name: String,
     },
@@ -72,18 +107,25 @@ pub enum ItemData<'a> {
         key: &'a str,
         text: String,
         ty: Structure,
+        conflicts: Vec<&'a Uri>,
     },
     Argument {
         name: &'a str,
-        image: Option<&'a str>,
+        command: &'a str,
+        index: usize,
+        file_names: &'a [String],
     },
     BeginCommand,
     Color {
         name: &'a str,
+        detail: Option<String>,
     },
     ColorModel {
         name: &'a str,
     },
+    Unit {
+        name: &'a str,
+    },
     GlossaryEntry {
         name: &'a str,
     },
@@ -103,17 +145,27 @@ impl<'a> ItemData<'a> {
             Self::UserCommand { name } => name,
             Self::UserEnvironment { name } => name,
             Self::Label { name, .. } => name,
+            Self::LabelSuggestion { name } => &name,
             Self::Class { name } => &name,
             Self::Package { name } => &name,
             Self::PgfLibrary { name } => name,
             Self::TikzLibrary { name } => name,
+            Self::BeamerFrameOption { name } => name,
+            Self::GraphicsOption { name } => name,
+            Self::ItemLabel { name } => name,
+            Self::ColumnSpecifier { name, .. } => name,
+            Self::Snippet { label, .. } => label,
+            Self::UrlScheme { name } => name,
+            Self::PageStyle { name } => name,
+            Self::BibliographyStyle { name } => name,
             Self::File { name } => &name,
             Self::Directory { name } => &name,
             Self::Citation { key, .. } => key,
             Self::Argument { name, .. } => name,
             Self::BeginCommand => "begin",
-            Self::Color { name } => name,
+            Self::Color { name, .. } => name,
             Self::ColorModel { name } => name,
+            Self::Unit { name } => name,
             Self::GlossaryEntry { name } => name,
             Self::EntryType { ty } => &ty.name,
             Self::Field { field } => &field.name,
@@ -125,21 +177,40 @@ impl<'a> ItemData<'a> {
 #[serde(rename_all = "camelCase")]
 pub enum CompletionItemData {
     Command,
+    ComponentCommand {
+        file_names: Vec<String>,
+    },
     CommandSnippet,
     Environment,
     Label,
+    LabelSuggestion,
     Folder,
     File,
     PgfLibrary,
     TikzLibrary,
+    BeamerFrameOption,
+    GraphicsOption,
+    ItemLabel,
+    ColumnSpecifier,
+    Snippet,
+    UrlScheme,
+    PageStyle,
+    BibliographyStyle,
     Color,
     ColorModel,
     Package,
     Class,
     EntryType,
     FieldName,
-    Citation { uri: Uri, key: String },
-    Argument,
+    Citation {
+        uri: Uri,
+        key: String,
+    },
+    Argument {
+        file_names: Vec<String>,
+        command: String,
+        index: usize,
+    },
     GlossaryEntry,
 }
 
@@ -1,8 +1,8 @@
 use crate::{
     feature::FeatureRequest,
     protocol::{
-        CompletionItemKind, CompletionParams, Documentation, MarkupContent, MarkupKind, Position,
-        RangeExt,
+        ClientCapabilities, CompletionItemKind, CompletionParams, Documentation, MarkupContent,
+        MarkupKind, Position, RangeExt,
     },
     syntax::{bibtex, latex, SyntaxNode},
     workspace::DocumentContent,
@@ -74,11 +74,11 @@ pub fn component_detail(file_names: &[String]) -> String {
 }
 
 pub fn image_documentation(
-    req: &FeatureRequest<CompletionParams>,
+    client_capabilities: &ClientCapabilities,
     name: &str,
     image: &str,
 ) -> Option<Documentation> {
-    if supports_images(req) {
+    if supports_images(client_capabilities) {
         Some(Documentation::MarkupContent(MarkupContent {
             kind: MarkupKind::Markdown,
             value: format!(
@@ -91,8 +91,8 @@ pub fn image_documentation(
     }
 }
 
-fn supports_images(req: &FeatureRequest<CompletionParams>) -> bool {
-    req.client_capabilities
+fn supports_images(client_capabilities: &ClientCapabilities) -> bool {
+    client_capabilities
         .text_document
         .as_ref()
         .and_then(|cap| cap.completion.as_ref())
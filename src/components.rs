@@ -25,6 +25,13 @@ impl Database {
         self.find(&sty).is_some() || self.find(&cls).is_some()
     }
 
+    pub fn find_by_file_names(&self, file_names: &[String]) -> Option<&Component> {
+        match file_names.first() {
+            Some(file_name) => self.find(file_name),
+            None => Some(self.kernel()),
+        }
+    }
+
     pub fn kernel(&self) -> &Component {
         self.components
             .iter()
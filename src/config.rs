@@ -1,6 +1,6 @@
 use crate::protocol::*;
 use futures::lock::Mutex;
-use log::{error, warn};
+use log::error;
 use serde::de::DeserializeOwned;
 use std::sync::Arc;
 
@@ -9,6 +9,8 @@ pub struct ConfigManager<C> {
     client: Arc<C>,
     client_capabilities: Arc<ClientCapabilities>,
     options: Mutex<Options>,
+    config: Mutex<Arc<Config>>,
+    reported_errors: Mutex<Option<Vec<String>>>,
 }
 
 impl<C: LspClient + Send + Sync + 'static> ConfigManager<C> {
@@ -17,6 +19,8 @@ impl<C: LspClient + Send + Sync + 'static> ConfigManager<C> {
             client,
             client_capabilities,
             options: Mutex::default(),
+            config: Mutex::default(),
+            reported_errors: Mutex::default(),
         }
     }
 
@@ -24,6 +28,10 @@ impl<C: LspClient + Send + Sync + 'static> ConfigManager<C> {
         self.options.lock().await.clone()
     }
 
+    pub async fn config(&self) -> Arc<Config> {
+        Arc::clone(&*self.config.lock().await)
+    }
+
     pub async fn register(&self) {
         if !self.client_capabilities.has_pull_configuration_support()
             && self.client_capabilities.has_push_configuration_support()
@@ -47,35 +55,69 @@ impl<C: LspClient + Send + Sync + 'static> ConfigManager<C> {
     }
 
     pub async fn push(&self, options: serde_json::Value) {
-        match serde_json::from_value(options) {
+        match serde_json::from_value::<Options>(options) {
             Ok(options) => {
+                let errors = options.validate().iter().map(ToString::to_string).collect();
+                *self.config.lock().await = Arc::new(Config::new(&options));
                 *self.options.lock().await = options;
+                self.report_errors(errors).await;
             }
             Err(why) => {
-                error!("Invalid configuration: {}", why);
+                self.report_errors(vec![why.to_string()]).await;
             }
         }
     }
 
     pub async fn pull(&self) -> bool {
         if self.client_capabilities.has_pull_configuration_support() {
-            let latex = self.pull_section("latex").await;
-            let bibtex = self.pull_section("bibtex").await;
+            let mut errors = Vec::new();
+            let latex = self.pull_section("latex", &mut errors).await;
+            let bibtex = self.pull_section("bibtex", &mut errors).await;
 
             let new_options = Options {
                 latex: Some(latex),
                 bibtex: Some(bibtex),
             };
+            errors.extend(new_options.validate().iter().map(ToString::to_string));
+
             let mut old_options = self.options.lock().await;
             let has_changed = *old_options != new_options;
+            if has_changed {
+                *self.config.lock().await = Arc::new(Config::new(&new_options));
+            }
             *old_options = new_options;
+            drop(old_options);
+
+            self.report_errors(errors).await;
             has_changed
         } else {
             false
         }
     }
 
-    async fn pull_section<T: DeserializeOwned + Default>(&self, section: &str) -> T {
+    async fn report_errors(&self, errors: Vec<String>) {
+        let mut reported_errors = self.reported_errors.lock().await;
+        if reported_errors.as_ref() == Some(&errors) {
+            return;
+        }
+        *reported_errors = Some(errors.clone());
+        drop(reported_errors);
+
+        for why in errors {
+            error!("Invalid configuration: {}", why);
+            let params = ShowMessageParams {
+                typ: MessageType::Error,
+                message: format!("Invalid configuration: {}", why),
+            };
+            self.client.show_message(params).await;
+        }
+    }
+
+    async fn pull_section<T: DeserializeOwned + Default>(
+        &self,
+        section: &str,
+        errors: &mut Vec<String>,
+    ) -> T {
         let params = ConfigurationParams {
             items: vec![ConfigurationItem {
                 section: Some(section.into()),
@@ -86,8 +128,8 @@ impl<C: LspClient + Send + Sync + 'static> ConfigManager<C> {
         match self.client.configuration(params).await {
             Ok(json) => match serde_json::from_value::<Vec<T>>(json) {
                 Ok(config) => config.into_iter().next().unwrap(),
-                Err(_) => {
-                    warn!("Invalid configuration: {}", section);
+                Err(why) => {
+                    errors.push(format!("{}: {}", section, why));
                     T::default()
                 }
             },
@@ -37,17 +37,32 @@ impl LatexCitationDefinitionProvider {
     }
 
     fn find_definitions(doc: &Document, reference: &latex::Token, links: &mut Vec<LocationLink>) {
-        if let DocumentContent::Bibtex(tree) = &doc.content {
-            for entry in tree
-                .children(tree.root)
-                .filter_map(|node| tree.as_entry(node))
-            {
-                if let Some(key) = &entry.key {
+        match &doc.content {
+            DocumentContent::Bibtex(tree) => {
+                for entry in tree
+                    .children(tree.root)
+                    .filter_map(|node| tree.as_entry(node))
+                {
+                    if let Some(key) = &entry.key {
+                        if key.text() == reference.text() {
+                            links.push(LocationLink {
+                                origin_selection_range: Some(reference.range()),
+                                target_uri: doc.uri.clone().into(),
+                                target_range: entry.range(),
+                                target_selection_range: key.range(),
+                            });
+                        }
+                    }
+                }
+            }
+            DocumentContent::Latex(table) => {
+                for bib_item in &table.bib_items {
+                    let key = bib_item.key(&table);
                     if key.text() == reference.text() {
                         links.push(LocationLink {
                             origin_selection_range: Some(reference.range()),
                             target_uri: doc.uri.clone().into(),
-                            target_range: entry.range(),
+                            target_range: table[bib_item.parent].range(),
                             target_selection_range: key.range(),
                         });
                     }
@@ -115,4 +130,33 @@ mod tests {
 
         assert_eq!(actual_links, exepcted_links);
     }
+
+    #[tokio::test]
+    async fn bib_item() {
+        let actual_links = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \cite{foo}
+                        \begin{thebibliography}{9}
+                        \bibitem{foo} Bar
+                        \end{thebibliography}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(0, 7)
+            .test_position(LatexCitationDefinitionProvider)
+            .await;
+
+        let exepcted_links = vec![LocationLink {
+            origin_selection_range: Some(Range::new_simple(0, 6, 0, 9)),
+            target_uri: FeatureTester::uri("main.tex").into(),
+            target_range: Range::new_simple(2, 0, 2, 13),
+            target_selection_range: Range::new_simple(2, 9, 2, 12),
+        }];
+
+        assert_eq!(actual_links, exepcted_links);
+    }
 }
@@ -0,0 +1,114 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{LocationLink, Range, RangeExt, TextDocumentPositionParams},
+    syntax::SyntaxNode,
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexIncludeDefinitionProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexIncludeDefinitionProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Vec<LocationLink>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut links = Vec::new();
+        if let DocumentContent::Latex(table) = &req.current().content {
+            for include in &table.includes {
+                let paths = include.paths(&table);
+                for (path, targets) in paths.iter().zip(&include.all_targets) {
+                    if !path.range().contains(req.params.position) {
+                        continue;
+                    }
+
+                    if let Some(doc) = req.snapshot().find_target(targets) {
+                        let target_range = Range::new_simple(0, 0, 0, 0);
+                        links.push(LocationLink {
+                            origin_selection_range: Some(path.range()),
+                            target_uri: doc.uri.clone().into(),
+                            target_range,
+                            target_selection_range: target_range,
+                        });
+                    }
+                }
+            }
+        }
+        links
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feature::FeatureTester;
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let actual_links = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_position(LatexIncludeDefinitionProvider)
+            .await;
+
+        assert!(actual_links.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let actual_links = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .position(0, 0)
+            .test_position(LatexIncludeDefinitionProvider)
+            .await;
+
+        assert!(actual_links.is_empty());
+    }
+
+    #[tokio::test]
+    async fn equivalent_paths() {
+        let actual_links = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \input{bar}
+                        \input{bar.tex}
+                        \input{./bar}
+                    "#
+                ),
+            )
+            .file("bar.tex", r#""#)
+            .main("foo.tex")
+            .position(2, 9)
+            .test_position(LatexIncludeDefinitionProvider)
+            .await;
+
+        let target_range = Range::new_simple(0, 0, 0, 0);
+        let expected_links = vec![LocationLink {
+            origin_selection_range: Some(Range::new_simple(2, 7, 2, 12)),
+            target_uri: FeatureTester::uri("bar.tex").into(),
+            target_range,
+            target_selection_range: target_range,
+        }];
+
+        assert_eq!(actual_links, expected_links);
+    }
+
+    #[tokio::test]
+    async fn missing_file() {
+        let actual_links = FeatureTester::new()
+            .file("foo.tex", r#"\input{bar}"#)
+            .main("foo.tex")
+            .position(0, 9)
+            .test_position(LatexIncludeDefinitionProvider)
+            .await;
+
+        assert!(actual_links.is_empty());
+    }
+}
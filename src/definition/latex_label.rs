@@ -1,9 +1,9 @@
 use crate::{
     feature::{DocumentView, FeatureProvider, FeatureRequest},
     outline::{Outline, OutlineContext, OutlineContextItem},
-    protocol::{LocationLink, Options, RangeExt, TextDocumentPositionParams},
+    protocol::{LocationLink, Options, TextDocumentPositionParams},
     symbol::build_section_tree,
-    syntax::{latex, LatexLabelKind, SyntaxNode},
+    syntax::{latex, SyntaxNode},
     workspace::DocumentContent,
 };
 use async_trait::async_trait;
@@ -45,10 +45,8 @@ impl LatexLabelDefinitionProvider {
     fn find_reference(req: &FeatureRequest<TextDocumentPositionParams>) -> Option<&latex::Token> {
         if let DocumentContent::Latex(table) = &req.current().content {
             table
-                .labels
-                .iter()
-                .flat_map(|label| label.names(&table))
-                .find(|label| label.range().contains(req.params.position))
+                .find_label_name(req.params.position)
+                .map(|label| label.name)
         } else {
             None
         }
@@ -64,29 +62,27 @@ impl LatexLabelDefinitionProvider {
         if let DocumentContent::Latex(table) = &view.current.content {
             let outline = Outline::analyze(view, options, current_dir);
             let section_tree = build_section_tree(view, table, options, current_dir);
-            for label in &table.labels {
-                if label.kind == LatexLabelKind::Definition {
-                    let context = OutlineContext::parse(view, &outline, *label);
-                    for name in label.names(&table) {
-                        if name.text() == reference.text() {
-                            let target_range = if let Some(OutlineContextItem::Section { .. }) =
-                                context.as_ref().map(|ctx| &ctx.item)
-                            {
-                                section_tree
-                                    .find(reference.text())
-                                    .map(|sec| sec.full_range)
-                            } else {
-                                context.as_ref().map(|ctx| ctx.range)
-                            };
-
-                            links.push(LocationLink {
-                                origin_selection_range: Some(reference.range()),
-                                target_uri: view.current.uri.clone().into(),
-                                target_range: target_range
-                                    .unwrap_or_else(|| table[label.parent].range()),
-                                target_selection_range: table[label.parent].range(),
-                            });
-                        }
+            for label in table.label_definitions() {
+                let context = OutlineContext::parse(view, &outline, *label);
+                for name in label.names(&table) {
+                    if name.text() == reference.text() {
+                        let target_range = if let Some(OutlineContextItem::Section { .. }) =
+                            context.as_ref().map(|ctx| &ctx.item)
+                        {
+                            section_tree
+                                .find(reference.text())
+                                .map(|sec| sec.full_range)
+                        } else {
+                            context.as_ref().map(|ctx| ctx.range)
+                        };
+
+                        links.push(LocationLink {
+                            origin_selection_range: Some(reference.range()),
+                            target_uri: view.current.uri.clone().into(),
+                            target_range: target_range
+                                .unwrap_or_else(|| table[label.parent].range()),
+                            target_selection_range: table[label.parent].range(),
+                        });
                     }
                 }
             }
@@ -97,7 +93,10 @@ impl LatexLabelDefinitionProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{feature::FeatureTester, protocol::Range};
+    use crate::{
+        feature::FeatureTester,
+        protocol::{Range, RangeExt},
+    };
     use indoc::indoc;
 
     #[tokio::test]
@@ -124,6 +123,85 @@ mod tests {
         assert!(actual_links.is_empty());
     }
 
+    #[tokio::test]
+    async fn cleveref_name() {
+        let actual_links = FeatureTester::new()
+            .file("main.tex", "\\label{foo}\n\\lcnamecref{foo}")
+            .main("main.tex")
+            .position(1, 13)
+            .test_position(LatexLabelDefinitionProvider)
+            .await;
+
+        let expected_links = vec![LocationLink {
+            origin_selection_range: Some(Range::new_simple(1, 12, 1, 15)),
+            target_uri: FeatureTester::uri("main.tex").into(),
+            target_range: Range::new_simple(0, 0, 0, 11),
+            target_selection_range: Range::new_simple(0, 0, 0, 11),
+        }];
+
+        assert_eq!(actual_links, expected_links);
+    }
+
+    #[tokio::test]
+    async fn zref_label() {
+        let actual_links = FeatureTester::new()
+            .file("main.tex", "\\zlabel{foo}\n\\zref{foo}")
+            .main("main.tex")
+            .position(1, 7)
+            .test_position(LatexLabelDefinitionProvider)
+            .await;
+
+        let expected_links = vec![LocationLink {
+            origin_selection_range: Some(Range::new_simple(1, 6, 1, 9)),
+            target_uri: FeatureTester::uri("main.tex").into(),
+            target_range: Range::new_simple(0, 0, 0, 12),
+            target_selection_range: Range::new_simple(0, 0, 0, 12),
+        }];
+
+        assert_eq!(actual_links, expected_links);
+    }
+
+    #[tokio::test]
+    async fn inside_of_listing_caption() {
+        let actual_links = FeatureTester::new()
+            .file(
+                "main.tex",
+                "\\label{x}\n\\begin{lstlisting}[caption={See \\ref{x}}]\n\\end{lstlisting}",
+            )
+            .main("main.tex")
+            .position(1, 37)
+            .test_position(LatexLabelDefinitionProvider)
+            .await;
+
+        let expected_links = vec![LocationLink {
+            origin_selection_range: Some(Range::new_simple(1, 37, 1, 38)),
+            target_uri: FeatureTester::uri("main.tex").into(),
+            target_range: Range::new_simple(0, 0, 0, 9),
+            target_selection_range: Range::new_simple(0, 0, 0, 9),
+        }];
+
+        assert_eq!(actual_links, expected_links);
+    }
+
+    #[tokio::test]
+    async fn name_reference() {
+        let actual_links = FeatureTester::new()
+            .file("main.tex", "\\label{foo}\n\\nameref{foo}")
+            .main("main.tex")
+            .position(1, 10)
+            .test_position(LatexLabelDefinitionProvider)
+            .await;
+
+        let expected_links = vec![LocationLink {
+            origin_selection_range: Some(Range::new_simple(1, 9, 1, 12)),
+            target_uri: FeatureTester::uri("main.tex").into(),
+            target_range: Range::new_simple(0, 0, 0, 11),
+            target_selection_range: Range::new_simple(0, 0, 0, 11),
+        }];
+
+        assert_eq!(actual_links, expected_links);
+    }
+
     #[tokio::test]
     async fn unknown_context() {
         let actual_links = FeatureTester::new()
@@ -152,4 +230,16 @@ mod tests {
 
         assert_eq!(actual_links, expected_links);
     }
+
+    #[tokio::test]
+    async fn commented_label() {
+        let actual_links = FeatureTester::new()
+            .file("main.tex", "%\\label{foo}\n\\ref{foo}")
+            .main("main.tex")
+            .position(1, 6)
+            .test_position(LatexLabelDefinitionProvider)
+            .await;
+
+        assert!(actual_links.is_empty());
+    }
 }
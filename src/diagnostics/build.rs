@@ -35,6 +35,10 @@ impl BuildDiagnosticsProvider {
         }
     }
 
+    pub async fn set(&self, diagnostics_by_uri: HashMap<Uri, Vec<Diagnostic>>) {
+        *self.diagnostics_by_uri.lock().await = diagnostics_by_uri;
+    }
+
     pub async fn update(
         &self,
         snapshot: &Snapshot,
@@ -76,22 +80,27 @@ impl BuildDiagnosticsProvider {
 
     async fn update_diagnostics(&self, tex_uri: &Uri, log_path: &Path) -> io::Result<bool> {
         let log = String::from_utf8_lossy(&fs::read(log_path).await?).into_owned();
-        let mut diagnostics_by_uri = self.diagnostics_by_uri.lock().await;
-        diagnostics_by_uri.clear();
-        for error in parse_build_log(tex_uri, &log) {
-            let diagnostics = diagnostics_by_uri
-                .entry(error.uri.clone())
-                .or_insert_with(Vec::new);
-            diagnostics.push(error.into());
-        }
+        *self.diagnostics_by_uri.lock().await = parse_build_diagnostics(tex_uri, &log);
         Ok(true)
     }
 }
 
+pub fn parse_build_diagnostics(tex_uri: &Uri, log: &str) -> HashMap<Uri, Vec<Diagnostic>> {
+    let mut diagnostics_by_uri = HashMap::new();
+    for error in parse_build_log(tex_uri, log) {
+        let diagnostics = diagnostics_by_uri
+            .entry(error.uri.clone())
+            .or_insert_with(Vec::new);
+        diagnostics.push(error.into());
+    }
+    diagnostics_by_uri
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum BuildErrorKind {
     Error,
     Warning,
+    Information,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -119,6 +128,7 @@ impl Into<Diagnostic> for BuildError {
         let severity = match self.kind {
             BuildErrorKind::Error => DiagnosticSeverity::Error,
             BuildErrorKind::Warning => DiagnosticSeverity::Warning,
+            BuildErrorKind::Information => DiagnosticSeverity::Information,
         };
         let range = Range::new(position, position);
         Diagnostic {
@@ -149,9 +159,15 @@ pub static WARNING_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new("(LaTeX|Package [a-zA-Z_\\-]+) Warning: (?P<msg>[^\r\n]*)").unwrap());
 
 pub static BAD_BOX_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new("(?P<msg>(Ov|Und)erfull \\\\[hv]box[^\r\n]*lines? (?P<line>\\d+)[^\r\n]*)").unwrap()
+    Regex::new(
+        "(?P<msg>(Ov|Und)erfull \\\\[hv]box[^\r\n]*lines? (?P<line>\\d+)[^\r\n]*)(\r?\n(?P<text>[ \t]*(\\[\\]|\\\\[a-zA-Z0-9]+/)[^\r\n]*))?",
+    )
+    .unwrap()
 });
 
+pub static BAD_BOX_TEXT_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("\\[\\]|\\\\[a-zA-Z0-9]+/\\S*\\s?").unwrap());
+
 fn parse_build_log(uri: &Uri, log: &str) -> Vec<BuildError> {
     let log = prepare_log(log);
     let mut ranges: Vec<FileRange> = FILE_REGEX
@@ -162,7 +178,13 @@ fn parse_build_log(uri: &Uri, log: &str) -> Vec<BuildError> {
 
     let tex_errors = extract_matches(&log, &uri, &ranges, &TEX_ERROR_REGEX, BuildErrorKind::Error);
     let warnings = extract_matches(&log, &uri, &ranges, &WARNING_REGEX, BuildErrorKind::Warning);
-    let bad_boxes = extract_matches(&log, &uri, &ranges, &BAD_BOX_REGEX, BuildErrorKind::Warning);
+    let bad_boxes = extract_matches(
+        &log,
+        &uri,
+        &ranges,
+        &BAD_BOX_REGEX,
+        BuildErrorKind::Information,
+    );
 
     vec![tex_errors, warnings, bad_boxes].concat()
 }
@@ -177,7 +199,7 @@ fn extract_matches(
     let mut errors = Vec::new();
     for result in regex.find_iter(&log) {
         let captures = regex.captures(&log[result.start()..result.end()]).unwrap();
-        let message = captures
+        let mut message = captures
             .name("msg")
             .or_else(|| captures.name("msg1"))
             .or_else(|| captures.name("msg2"))
@@ -188,6 +210,14 @@ fn extract_matches(
             .unwrap_or_default()
             .to_owned();
 
+        if let Some(text) = captures.name("text") {
+            let text = BAD_BOX_TEXT_REGEX.replace_all(text.as_str(), "");
+            if !text.trim().is_empty() {
+                message.push('\n');
+                message.push_str(text.trim());
+            }
+        }
+
         if let Some(range) = ranges.iter().find(|range| range.contains(result.start())) {
             let line = captures
                 .name("line")
@@ -399,19 +429,68 @@ mod tests {
 
         let error1 = BuildError::new(
             create_uri("parent.tex"),
-            BuildErrorKind::Warning,
-            "Overfull \\hbox (200.00162pt too wide) in paragraph at lines 8--9".into(),
+            BuildErrorKind::Information,
+            format!(
+                "Overfull \\hbox (200.00162pt too wide) in paragraph at lines 8--9\n{}",
+                "a".repeat(106)
+            ),
             Some(7),
         );
         let error2 = BuildError::new(
             create_uri("parent.tex"),
-            BuildErrorKind::Warning,
+            BuildErrorKind::Information,
             "Overfull \\vbox (3.19998pt too high) detected at line 23".into(),
             Some(22),
         );
         assert_eq!(actual_errors, vec![error1, error2]);
     }
 
+    #[test]
+    fn underfull_box() {
+        let log = indoc!(
+            r#"
+                (./parent.tex
+                Underfull \hbox (badness 10000) in paragraph at lines 5--6
+                []\OT1/cmr/m/n/10 foo bar
+                 []
+
+                )
+            "#
+        );
+
+        let actual_errors = parse_build_log(&create_uri("parent.tex"), log);
+
+        let expected_errors = vec![BuildError::new(
+            create_uri("parent.tex"),
+            BuildErrorKind::Information,
+            "Underfull \\hbox (badness 10000) in paragraph at lines 5--6\nfoo bar".into(),
+            Some(4),
+        )];
+        assert_eq!(actual_errors, expected_errors);
+    }
+
+    #[test]
+    fn bad_box_without_text() {
+        let log = indoc!(
+            r#"
+                (./parent.tex
+                Overfull \hbox (1.0pt too wide) detected at line 12
+                LaTeX Font Info:    Trying to load font information for U+msa on input line 13.
+                )
+            "#
+        );
+
+        let actual_errors = parse_build_log(&create_uri("parent.tex"), log);
+
+        let expected_errors = vec![BuildError::new(
+            create_uri("parent.tex"),
+            BuildErrorKind::Information,
+            "Overfull \\hbox (1.0pt too wide) detected at line 12".into(),
+            Some(11),
+        )];
+        assert_eq!(actual_errors, expected_errors);
+    }
+
     #[test]
     fn related() {
         let log = indoc!(
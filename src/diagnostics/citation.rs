@@ -0,0 +1,155 @@
+use crate::{
+    feature::DocumentView,
+    protocol::{Diagnostic, DiagnosticSeverity},
+    syntax::SyntaxNode,
+    workspace::DocumentContent,
+};
+use std::collections::HashSet;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct CitationDiagnosticsProvider;
+
+impl CitationDiagnosticsProvider {
+    pub fn get(self, view: &DocumentView) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let table = match &view.current.content {
+            DocumentContent::Latex(table) => table,
+            DocumentContent::Bibtex(_) => return diagnostics,
+        };
+
+        let mut has_bibliography = false;
+        let mut keys = HashSet::new();
+        for doc in &view.related {
+            match &doc.content {
+                DocumentContent::Latex(table) => {
+                    for bib_item in &table.bib_items {
+                        has_bibliography = true;
+                        keys.insert(bib_item.key(&table).text());
+                    }
+                }
+                DocumentContent::Bibtex(tree) => {
+                    has_bibliography = true;
+                    tree.children(tree.root)
+                        .filter_map(|node| tree.as_entry(node))
+                        .filter(|entry| !entry.is_comment())
+                        .filter_map(|entry| entry.key.as_ref())
+                        .for_each(|key| {
+                            keys.insert(key.text());
+                        });
+                }
+            }
+        }
+
+        if !has_bibliography {
+            return diagnostics;
+        }
+
+        for citation in &table.citations {
+            for key in citation.keys(&table) {
+                if key.text() != "*" && !keys.contains(key.text()) {
+                    diagnostics.push(Diagnostic {
+                        source: Some("latex".into()),
+                        range: key.range(),
+                        message: format!("Undefined citation: {}", key.text()),
+                        severity: Some(DiagnosticSeverity::Warning),
+                        code: None,
+                        related_information: None,
+                        tags: None,
+                    });
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        diagnostics::helper::analyze_view,
+        protocol::{Range, RangeExt},
+    };
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn defined_citation() {
+        let diagnostics = analyze_view(
+            vec![
+                ("foo.tex", r#"\addbibresource{foo.bib}\cite{bar}"#),
+                ("foo.bib", r#"@article{bar, title = {Baz}}"#),
+            ],
+            |view| CitationDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn undefined_citation() {
+        let diagnostics = analyze_view(
+            vec![
+                (
+                    "foo.tex",
+                    indoc!(
+                        r#"
+                        \addbibresource{foo.bib}
+                        \cite{bar, qux}
+                        \nocite{*}
+                    "#
+                    ),
+                ),
+                ("foo.bib", r#"@article{bar, title = {Baz}}"#),
+            ],
+            |view| CitationDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(1, 11, 1, 14));
+        assert_eq!(diagnostics[0].message, "Undefined citation: qux");
+    }
+
+    #[tokio::test]
+    async fn unrelated_bibliography() {
+        let diagnostics = analyze_view(
+            vec![
+                ("foo.tex", r#"\addbibresource{foo.bib}\cite{qux}"#),
+                ("foo.bib", r#"@article{bar, title = {Baz}}"#),
+                ("bar.bib", r#"@article{qux, title = {Baz}}"#),
+            ],
+            |view| CitationDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert_eq!(diagnostics.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn bib_item() {
+        let diagnostics = analyze_view(
+            vec![(
+                "foo.tex",
+                indoc!(
+                    r#"
+                    \cite{bar, qux}
+                    \begin{thebibliography}{9}
+                    \bibitem{bar} Baz
+                    \end{thebibliography}
+                "#
+                ),
+            )],
+            |view| CitationDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "Undefined citation: qux");
+    }
+
+    #[tokio::test]
+    async fn no_bibliography() {
+        let diagnostics = analyze_view(vec![("foo.tex", r#"\cite{bar}"#)], |view| {
+            CitationDiagnosticsProvider.get(view)
+        })
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{
+    feature::DocumentView,
+    protocol::{Diagnostic, DiagnosticSeverity},
+    syntax::{latex, SyntaxNode},
+    workspace::DocumentContent,
+};
+
+const NEW_COMMANDS: &[&str] = &["\\newcommand", "\\newcommand*"];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct CommandRedefinitionDiagnosticsProvider;
+
+impl CommandRedefinitionDiagnosticsProvider {
+    pub fn get(self, view: &DocumentView) -> Vec<Diagnostic> {
+        let table = match &view.current.content {
+            DocumentContent::Latex(table) => table,
+            DocumentContent::Bibtex(_) => return Vec::new(),
+        };
+
+        let components = view.components();
+        let order = view.include_order();
+        let mut diagnostics = Vec::new();
+        for def in table
+            .command_definitions
+            .iter()
+            .filter(|def| is_new_command(table, def))
+        {
+            let name = def.definition_name(&table);
+            let is_builtin = components
+                .iter()
+                .flat_map(|comp| comp.commands.iter())
+                .any(|cmd| cmd.name == name[1..]);
+
+            let key = order.key(&view.current.uri, table[def.parent].start());
+            let is_user_defined = view.related.iter().any(|doc| match &doc.content {
+                DocumentContent::Latex(other_table) => {
+                    other_table.command_definitions.iter().any(|other| {
+                        is_new_command(other_table, other)
+                            && other.definition_name(&other_table) == name
+                            && order.key(&doc.uri, other_table[other.parent].start()) < key
+                    })
+                }
+                DocumentContent::Bibtex(_) => false,
+            });
+
+            if is_builtin || is_user_defined {
+                diagnostics.push(Diagnostic {
+                    source: Some("latex".into()),
+                    range: table[def.definition].range(),
+                    message: format!(
+                        "Command {} is already defined, use \\renewcommand instead",
+                        name
+                    ),
+                    severity: Some(DiagnosticSeverity::Warning),
+                    code: None,
+                    related_information: None,
+                    tags: None,
+                });
+            }
+        }
+        diagnostics
+    }
+}
+
+fn is_new_command(table: &latex::SymbolTable, def: &latex::CommandDefinition) -> bool {
+    let name = table.as_command(def.parent).unwrap().name.text();
+    NEW_COMMANDS.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        diagnostics::helper::analyze_view,
+        protocol::{Range, RangeExt},
+    };
+
+    #[tokio::test]
+    async fn new_command() {
+        let diagnostics = analyze_view(vec![("foo.tex", r#"\newcommand{\foo}{bar}"#)], |view| {
+            CommandRedefinitionDiagnosticsProvider.get(view)
+        })
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn builtin_command() {
+        let diagnostics =
+            analyze_view(vec![("foo.tex", r#"\newcommand{\section}{bar}"#)], |view| {
+                CommandRedefinitionDiagnosticsProvider.get(view)
+            })
+            .await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 12, 0, 20));
+        assert_eq!(
+            diagnostics[0].message,
+            "Command \\section is already defined, use \\renewcommand instead"
+        );
+    }
+
+    #[tokio::test]
+    async fn renew_command() {
+        let diagnostics = analyze_view(
+            vec![("foo.tex", r#"\renewcommand{\section}{bar}"#)],
+            |view| CommandRedefinitionDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn user_command_same_document() {
+        let diagnostics = analyze_view(
+            vec![("foo.tex", r#"\newcommand{\foo}{bar}\newcommand{\foo}{baz}"#)],
+            |view| CommandRedefinitionDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 34, 0, 38));
+    }
+
+    #[tokio::test]
+    async fn starred_command() {
+        let diagnostics = analyze_view(
+            vec![("foo.tex", r#"\newcommand*{\section}{bar}"#)],
+            |view| CommandRedefinitionDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 13, 0, 21));
+    }
+
+    #[tokio::test]
+    async fn user_command_other_document() {
+        let diagnostics = analyze_view(
+            vec![
+                ("foo.tex", r#"\include{bar}\newcommand{\foo}{bar}"#),
+                ("bar.tex", r#"\newcommand{\foo}{baz}"#),
+            ],
+            |view| CommandRedefinitionDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 25, 0, 29));
+    }
+
+    #[tokio::test]
+    async fn user_command_first_definition() {
+        let diagnostics = analyze_view(
+            vec![
+                ("bar.tex", r#"\newcommand{\foo}{baz}"#),
+                ("foo.tex", r#"\include{bar}\newcommand{\foo}{bar}"#),
+            ],
+            |view| CommandRedefinitionDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn renew_command_other_document() {
+        let diagnostics = analyze_view(
+            vec![
+                ("foo.tex", r#"\renewcommand{\foo}{bar}\include{bar}"#),
+                ("bar.tex", r#"\newcommand{\foo}{baz}"#),
+            ],
+            |view| CommandRedefinitionDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+}
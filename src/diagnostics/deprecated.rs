@@ -0,0 +1,138 @@
+use crate::{
+    protocol::{Diagnostic, DiagnosticSeverity, DiagnosticTag, Options},
+    syntax::{SyntaxNode, LANGUAGE_DATA},
+    workspace::{Document, DocumentContent},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct DeprecatedCommandDiagnosticsProvider;
+
+impl DeprecatedCommandDiagnosticsProvider {
+    pub fn get(self, document: &Document, options: &Options) -> Vec<Diagnostic> {
+        let table = match &document.content {
+            DocumentContent::Latex(table) => table,
+            DocumentContent::Bibtex(_) => return Vec::new(),
+        };
+
+        let flagged_commands = options
+            .latex
+            .as_ref()
+            .and_then(|opts| opts.lint.as_ref())
+            .and_then(|opts| opts.deprecated_commands.clone());
+
+        let is_flagged = |name: &str| match &flagged_commands {
+            Some(names) => names.iter().any(|flagged| flagged == name),
+            None => LANGUAGE_DATA
+                .deprecated_commands
+                .iter()
+                .any(|cmd| cmd == name),
+        };
+
+        let mut diagnostics: Vec<_> = table
+            .commands
+            .iter()
+            .filter_map(|node| table.as_command(*node))
+            .filter(|cmd| is_flagged(&cmd.name.text()[1..]))
+            .filter(|cmd| !table.is_inside_definition(cmd.start()))
+            .map(|cmd| {
+                let name = cmd.name.text();
+                let replacement = LANGUAGE_DATA.command_replacement(&name[1..]);
+
+                let message = match replacement {
+                    Some(replacement) => {
+                        format!("{} is deprecated, use {} instead", name, replacement)
+                    }
+                    None => format!("{} is deprecated", name),
+                };
+
+                Diagnostic {
+                    source: Some("latex".into()),
+                    range: cmd.name.range(),
+                    message,
+                    severity: Some(DiagnosticSeverity::Information),
+                    code: None,
+                    related_information: None,
+                    tags: Some(vec![DiagnosticTag::Deprecated]),
+                }
+            })
+            .collect();
+        diagnostics.sort_by_key(|diagnostic| diagnostic.range.start);
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        diagnostics::helper::{analyze_document, test_document},
+        protocol::{LatexLintOptions, LatexOptions, Range, RangeExt},
+    };
+
+    #[test]
+    fn deprecated_command() {
+        let diagnostics = analyze_document(
+            r#"{\bf foo} \textit{bar}"#,
+            &Options::default(),
+            |doc, options| DeprecatedCommandDiagnosticsProvider.get(doc, options),
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 1, 0, 4));
+        assert_eq!(
+            diagnostics[0].message,
+            "\\bf is deprecated, use \\textbf instead"
+        );
+    }
+
+    #[test]
+    fn configured_commands() {
+        let options = Options {
+            latex: Some(LatexOptions {
+                lint: Some(LatexLintOptions {
+                    deprecated_commands: Some(vec!["it".into(), "foo".into()]),
+                    ..LatexLintOptions::default()
+                }),
+                ..LatexOptions::default()
+            }),
+            ..Options::default()
+        };
+
+        let diagnostics =
+            analyze_document(r#"{\bf foo} {\it bar} \foo"#, &options, |doc, options| {
+                DeprecatedCommandDiagnosticsProvider.get(doc, options)
+            });
+        let actual_messages: Vec<_> = diagnostics
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect();
+        assert_eq!(
+            actual_messages,
+            vec![
+                "\\it is deprecated, use \\textit instead",
+                "\\foo is deprecated"
+            ]
+        );
+    }
+
+    #[test]
+    fn definition_body() {
+        let diagnostics = analyze_document(
+            r#"\newcommand{\foo}{\bf} \newenvironment{bar}{\it}{} {\sc baz}"#,
+            &Options::default(),
+            |doc, options| DeprecatedCommandDiagnosticsProvider.get(doc, options),
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 52, 0, 55));
+    }
+
+    #[test]
+    fn bibtex_document() {
+        let document = test_document(
+            "main.bib",
+            r#"@article{foo, title = {\bf bar}}"#,
+            &Options::default(),
+        );
+        let diagnostics = DeprecatedCommandDiagnosticsProvider.get(&document, &Options::default());
+        assert!(diagnostics.is_empty());
+    }
+}
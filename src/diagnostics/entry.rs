@@ -0,0 +1,145 @@
+use crate::{
+    feature::DocumentView,
+    protocol::{Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, Range},
+    syntax::SyntaxNode,
+    workspace::{Document, DocumentContent},
+};
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct DuplicateEntryDiagnosticsProvider;
+
+impl DuplicateEntryDiagnosticsProvider {
+    pub fn get(self, view: &DocumentView) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let tree = match &view.current.content {
+            DocumentContent::Bibtex(tree) => tree,
+            DocumentContent::Latex(_) => return diagnostics,
+        };
+
+        let mut keys: HashMap<&str, Vec<(&Document, Range)>> = HashMap::new();
+        for doc in &view.related {
+            if let DocumentContent::Bibtex(tree) = &doc.content {
+                for entry in tree
+                    .children(tree.root)
+                    .filter_map(|node| tree.as_entry(node))
+                    .filter(|entry| !entry.is_comment())
+                {
+                    if let Some(key) = &entry.key {
+                        keys.entry(key.text()).or_default().push((doc, key.range()));
+                    }
+                }
+            }
+        }
+
+        for entry in tree
+            .children(tree.root)
+            .filter_map(|node| tree.as_entry(node))
+            .filter(|entry| !entry.is_comment())
+        {
+            let key = match &entry.key {
+                Some(key) => key,
+                None => continue,
+            };
+
+            let related_information: Vec<_> = keys[key.text()]
+                .iter()
+                .filter(|(doc, range)| doc.uri != view.current.uri || *range != key.range())
+                .map(|(doc, range)| DiagnosticRelatedInformation {
+                    location: Location::new(doc.uri.clone().into(), *range),
+                    message: "Also defined here".into(),
+                })
+                .collect();
+
+            if related_information.is_empty() {
+                continue;
+            }
+
+            diagnostics.push(Diagnostic {
+                source: Some("bibtex".into()),
+                range: key.range(),
+                message: format!("Duplicate entry key: {}", key.text()),
+                severity: Some(DiagnosticSeverity::Warning),
+                code: None,
+                related_information: Some(related_information),
+                tags: None,
+            });
+        }
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{diagnostics::helper::analyze_view, feature::FeatureTester, protocol::RangeExt};
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn unique_keys() {
+        let diagnostics = analyze_view(vec![("main.bib", "@article{foo,}\n@book{bar,}")], |view| {
+            DuplicateEntryDiagnosticsProvider.get(view)
+        })
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn duplicate_in_same_document() {
+        let diagnostics = analyze_view(
+            vec![(
+                "main.bib",
+                indoc!(
+                    r#"
+                    @article{foo,}
+                    @book{foo,}
+                "#
+                ),
+            )],
+            |view| DuplicateEntryDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 9, 0, 12));
+        assert_eq!(diagnostics[1].range, Range::new_simple(1, 6, 1, 9));
+        assert_eq!(diagnostics[0].message, "Duplicate entry key: foo");
+        assert_eq!(
+            diagnostics[0].related_information.as_ref().unwrap()[0].location,
+            Location::new(
+                FeatureTester::uri("main.bib").into(),
+                Range::new_simple(1, 6, 1, 9)
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn duplicate_in_other_document() {
+        let diagnostics = analyze_view(
+            vec![
+                ("foo.bib", "@article{baz,}"),
+                ("main.tex", r#"\bibliography{foo,bar}"#),
+                ("bar.bib", "@book{baz,}"),
+            ],
+            |view| DuplicateEntryDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].related_information.as_ref().unwrap()[0].location,
+            Location::new(
+                FeatureTester::uri("bar.bib").into(),
+                Range::new_simple(0, 6, 0, 9)
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn comment_entry() {
+        let diagnostics = analyze_view(
+            vec![("main.bib", "@comment{foo,}\n@article{foo,}")],
+            |view| DuplicateEntryDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{
+    feature::DocumentView,
+    protocol::{Diagnostic, DiagnosticSeverity, Uri},
+    syntax::{latex, AstNodeIndex, LatexIncludeKind, SyntaxNode},
+    workspace::{Document, DocumentContent},
+};
+use chashmap::CHashMap;
+
+/// Unclosed environments are only checked when a document is opened or saved
+/// because they are expected while the user is still typing.
+#[derive(Debug, Default)]
+pub struct EnvironmentDiagnosticsProvider {
+    diagnostics_by_uri: CHashMap<Uri, Vec<Diagnostic>>,
+}
+
+impl EnvironmentDiagnosticsProvider {
+    pub fn get(&self, doc: &Document) -> Vec<Diagnostic> {
+        match self.diagnostics_by_uri.get(&doc.uri) {
+            Some(diagnostics) => diagnostics.to_owned(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn update(&self, view: &DocumentView) {
+        self.diagnostics_by_uri
+            .insert(view.current.uri.clone(), analyze(view));
+    }
+}
+
+fn analyze(view: &DocumentView) -> Vec<Diagnostic> {
+    let table = match &view.current.content {
+        DocumentContent::Latex(table) => table,
+        DocumentContent::Bibtex(_) => return Vec::new(),
+    };
+
+    let mut diagnostics = Vec::new();
+    for node in unmatched_delimiters(table, "\\begin") {
+        if table.is_inside_definition(table[node].start()) {
+            continue;
+        }
+
+        let name = table
+            .extract_word(node, latex::GroupKind::Group, 0)
+            .unwrap()
+            .text();
+
+        let is_closed_in_include = table
+            .includes
+            .iter()
+            .filter(|include| include.kind == LatexIncludeKind::Latex)
+            .filter(|include| table[include.parent].start() >= table[node].end())
+            .flat_map(|include| include.all_targets.iter())
+            .filter_map(|targets| view.snapshot.find_target(targets))
+            .any(|doc| match &doc.content {
+                DocumentContent::Latex(child_table) => unmatched_delimiters(child_table, "\\end")
+                    .into_iter()
+                    .filter_map(|end| child_table.extract_word(end, latex::GroupKind::Group, 0))
+                    .any(|end_name| end_name.text() == name),
+                DocumentContent::Bibtex(_) => false,
+            });
+
+        let message = if is_closed_in_include {
+            format!("Environment {} is closed in an included file", name)
+        } else {
+            format!("Environment {} is not closed", name)
+        };
+
+        diagnostics.push(Diagnostic {
+            source: Some("latex".into()),
+            range: table[node].range(),
+            message,
+            severity: Some(DiagnosticSeverity::Warning),
+            code: None,
+            related_information: None,
+            tags: None,
+        });
+    }
+    diagnostics
+}
+
+fn unmatched_delimiters(table: &latex::SymbolTable, delimiter: &str) -> Vec<AstNodeIndex> {
+    table
+        .commands
+        .iter()
+        .copied()
+        .filter(|node| table.as_command(*node).unwrap().name.text() == delimiter)
+        .filter(|node| {
+            table
+                .extract_word(*node, latex::GroupKind::Group, 0)
+                .is_some()
+        })
+        .filter(|node| {
+            !table
+                .environments
+                .iter()
+                .any(|env| env.left.parent == *node || env.right.parent == *node)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        diagnostics::helper::analyze_view,
+        feature::FeatureTester,
+        protocol::{Range, RangeExt},
+    };
+
+    fn update_and_get(view: &DocumentView) -> Vec<Diagnostic> {
+        let provider = EnvironmentDiagnosticsProvider::default();
+        provider.update(view);
+        provider.get(&view.current)
+    }
+
+    #[tokio::test]
+    async fn balanced_environment() {
+        let diagnostics = analyze_view(
+            vec![("foo.tex", r#"\begin{foo}\input{bar}\end{foo}"#)],
+            update_and_get,
+        )
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn closed_in_included_file() {
+        let diagnostics = analyze_view(
+            vec![
+                ("foo.tex", r#"\begin{foo}\input{bar}"#),
+                ("bar.tex", r#"\end{foo}"#),
+            ],
+            update_and_get,
+        )
+        .await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 0, 0, 11));
+        assert_eq!(
+            diagnostics[0].message,
+            "Environment foo is closed in an included file"
+        );
+    }
+
+    #[tokio::test]
+    async fn not_closed() {
+        let diagnostics = analyze_view(
+            vec![("foo.tex", r#"\begin{foo}\begin{bar}\end{bar}"#)],
+            update_and_get,
+        )
+        .await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "Environment foo is not closed");
+    }
+
+    #[tokio::test]
+    async fn definition_body() {
+        let diagnostics = analyze_view(
+            vec![(
+                "foo.tex",
+                r#"\newenvironment{foo}{\begin{bar}}{\end{bar}}\newcommand{\baz}{\begin{qux}}"#,
+            )],
+            update_and_get,
+        )
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn not_updated() {
+        let view = FeatureTester::from_files(vec![("foo.tex", r#"\begin{foo}"#)])
+            .test_view()
+            .await;
+        let provider = EnvironmentDiagnosticsProvider::default();
+        assert!(provider.get(&view.current).is_empty());
+    }
+
+    #[tokio::test]
+    async fn unmatched_end() {
+        let diagnostics = analyze_view(
+            vec![
+                ("bar.tex", r#"\end{foo}"#),
+                ("foo.tex", r#"\begin{foo}\input{bar}"#),
+            ],
+            update_and_get,
+        )
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{
+    feature::DocumentView,
+    protocol::{Diagnostic, DiagnosticSeverity, Uri},
+    syntax::{LatexIncludeKind, SyntaxNode},
+    workspace::DocumentContent,
+};
+use chashmap::CHashMap;
+use std::{
+    path::{Path, PathBuf},
+    str,
+    time::SystemTime,
+};
+use tokio::fs;
+
+/// The encoding check reads the whole file, so its result is kept
+/// until the file is modified.
+#[derive(Debug, Default)]
+pub struct IncludeDiagnosticsProvider {
+    encodings_by_path: CHashMap<PathBuf, (Option<SystemTime>, bool)>,
+}
+
+impl IncludeDiagnosticsProvider {
+    pub async fn get(&self, view: &DocumentView) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if !view.current.is_file() {
+            return diagnostics;
+        }
+
+        if let DocumentContent::Latex(table) = &view.current.content {
+            for include in &table.includes {
+                match include.kind {
+                    LatexIncludeKind::Latex | LatexIncludeKind::Bibliography => (),
+                    LatexIncludeKind::Package
+                    | LatexIncludeKind::Class
+                    | LatexIncludeKind::Image
+                    | LatexIncludeKind::Svg
+                    | LatexIncludeKind::Pdf
+                    | LatexIncludeKind::Everything => continue,
+                }
+
+                let paths = include.paths(&table);
+                for (path, targets) in paths.iter().zip(&include.all_targets) {
+                    if view.snapshot.find_target(targets).is_some() {
+                        continue;
+                    }
+
+                    let message = match find_file(targets).await {
+                        Some((file, modified)) if self.check_encoding(&file, modified).await => {
+                            continue
+                        }
+                        Some(_) => format!("File is not encoded in UTF-8: {}", path.text()),
+                        None => format!("Unable to find file: {}", path.text()),
+                    };
+
+                    diagnostics.push(Diagnostic {
+                        source: Some("latex".into()),
+                        range: path.range(),
+                        message,
+                        severity: Some(DiagnosticSeverity::Warning),
+                        code: None,
+                        related_information: None,
+                        tags: None,
+                    });
+                }
+            }
+        }
+        diagnostics
+    }
+
+    async fn check_encoding(&self, file: &Path, modified: Option<SystemTime>) -> bool {
+        let cached = self.encodings_by_path.get(file).map(|entry| *entry);
+        match cached {
+            Some((cached_modified, valid)) if modified.is_some() && cached_modified == modified => {
+                valid
+            }
+            _ => {
+                let valid = is_utf8(file).await;
+                self.encodings_by_path
+                    .insert(file.to_owned(), (modified, valid));
+                valid
+            }
+        }
+    }
+}
+
+async fn find_file(targets: &[Uri]) -> Option<(PathBuf, Option<SystemTime>)> {
+    for path in targets
+        .iter()
+        .filter_map(|target| target.to_file_path().ok())
+    {
+        match fs::metadata(&path).await {
+            Ok(metadata) if metadata.is_file() => {
+                return Some((path, metadata.modified().ok()));
+            }
+            _ => (),
+        }
+    }
+    None
+}
+
+async fn is_utf8(file: &Path) -> bool {
+    match fs::read(file).await {
+        Ok(bytes) => str::from_utf8(&bytes).is_ok(),
+        Err(_) => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        feature::FeatureTester,
+        protocol::{Range, RangeExt},
+    };
+    use tempfile::tempdir;
+
+    async fn analyze(files: Vec<(&str, &str)>) -> Vec<Diagnostic> {
+        let view = FeatureTester::from_files(files).test_view().await;
+        IncludeDiagnosticsProvider::default().get(&view).await
+    }
+
+    #[tokio::test]
+    async fn existing_file() {
+        let diagnostics = analyze(vec![
+            ("include_foo.tex", r#"\input{include_bar}"#),
+            ("include_bar.tex", ""),
+        ])
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn missing_file() {
+        let diagnostics = analyze(vec![(
+            "include_foo.tex",
+            r#"\input{include_missing}\usepackage{amsmath}"#,
+        )])
+        .await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 7, 0, 22));
+    }
+
+    #[tokio::test]
+    async fn invalid_encoding() {
+        let dir = tempdir().unwrap();
+        let name = dir.path().file_name().unwrap().to_str().unwrap();
+        fs::write(dir.path().join("latin1.tex"), b"caf\xe9")
+            .await
+            .unwrap();
+        let text = format!("\\input{{{}/latin1}}", name);
+        let diagnostics = analyze(vec![("include_foo.tex", text.as_str())]).await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            format!("File is not encoded in UTF-8: {}/latin1", name)
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{
+    protocol::{Diagnostic, DiagnosticSeverity, Options, RangeExt},
+    syntax::{SyntaxNode, LANGUAGE_DATA},
+    workspace::{Document, DocumentContent},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct ItemDiagnosticsProvider;
+
+impl ItemDiagnosticsProvider {
+    pub fn get(self, document: &Document, options: &Options) -> Vec<Diagnostic> {
+        let table = match &document.content {
+            DocumentContent::Latex(table) => table,
+            DocumentContent::Bibtex(_) => return Vec::new(),
+        };
+
+        let configured_environments = options
+            .latex
+            .as_ref()
+            .and_then(|opts| opts.lint.as_ref())
+            .and_then(|opts| opts.list_environments.as_ref());
+
+        let is_list = |name: &str| {
+            LANGUAGE_DATA
+                .enum_environments
+                .iter()
+                .chain(&LANGUAGE_DATA.list_environments)
+                .chain(configured_environments.into_iter().flatten())
+                .any(|env| env == name)
+        };
+
+        table
+            .items
+            .iter()
+            .filter_map(|item| table.as_command(item.parent))
+            .filter(|item| !table.is_inside_definition(item.start()))
+            .filter(|item| {
+                !table
+                    .environments
+                    .iter()
+                    .filter(|env| {
+                        env.left
+                            .name(&table)
+                            .filter(|name| is_list(name.text()))
+                            .is_some()
+                    })
+                    .any(|env| env.range(&table).contains(item.start()))
+            })
+            .map(|item| Diagnostic {
+                source: Some("latex".into()),
+                range: item.name.range(),
+                message: "\\item used outside of a list environment".into(),
+                severity: Some(DiagnosticSeverity::Error),
+                code: None,
+                related_information: None,
+                tags: None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        diagnostics::helper::{analyze_document, test_document},
+        protocol::{LatexLintOptions, LatexOptions, Range},
+    };
+    use indoc::indoc;
+
+    #[test]
+    fn inside_of_list() {
+        let diagnostics = analyze_document(
+            indoc!(
+                r#"
+                    \begin{itemize}
+                        \item foo
+                        \begin{enumerate}
+                            \item bar
+                        \end{enumerate}
+                    \end{itemize}
+                "#
+            ),
+            &Options::default(),
+            |doc, options| ItemDiagnosticsProvider.get(doc, options),
+        );
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn outside_of_list() {
+        let diagnostics = analyze_document(
+            indoc!(
+                r#"
+                    \begin{figure}
+                        \item foo
+                    \end{figure}
+                "#
+            ),
+            &Options::default(),
+            |doc, options| ItemDiagnosticsProvider.get(doc, options),
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(1, 4, 1, 9));
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::Error));
+    }
+
+    #[test]
+    fn command_definition() {
+        let diagnostics = analyze_document(
+            r#"\newcommand{\foo}{\item}"#,
+            &Options::default(),
+            |doc, options| ItemDiagnosticsProvider.get(doc, options),
+        );
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn environment_definition() {
+        let diagnostics = analyze_document(
+            r#"\newenvironment{foo}{\item}{\item}"#,
+            &Options::default(),
+            |doc, options| ItemDiagnosticsProvider.get(doc, options),
+        );
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn configured_environments() {
+        let options = Options {
+            latex: Some(LatexOptions {
+                lint: Some(LatexLintOptions {
+                    list_environments: Some(vec!["foo".into()]),
+                    ..LatexLintOptions::default()
+                }),
+                ..LatexOptions::default()
+            }),
+            ..Options::default()
+        };
+
+        let diagnostics = analyze_document(
+            r#"\begin{foo}\item bar\end{foo}"#,
+            &options,
+            |doc, options| ItemDiagnosticsProvider.get(doc, options),
+        );
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn bibtex_document() {
+        let document = test_document(
+            "main.bib",
+            r#"@article{foo, title = {\item bar}}"#,
+            &Options::default(),
+        );
+        let diagnostics = ItemDiagnosticsProvider.get(&document, &Options::default());
+        assert!(diagnostics.is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{
+    feature::DocumentView,
+    protocol::{Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, Range},
+    syntax::SyntaxNode,
+    workspace::{Document, DocumentContent},
+};
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct DuplicateLabelDiagnosticsProvider;
+
+impl DuplicateLabelDiagnosticsProvider {
+    pub fn get(self, view: &DocumentView) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let table = match &view.current.content {
+            DocumentContent::Latex(table) => table,
+            DocumentContent::Bibtex(_) => return diagnostics,
+        };
+
+        let order = view.include_order();
+        // zref labels live in their own namespace.
+        let mut originals: HashMap<(bool, &str), (_, &Document, Range)> = HashMap::new();
+        for doc in &view.related {
+            if let DocumentContent::Latex(table) = &doc.content {
+                for label in table.label_definitions() {
+                    let zref = label.is_zref(&table);
+                    for name in label.names(&table) {
+                        let key = order.key(&doc.uri, name.start());
+                        match originals.get(&(zref, name.text())) {
+                            Some((original_key, _, _)) if *original_key <= key => (),
+                            _ => {
+                                originals.insert((zref, name.text()), (key, doc, name.range()));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        for label in table.label_definitions() {
+            let zref = label.is_zref(&table);
+            for name in label.names(&table) {
+                let (doc, range) = match originals.get(&(zref, name.text())) {
+                    Some((_, doc, range))
+                        if doc.uri != view.current.uri || *range != name.range() =>
+                    {
+                        (doc, *range)
+                    }
+                    _ => continue,
+                };
+
+                diagnostics.push(Diagnostic {
+                    source: Some("latex".into()),
+                    range: name.range(),
+                    message: format!("Duplicate label: {}", name.text()),
+                    severity: Some(DiagnosticSeverity::Warning),
+                    code: None,
+                    related_information: Some(vec![DiagnosticRelatedInformation {
+                        location: Location::new(doc.uri.clone().into(), range),
+                        message: "First defined here".into(),
+                    }]),
+                    tags: None,
+                });
+            }
+        }
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{diagnostics::helper::analyze_view, feature::FeatureTester, protocol::RangeExt};
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn unique_labels() {
+        let diagnostics = analyze_view(
+            vec![("foo.tex", r#"\label{foo}\label{bar}\ref{foo}"#)],
+            |view| DuplicateLabelDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn duplicate_in_same_document() {
+        let diagnostics = analyze_view(
+            vec![(
+                "foo.tex",
+                indoc!(
+                    r#"
+                    \label{foo}
+                    \label{foo}
+                "#
+                ),
+            )],
+            |view| DuplicateLabelDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(1, 7, 1, 10));
+        assert_eq!(diagnostics[0].message, "Duplicate label: foo");
+    }
+
+    #[tokio::test]
+    async fn zref_label() {
+        let diagnostics = analyze_view(vec![("foo.tex", r#"\label{foo}\zlabel{foo}"#)], |view| {
+            DuplicateLabelDiagnosticsProvider.get(view)
+        })
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn duplicate_in_other_document() {
+        let diagnostics = analyze_view(
+            vec![
+                ("foo.tex", r#"\include{bar}\label{baz}"#),
+                ("bar.tex", r#"\label{baz}"#),
+            ],
+            |view| DuplicateLabelDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 20, 0, 23));
+        assert_eq!(
+            diagnostics[0].related_information.as_ref().unwrap()[0].location,
+            Location::new(
+                FeatureTester::uri("bar.tex").into(),
+                Range::new_simple(0, 7, 0, 10)
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn duplicate_in_later_document() {
+        let diagnostics = analyze_view(
+            vec![
+                ("foo.tex", r#"\label{baz}\include{bar}"#),
+                ("bar.tex", r#"\label{baz}"#),
+            ],
+            |view| DuplicateLabelDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn unrelated_document() {
+        let diagnostics = analyze_view(
+            vec![("foo.tex", r#"\label{baz}"#), ("bar.tex", r#"\label{baz}"#)],
+            |view| DuplicateLabelDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+}
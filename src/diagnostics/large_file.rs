@@ -0,0 +1,110 @@
+use crate::{
+    protocol::{Diagnostic, DiagnosticSeverity, Options, Range, RangeExt, Uri},
+    workspace::Document,
+};
+use futures::lock::Mutex;
+use std::collections::HashSet;
+
+/// The size limit is only reported once per document
+/// until the document fits into the limit again.
+#[derive(Debug, Default)]
+pub struct LargeFileDiagnosticsProvider {
+    notified_uris: Mutex<HashSet<Uri>>,
+}
+
+impl LargeFileDiagnosticsProvider {
+    pub async fn get(&self, document: &Document, options: &Options) -> Vec<Diagnostic> {
+        let mut notified_uris = self.notified_uris.lock().await;
+        if !document.is_large(options) {
+            notified_uris.remove(&document.uri);
+            return Vec::new();
+        }
+
+        if !notified_uris.insert(document.uri.clone()) {
+            return Vec::new();
+        }
+
+        vec![Diagnostic {
+            source: Some("latex".into()),
+            range: Range::new_simple(0, 0, 0, 0),
+            message: "This file exceeds the configured size limit. \
+                      Completion and document symbols are disabled for it."
+                .into(),
+            severity: Some(DiagnosticSeverity::Information),
+            code: None,
+            related_information: None,
+            tags: None,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{diagnostics::helper::test_document, protocol::LatexOptions};
+
+    fn limited_options(max_file_lines: Option<usize>, max_file_size: Option<usize>) -> Options {
+        Options {
+            latex: Some(LatexOptions {
+                max_file_lines,
+                max_file_size,
+                ..LatexOptions::default()
+            }),
+            ..Options::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn small_file() {
+        let options = Options::default();
+        let document = test_document("main.tex", "foo\nbar", &options);
+        let diagnostics = LargeFileDiagnosticsProvider::default()
+            .get(&document, &options)
+            .await;
+        assert!(diagnostics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn too_many_lines() {
+        let options = limited_options(Some(1), None);
+        let document = test_document("main.tex", "foo\nbar", &options);
+        let diagnostics = LargeFileDiagnosticsProvider::default()
+            .get(&document, &options)
+            .await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].severity,
+            Some(DiagnosticSeverity::Information)
+        );
+    }
+
+    #[tokio::test]
+    async fn too_many_bytes() {
+        let options = limited_options(None, Some(4));
+        let document = test_document("main.tex", "foo bar", &options);
+        let diagnostics = LargeFileDiagnosticsProvider::default()
+            .get(&document, &options)
+            .await;
+        assert_eq!(diagnostics.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn notified_once() {
+        let options = limited_options(Some(1), None);
+        let document = test_document("main.tex", "foo\nbar", &options);
+        let provider = LargeFileDiagnosticsProvider::default();
+        assert_eq!(provider.get(&document, &options).await.len(), 1);
+        assert!(provider.get(&document, &options).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn notified_again_after_shrinking() {
+        let options = limited_options(Some(1), None);
+        let large_document = test_document("main.tex", "foo\nbar", &options);
+        let small_document = test_document("main.tex", "foo", &options);
+        let provider = LargeFileDiagnosticsProvider::default();
+        assert_eq!(provider.get(&large_document, &options).await.len(), 1);
+        assert!(provider.get(&small_document, &options).await.is_empty());
+        assert_eq!(provider.get(&large_document, &options).await.len(), 1);
+    }
+}
@@ -0,0 +1,244 @@
+use crate::{
+    protocol::{Diagnostic, DiagnosticSeverity, Range, RangeExt},
+    syntax::{latex, AstNodeIndex, SyntaxNode},
+    workspace::{Document, DocumentContent},
+};
+
+const VERBATIM_ENVIRONMENTS: &[&str] = &[
+    "verbatim",
+    "verbatim*",
+    "Verbatim",
+    "lstlisting",
+    "minted",
+    "comment",
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MathErrorCode {
+    Unterminated,
+    Nested,
+}
+
+impl MathErrorCode {
+    pub fn message(self) -> &'static str {
+        match self {
+            MathErrorCode::Unterminated => "Unterminated math mode",
+            MathErrorCode::Nested => "Math mode is already active",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct MathError {
+    code: MathErrorCode,
+    range: Range,
+}
+
+impl MathError {
+    pub fn new(code: MathErrorCode, range: Range) -> Self {
+        Self { code, range }
+    }
+
+    pub fn analyze(table: &latex::SymbolTable) -> Vec<Self> {
+        let verbatim_ranges: Vec<_> = table
+            .environments
+            .iter()
+            .filter(|env| {
+                env.left
+                    .name(&table)
+                    .filter(|name| VERBATIM_ENVIRONMENTS.contains(&name.text()))
+                    .is_some()
+            })
+            .map(|env| env.range(&table))
+            .collect();
+
+        let mut errors = Vec::new();
+        Self::analyze_group(&mut errors, table, &verbatim_ranges, table.root);
+        errors.sort_by_key(|error| error.range.start);
+        errors
+    }
+
+    fn analyze_group(
+        errors: &mut Vec<Self>,
+        tree: &latex::Tree,
+        verbatim_ranges: &[Range],
+        parent: AstNodeIndex,
+    ) {
+        let mut delimiters: Vec<(&str, Range)> = Vec::new();
+        for child in tree.children(parent) {
+            if verbatim_ranges
+                .iter()
+                .any(|range| range.contains_exclusive(tree[child].start()))
+            {
+                continue;
+            }
+
+            let token = match &tree[child] {
+                latex::Node::Math(math) => &math.token,
+                latex::Node::Command(cmd) => {
+                    Self::analyze_group(errors, tree, verbatim_ranges, child);
+                    &cmd.name
+                }
+                latex::Node::Group(_) => {
+                    Self::analyze_group(errors, tree, verbatim_ranges, child);
+                    continue;
+                }
+                latex::Node::Root(_) | latex::Node::Text(_) | latex::Node::Comma(_) => continue,
+            };
+
+            let name = token.text();
+            match name {
+                "$" | "$$" if delimiters.last().map(|(opener, _)| *opener) == Some(name) => {
+                    delimiters.pop();
+                }
+                "\\)" | "\\]" => {
+                    if let Some(index) = delimiters
+                        .iter()
+                        .rposition(|(opener, _)| closer(opener) == name)
+                    {
+                        for (_, range) in delimiters.drain(index..).skip(1) {
+                            errors.push(Self::new(MathErrorCode::Unterminated, range));
+                        }
+                    }
+                }
+                "$" | "$$" | "\\(" | "\\[" => {
+                    if !delimiters.is_empty() {
+                        errors.push(Self::new(MathErrorCode::Nested, token.range()));
+                    }
+                    delimiters.push((name, token.range()));
+                }
+                _ => {}
+            }
+        }
+
+        for (_, range) in delimiters {
+            errors.push(Self::new(MathErrorCode::Unterminated, range));
+        }
+    }
+}
+
+fn closer(opener: &str) -> &'static str {
+    match opener {
+        "$" => "$",
+        "$$" => "$$",
+        "\\(" => "\\)",
+        "\\[" => "\\]",
+        _ => "",
+    }
+}
+
+impl Into<Diagnostic> for MathError {
+    fn into(self) -> Diagnostic {
+        let severity = match self.code {
+            MathErrorCode::Unterminated => DiagnosticSeverity::Error,
+            MathErrorCode::Nested => DiagnosticSeverity::Warning,
+        };
+
+        Diagnostic {
+            source: Some("latex".into()),
+            range: self.range,
+            message: self.code.message().into(),
+            severity: Some(severity),
+            code: None,
+            related_information: None,
+            tags: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct MathDiagnosticsProvider;
+
+impl MathDiagnosticsProvider {
+    pub fn get(self, doc: &Document) -> Vec<Diagnostic> {
+        if let DocumentContent::Latex(table) = &doc.content {
+            MathError::analyze(&table)
+                .into_iter()
+                .map(Into::into)
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        protocol::{Options, RangeExt, Uri},
+        tex::Resolver,
+    };
+    use std::env;
+
+    fn analyze(text: &str) -> Vec<MathError> {
+        let table = latex::open(latex::OpenParams {
+            text,
+            uri: &Uri::parse("http://www.foo.com/bar.tex").unwrap(),
+            resolver: &Resolver::default(),
+            options: &Options::default(),
+            current_dir: &env::current_dir().unwrap(),
+        });
+        MathError::analyze(&table)
+    }
+
+    #[test]
+    fn valid() {
+        let errors = analyze("$x$ \\(y\\) \\[z\\] $$w$$ \\[\\text{$a$}\\]");
+        assert_eq!(errors, Vec::new());
+    }
+
+    #[test]
+    fn unterminated_inline() {
+        let errors = analyze("foo $x");
+        assert_eq!(
+            errors,
+            vec![MathError::new(
+                MathErrorCode::Unterminated,
+                Range::new_simple(0, 4, 0, 5),
+            )]
+        );
+    }
+
+    #[test]
+    fn unterminated_display() {
+        let errors = analyze("\\[x\n\\(y\\)");
+        assert_eq!(
+            errors,
+            vec![
+                MathError::new(MathErrorCode::Unterminated, Range::new_simple(0, 0, 0, 2)),
+                MathError::new(MathErrorCode::Nested, Range::new_simple(1, 0, 1, 2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn unterminated_inside_display() {
+        let errors = analyze("\\[ $x \\]");
+        assert_eq!(
+            errors,
+            vec![
+                MathError::new(MathErrorCode::Nested, Range::new_simple(0, 3, 0, 4)),
+                MathError::new(MathErrorCode::Unterminated, Range::new_simple(0, 3, 0, 4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn verbatim() {
+        let errors = analyze("\\begin{verbatim}\n$x \\(y\n\\end{verbatim}");
+        assert_eq!(errors, Vec::new());
+    }
+
+    #[test]
+    fn nested_inline() {
+        let errors = analyze("\\(x $y$\\)");
+        assert_eq!(
+            errors,
+            vec![MathError::new(
+                MathErrorCode::Nested,
+                Range::new_simple(0, 5, 0, 6),
+            )]
+        );
+    }
+}
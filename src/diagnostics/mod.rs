@@ -1,28 +1,162 @@
 mod bibtex;
 mod build;
+mod citation;
+mod command_definition;
+mod deprecated;
+mod entry;
+mod environment;
+mod include;
+mod item;
+mod label;
+mod large_file;
 mod latex;
+mod math;
+mod package;
+mod renamed_label;
+mod unused_label;
 
 pub use self::{
     bibtex::{BibtexDiagnosticsProvider, BibtexError, BibtexErrorCode},
-    build::BuildDiagnosticsProvider,
+    build::{parse_build_diagnostics, BuildDiagnosticsProvider},
+    citation::CitationDiagnosticsProvider,
+    command_definition::CommandRedefinitionDiagnosticsProvider,
+    deprecated::DeprecatedCommandDiagnosticsProvider,
+    entry::DuplicateEntryDiagnosticsProvider,
+    environment::EnvironmentDiagnosticsProvider,
+    include::IncludeDiagnosticsProvider,
+    item::ItemDiagnosticsProvider,
+    label::DuplicateLabelDiagnosticsProvider,
+    large_file::LargeFileDiagnosticsProvider,
     latex::LatexDiagnosticsProvider,
+    math::{MathDiagnosticsProvider, MathError, MathErrorCode},
+    package::DuplicatePackageDiagnosticsProvider,
+    renamed_label::RenamedLabelDiagnosticsProvider,
+    unused_label::UnusedLabelDiagnosticsProvider,
 };
 
-use crate::{protocol::Diagnostic, workspace::Document};
+use crate::{
+    feature::DocumentView,
+    protocol::{Diagnostic, DocumentDiagnosticReport, FullDocumentDiagnosticReport, Options},
+};
 
 #[derive(Debug, Default)]
 pub struct DiagnosticsManager {
     pub bibtex: BibtexDiagnosticsProvider,
     pub latex: LatexDiagnosticsProvider,
+    pub include: IncludeDiagnosticsProvider,
+    pub citation: CitationDiagnosticsProvider,
+    pub label: DuplicateLabelDiagnosticsProvider,
+    pub package: DuplicatePackageDiagnosticsProvider,
+    pub unused_label: UnusedLabelDiagnosticsProvider,
+    pub command_definition: CommandRedefinitionDiagnosticsProvider,
+    pub deprecated: DeprecatedCommandDiagnosticsProvider,
+    pub entry: DuplicateEntryDiagnosticsProvider,
+    pub environment: EnvironmentDiagnosticsProvider,
+    pub item: ItemDiagnosticsProvider,
+    pub large_file: LargeFileDiagnosticsProvider,
+    pub math: MathDiagnosticsProvider,
+    pub renamed_label: RenamedLabelDiagnosticsProvider,
     pub build: BuildDiagnosticsProvider,
 }
 
 impl DiagnosticsManager {
-    pub async fn get(&self, doc: &Document) -> Vec<Diagnostic> {
+    pub async fn get(&self, view: &DocumentView, options: &Options) -> Vec<Diagnostic> {
+        let doc = &view.current;
         let mut diagnostics = Vec::new();
         diagnostics.append(&mut self.bibtex.get(doc));
         diagnostics.append(&mut self.latex.get(doc));
+        diagnostics.append(&mut self.include.get(view).await);
+        diagnostics.append(&mut self.citation.get(view));
+        diagnostics.append(&mut self.label.get(view));
+        diagnostics.append(&mut self.package.get(view));
+        diagnostics.append(&mut self.unused_label.get(view));
+        diagnostics.append(&mut self.command_definition.get(view));
+        diagnostics.append(&mut self.deprecated.get(doc, options));
+        diagnostics.append(&mut self.entry.get(view));
+        diagnostics.append(&mut self.environment.get(doc));
+        diagnostics.append(&mut self.item.get(doc, options));
+        diagnostics.append(&mut self.large_file.get(doc, options).await);
+        diagnostics.append(&mut self.math.get(doc));
+        diagnostics.append(&mut self.renamed_label.get(doc).await);
         diagnostics.append(&mut self.build.get(doc).await);
         diagnostics
     }
+
+    pub async fn report(&self, view: &DocumentView, options: &Options) -> DocumentDiagnosticReport {
+        let items = self.get(view, options).await;
+        DocumentDiagnosticReport::Full(FullDocumentDiagnosticReport { items })
+    }
+}
+
+#[cfg(test)]
+mod helper {
+    use crate::{
+        feature::{DocumentView, FeatureTester},
+        protocol::{Diagnostic, Options, Uri},
+        tex::{Language, Resolver},
+        workspace::{Document, DocumentParams},
+    };
+    use std::env;
+
+    pub fn test_uri(name: &str) -> Uri {
+        Uri::parse(&format!("http://www.example.com/{}", name)).unwrap()
+    }
+
+    pub fn test_document(name: &str, text: &str, options: &Options) -> Document {
+        let language = if name.ends_with(".bib") {
+            Language::Bibtex
+        } else {
+            Language::Latex
+        };
+
+        Document::open(DocumentParams {
+            uri: test_uri(name),
+            text: text.into(),
+            language,
+            resolver: &Resolver::default(),
+            options,
+            current_dir: &env::current_dir().unwrap(),
+        })
+    }
+
+    pub async fn analyze_view<F>(files: Vec<(&str, &str)>, get: F) -> Vec<Diagnostic>
+    where
+        F: FnOnce(&DocumentView) -> Vec<Diagnostic>,
+    {
+        let view = FeatureTester::from_files(files).test_view().await;
+        get(&view)
+    }
+
+    pub fn analyze_document<F>(text: &str, options: &Options, get: F) -> Vec<Diagnostic>
+    where
+        F: FnOnce(&Document, &Options) -> Vec<Diagnostic>,
+    {
+        let document = test_document("main.tex", text, options);
+        get(&document, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{helper::test_document, *};
+    use crate::workspace::Snapshot;
+    use std::{env, sync::Arc};
+
+    fn create_view(text: &str) -> DocumentView {
+        let current_dir = env::current_dir().unwrap();
+        let doc = Arc::new(test_document("main.tex", text, &Options::default()));
+        let snapshot = Arc::new(Snapshot(vec![Arc::clone(&doc)]));
+        DocumentView::analyze(snapshot, doc, &Options::default(), &current_dir)
+    }
+
+    #[tokio::test]
+    async fn full_report() {
+        let view = create_view(r#"\label{foo}\label{foo}\ref{foo}"#);
+        let report = DiagnosticsManager::default()
+            .report(&view, &Options::default())
+            .await;
+
+        let DocumentDiagnosticReport::Full(report) = report;
+        assert_eq!(report.items.len(), 1);
+    }
 }
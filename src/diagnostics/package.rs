@@ -0,0 +1,197 @@
+use crate::{
+    feature::DocumentView,
+    protocol::{Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, Range},
+    syntax::{latex, LatexIncludeKind, SyntaxNode},
+    workspace::{Document, DocumentContent},
+};
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct DuplicatePackageDiagnosticsProvider;
+
+impl DuplicatePackageDiagnosticsProvider {
+    pub fn get(self, view: &DocumentView) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let table = match &view.current.content {
+            DocumentContent::Latex(table) => table,
+            DocumentContent::Bibtex(_) => return diagnostics,
+        };
+
+        let order = view.include_order();
+        let mut originals: HashMap<&str, (_, &Document, Range, BTreeSet<&str>)> = HashMap::new();
+        for doc in &view.related {
+            if let DocumentContent::Latex(table) = &doc.content {
+                for (name, options) in packages(table) {
+                    let key = order.key(&doc.uri, name.start());
+                    match originals.get(name.text()) {
+                        Some((original_key, _, _, _)) if *original_key <= key => (),
+                        _ => {
+                            originals.insert(name.text(), (key, doc, name.range(), options));
+                        }
+                    }
+                }
+            }
+        }
+
+        for (name, options) in packages(table) {
+            let (doc, range, original_options) = match originals.get(name.text()) {
+                Some((_, doc, range, original_options))
+                    if doc.uri != view.current.uri || *range != name.range() =>
+                {
+                    (doc, *range, original_options)
+                }
+                _ => continue,
+            };
+
+            let (message, severity) = if options == *original_options {
+                (
+                    format!("Package loaded more than once: {}", name.text()),
+                    DiagnosticSeverity::Information,
+                )
+            } else {
+                (
+                    format!(
+                        "Package loaded more than once with different options: {}",
+                        name.text()
+                    ),
+                    DiagnosticSeverity::Warning,
+                )
+            };
+
+            diagnostics.push(Diagnostic {
+                source: Some("latex".into()),
+                range: name.range(),
+                message,
+                severity: Some(severity),
+                code: None,
+                related_information: Some(vec![DiagnosticRelatedInformation {
+                    location: Location::new(doc.uri.clone().into(), range),
+                    message: "First loaded here".into(),
+                }]),
+                tags: None,
+            });
+        }
+        diagnostics
+    }
+}
+
+fn packages(table: &latex::SymbolTable) -> Vec<(&latex::Token, BTreeSet<&str>)> {
+    table
+        .includes
+        .iter()
+        .filter(|include| include.kind == LatexIncludeKind::Package)
+        .flat_map(|include| {
+            let options: BTreeSet<_> = table
+                .extract_comma_separated_words(include.parent, latex::GroupKind::Options, 0)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|option| option.text())
+                .collect();
+            include
+                .paths(&table)
+                .into_iter()
+                .map(move |name| (name, options.clone()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{diagnostics::helper::analyze_view, feature::FeatureTester, protocol::RangeExt};
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn unique_packages() {
+        let diagnostics = analyze_view(
+            vec![(
+                "foo.tex",
+                r#"\usepackage{amsmath}\usepackage[utf8]{inputenc}"#,
+            )],
+            |view| DuplicatePackageDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn same_options() {
+        let diagnostics = analyze_view(
+            vec![(
+                "foo.tex",
+                indoc!(
+                    r#"
+                    \usepackage[a, b]{foo}
+                    \usepackage[b,a]{foo}
+                "#
+                ),
+            )],
+            |view| DuplicatePackageDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(1, 17, 1, 20));
+        assert_eq!(diagnostics[0].message, "Package loaded more than once: foo");
+        assert_eq!(
+            diagnostics[0].severity,
+            Some(DiagnosticSeverity::Information)
+        );
+    }
+
+    #[tokio::test]
+    async fn different_options() {
+        let diagnostics = analyze_view(
+            vec![(
+                "foo.tex",
+                indoc!(
+                    r#"
+                    \usepackage{foo,bar}
+                    \usepackage[baz]{foo}
+                "#
+                ),
+            )],
+            |view| DuplicatePackageDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(1, 17, 1, 20));
+        assert_eq!(
+            diagnostics[0].message,
+            "Package loaded more than once with different options: foo"
+        );
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::Warning));
+    }
+
+    #[tokio::test]
+    async fn other_document() {
+        let diagnostics = analyze_view(
+            vec![
+                ("foo.tex", r#"\include{bar}\usepackage[draft]{foo}"#),
+                ("bar.tex", r#"\usepackage{foo}"#),
+            ],
+            |view| DuplicatePackageDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 32, 0, 35));
+        assert_eq!(
+            diagnostics[0].related_information.as_ref().unwrap()[0].location,
+            Location::new(
+                FeatureTester::uri("bar.tex").into(),
+                Range::new_simple(0, 12, 0, 15)
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn require_package() {
+        let diagnostics = analyze_view(
+            vec![("foo.tex", r#"\usepackage{foo}\RequirePackage[bar]{foo}"#)],
+            |view| DuplicatePackageDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 37, 0, 40));
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::Warning));
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{
+    protocol::{Diagnostic, DiagnosticSeverity, Position, Range, RangeExt, Uri, WorkspaceEdit},
+    syntax::SyntaxNode,
+    workspace::{Document, DocumentContent},
+};
+use futures::lock::Mutex;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+static HARD_CODED_REFERENCE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new("(?i)\\b(fig(ure)?|tab(le)?|eq(uation)?|sec(tion)?|chap(ter)?|thm|theorem|lemma)\\.?[~\\s]*\\(?\\d+(\\.\\d+)*\\)?").unwrap()
+});
+
+// Hard-coded references are only searched for on the lines directly around the label.
+const CONTEXT_LINES: u64 = 1;
+
+#[derive(Debug, Default)]
+pub struct RenamedLabelDiagnosticsProvider {
+    names_by_uri: Mutex<HashMap<Uri, HashSet<String>>>,
+}
+
+impl RenamedLabelDiagnosticsProvider {
+    pub async fn get(&self, doc: &Document) -> Vec<Diagnostic> {
+        let table = match &doc.content {
+            DocumentContent::Latex(table) => table,
+            DocumentContent::Bibtex(_) => return Vec::new(),
+        };
+
+        let mut names_by_uri = self.names_by_uri.lock().await;
+        let names = match names_by_uri.get_mut(&doc.uri) {
+            Some(names) => names,
+            None => return Vec::new(),
+        };
+
+        names.retain(|name| table.label_names().any(|label| label.text() == name));
+        if names.is_empty() {
+            names_by_uri.remove(&doc.uri);
+            return Vec::new();
+        }
+
+        let mut lines = BTreeSet::new();
+        for label in table
+            .label_names()
+            .filter(|label| names.contains(label.text()))
+        {
+            let line = label.name.start().line;
+            lines.extend(line.saturating_sub(CONTEXT_LINES)..=line + CONTEXT_LINES);
+        }
+
+        let text_lines: Vec<_> = doc.text.lines().collect();
+        let mut diagnostics = Vec::new();
+        for line in lines {
+            let text = match text_lines.get(line as usize) {
+                Some(text) => text,
+                None => continue,
+            };
+
+            for reference in HARD_CODED_REFERENCE_REGEX.find_iter(text) {
+                let start = text[..reference.start()].chars().count() as u64;
+                let end = start + reference.as_str().chars().count() as u64;
+                diagnostics.push(Diagnostic {
+                    source: Some("latex".into()),
+                    range: Range::new(Position::new(line, start), Position::new(line, end)),
+                    message: format!(
+                        "Hard-coded reference near a renamed label: {}",
+                        reference.as_str()
+                    ),
+                    severity: Some(DiagnosticSeverity::Information),
+                    code: None,
+                    related_information: None,
+                    tags: None,
+                });
+            }
+        }
+        diagnostics
+    }
+
+    pub async fn update(
+        &self,
+        doc: &Document,
+        pos: Position,
+        new_name: &str,
+        edit: &WorkspaceEdit,
+    ) {
+        if let DocumentContent::Latex(table) = &doc.content {
+            if table
+                .label_names()
+                .any(|label| label.name.range().contains(pos))
+            {
+                let mut names_by_uri = self.names_by_uri.lock().await;
+                for uri in edit.changes.iter().flat_map(|changes| changes.keys()) {
+                    names_by_uri
+                        .entry(uri.clone().into())
+                        .or_default()
+                        .insert(new_name.to_owned());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        diagnostics::helper::{test_document, test_uri},
+        protocol::{Options, TextEdit},
+    };
+    use indoc::indoc;
+
+    fn open(name: &str, text: &str) -> Document {
+        test_document(name, text, &Options::default())
+    }
+
+    fn edit(names: &[&str]) -> WorkspaceEdit {
+        let changes = names
+            .iter()
+            .map(|name| (test_uri(name).into(), Vec::<TextEdit>::new()))
+            .collect();
+        WorkspaceEdit::new(changes)
+    }
+
+    #[tokio::test]
+    async fn not_renamed() {
+        let doc = open("main.tex", r#"See Figure~3 and \ref{fig:foo}."#);
+        let provider = RenamedLabelDiagnosticsProvider::default();
+        assert!(provider.get(&doc).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn other_position() {
+        let doc = open("main.tex", r#"\label{foo} bar"#);
+        let provider = RenamedLabelDiagnosticsProvider::default();
+        provider
+            .update(&doc, Position::new(0, 13), "baz", &edit(&["main.tex"]))
+            .await;
+        assert!(provider.names_by_uri.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn renamed() {
+        let old_doc = open("main.tex", r#"\label{fig:foo}"#);
+        let provider = RenamedLabelDiagnosticsProvider::default();
+        provider
+            .update(
+                &old_doc,
+                Position::new(0, 9),
+                "fig:bar",
+                &edit(&["main.tex"]),
+            )
+            .await;
+
+        let doc = open(
+            "main.tex",
+            indoc!(
+                r#"
+                    As shown in Figure~3, see \ref{fig:bar}.
+
+                    Unrelated equation 4.
+                "#
+            ),
+        );
+        let diagnostics = provider.get(&doc).await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 12, 0, 20));
+        assert_eq!(
+            diagnostics[0].message,
+            "Hard-coded reference near a renamed label: Figure~3"
+        );
+    }
+
+    #[tokio::test]
+    async fn untouched_document() {
+        let old_doc = open("main.tex", r#"\label{fig:foo}"#);
+        let provider = RenamedLabelDiagnosticsProvider::default();
+        provider
+            .update(
+                &old_doc,
+                Position::new(0, 9),
+                "fig:bar",
+                &edit(&["main.tex"]),
+            )
+            .await;
+
+        let doc = open("other.tex", r#"Figure~3, see \ref{fig:bar}."#);
+        assert!(provider.get(&doc).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn label_removed() {
+        let old_doc = open("main.tex", r#"\label{fig:foo}"#);
+        let provider = RenamedLabelDiagnosticsProvider::default();
+        provider
+            .update(
+                &old_doc,
+                Position::new(0, 9),
+                "fig:bar",
+                &edit(&["main.tex"]),
+            )
+            .await;
+
+        let doc = open("main.tex", r#"Figure~3"#);
+        assert!(provider.get(&doc).await.is_empty());
+        assert!(provider.names_by_uri.lock().await.is_empty());
+    }
+}
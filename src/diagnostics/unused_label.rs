@@ -0,0 +1,127 @@
+use crate::{
+    feature::DocumentView,
+    protocol::{Diagnostic, DiagnosticSeverity, DiagnosticTag, Uri},
+    syntax::{latex, LatexIncludeKind, LatexLabelKind, SyntaxNode},
+    workspace::DocumentContent,
+};
+use std::collections::HashSet;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct UnusedLabelDiagnosticsProvider;
+
+impl UnusedLabelDiagnosticsProvider {
+    pub fn get(self, view: &DocumentView) -> Vec<Diagnostic> {
+        let table = match &view.current.content {
+            DocumentContent::Latex(table) => table,
+            DocumentContent::Bibtex(_) => return Vec::new(),
+        };
+
+        if is_external_document(view) {
+            return Vec::new();
+        }
+
+        let mut references = HashSet::new();
+        for doc in &view.related {
+            if let DocumentContent::Latex(table) = &doc.content {
+                table
+                    .label_names()
+                    .filter(|label| label.kind().is_reference())
+                    .for_each(|label| {
+                        references.insert(label.text());
+                    });
+            }
+        }
+
+        table
+            .label_names()
+            .filter(|label| label.kind() == LatexLabelKind::Definition)
+            .filter(|label| !references.contains(label.text()))
+            .map(|label| Diagnostic {
+                source: Some("latex".into()),
+                range: label.name.range(),
+                message: format!("Unused label: {}", label.text()),
+                severity: Some(DiagnosticSeverity::Hint),
+                code: None,
+                related_information: None,
+                tags: Some(vec![DiagnosticTag::Unnecessary]),
+            })
+            .collect()
+    }
+}
+
+fn is_external_document(view: &DocumentView) -> bool {
+    view.snapshot
+        .0
+        .iter()
+        .filter_map(|doc| Some((&doc.uri, doc.content.as_latex()?)))
+        .flat_map(|(uri, table)| external_documents(uri, table))
+        .any(|target| target == view.current.uri)
+}
+
+fn external_documents(uri: &Uri, table: &latex::SymbolTable) -> Vec<Uri> {
+    table
+        .commands
+        .iter()
+        .filter(|node| table.as_command(**node).unwrap().name.text() == "\\externaldocument")
+        .filter_map(|node| table.extract_word(*node, latex::GroupKind::Group, 0))
+        .filter_map(|path| latex::include_targets(uri, path.text(), LatexIncludeKind::Latex))
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        diagnostics::helper::analyze_view,
+        protocol::{Range, RangeExt},
+    };
+
+    #[tokio::test]
+    async fn referenced_label() {
+        let diagnostics = analyze_view(vec![("foo.tex", r#"\label{foo}\ref{foo}"#)], |view| {
+            UnusedLabelDiagnosticsProvider.get(view)
+        })
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn unused_label() {
+        let diagnostics = analyze_view(
+            vec![("foo.tex", r#"\label{foo}\label{bar}\ref{foo}"#)],
+            |view| UnusedLabelDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 18, 0, 21));
+        assert_eq!(diagnostics[0].message, "Unused label: bar");
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::Hint));
+    }
+
+    #[tokio::test]
+    async fn referenced_in_other_document() {
+        let diagnostics = analyze_view(
+            vec![
+                ("foo.tex", r#"\include{bar}\label{foo}"#),
+                ("bar.tex", r#"\cref{foo}"#),
+            ],
+            |view| UnusedLabelDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn external_document() {
+        let diagnostics = analyze_view(
+            vec![
+                ("foo.tex", r#"\label{foo}"#),
+                ("bar.tex", r#"\externaldocument{foo}\ref{foo}"#),
+            ],
+            |view| UnusedLabelDiagnosticsProvider.get(view),
+        )
+        .await;
+        assert!(diagnostics.is_empty());
+    }
+}
@@ -7,9 +7,13 @@ use crate::{
 use async_trait::async_trait;
 use itertools::Itertools;
 use std::{
+    collections::{BTreeMap, HashMap, HashSet},
     env,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -61,6 +65,109 @@ impl DocumentView {
             .unique_by(|component| &component.file_names)
             .collect()
     }
+
+    pub fn include_order(&self) -> IncludeOrder {
+        let docs_by_uri: HashMap<_, _> = self.related.iter().map(|doc| (&doc.uri, doc)).collect();
+        let mut children_by_uri = HashMap::new();
+        let mut included = HashSet::new();
+        for doc in &self.related {
+            if let DocumentContent::Latex(table) = &doc.content {
+                let includes = table.includes.iter().flat_map(|include| {
+                    let start = table[include.parent].start();
+                    include
+                        .all_targets
+                        .iter()
+                        .map(move |targets| (start, targets.as_slice()))
+                });
+                let imports = table
+                    .imports
+                    .iter()
+                    .map(|import| (table[import.parent].start(), import.targets.as_slice()));
+
+                let mut children = Vec::new();
+                for (start, targets) in includes.chain(imports) {
+                    if let Some(child) = targets.iter().find_map(|target| docs_by_uri.get(target)) {
+                        if child.uri != doc.uri {
+                            included.insert(&child.uri);
+                            children.push((start, *child));
+                        }
+                    }
+                }
+                children.sort_by_key(|(start, _)| *start);
+                children_by_uri.insert(&doc.uri, children);
+            }
+        }
+
+        let mut roots: Vec<_> = self
+            .related
+            .iter()
+            .filter(|doc| doc.content.as_latex().is_some() && !included.contains(&doc.uri))
+            .collect();
+        roots.sort_by(|a, b| a.uri.as_str().cmp(b.uri.as_str()));
+
+        let mut prefixes = HashMap::new();
+        let mut stack: Vec<_> = roots
+            .into_iter()
+            .rev()
+            .map(|doc| (doc, Vec::new()))
+            .collect();
+        while let Some((doc, prefix)) = stack.pop() {
+            if prefixes.contains_key(&doc.uri) {
+                continue;
+            }
+
+            for (start, child) in children_by_uri.get(&doc.uri).into_iter().flatten().rev() {
+                let mut child_prefix = prefix.clone();
+                child_prefix.push(*start);
+                stack.push((child, child_prefix));
+            }
+            prefixes.insert(doc.uri.clone(), prefix);
+        }
+        IncludeOrder { prefixes }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct IncludeOrder {
+    prefixes: HashMap<Uri, Vec<Position>>,
+}
+
+impl IncludeOrder {
+    pub fn key(&self, uri: &Uri, pos: Position) -> (bool, Vec<Position>, String) {
+        match self.prefixes.get(uri) {
+            Some(prefix) => {
+                let mut positions = prefix.clone();
+                positions.push(pos);
+                (false, positions, uri.as_str().to_owned())
+            }
+            None => (true, vec![pos], uri.as_str().to_owned()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+impl From<Arc<AtomicBool>> for CancellationToken {
+    fn from(cancelled: Arc<AtomicBool>) -> Self {
+        Self { cancelled }
+    }
 }
 
 #[derive(Clone)]
@@ -70,7 +177,10 @@ pub struct FeatureRequest<P> {
     pub distro: Arc<dyn Distribution>,
     pub client_capabilities: Arc<ClientCapabilities>,
     pub options: Options,
+    pub config: Arc<Config>,
     pub current_dir: Arc<PathBuf>,
+    pub root_dir: Option<PathBuf>,
+    pub cancellation: CancellationToken,
 }
 
 impl<P> FeatureRequest<P> {
@@ -120,6 +230,10 @@ where
     async fn execute<'a>(&'a self, req: &'a FeatureRequest<P>) -> Vec<O> {
         let mut items = Vec::new();
         for provider in &self.providers {
+            if req.cancellation.is_cancelled() {
+                break;
+            }
+
             items.append(&mut provider.execute(req).await);
         }
         items
@@ -150,6 +264,10 @@ where
 
     async fn execute<'a>(&'a self, req: &'a FeatureRequest<P>) -> Option<O> {
         for provider in &self.providers {
+            if req.cancellation.is_cancelled() {
+                return None;
+            }
+
             let item = provider.execute(req).await;
             if item.is_some() {
                 return item;
@@ -169,8 +287,14 @@ pub struct FeatureTester {
     include_declaration: bool,
     client_capabilities: Arc<ClientCapabilities>,
     current_dir: Arc<PathBuf>,
+    workspace_dir: Option<PathBuf>,
     root_dir: Option<PathBuf>,
     output_dir: Option<PathBuf>,
+    latex_completion: Option<LatexCompletionOptions>,
+    max_file_lines: Option<usize>,
+    surround_selection: Option<bool>,
+    completion_context: Option<CompletionContext>,
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl Default for FeatureTester {
@@ -190,9 +314,26 @@ impl FeatureTester {
             include_declaration: false,
             client_capabilities: Arc::default(),
             current_dir: Arc::new(env::temp_dir()),
+            workspace_dir: None,
             root_dir: None,
             output_dir: None,
+            latex_completion: None,
+            max_file_lines: None,
+            surround_selection: None,
+            completion_context: None,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    pub fn from_files(files: Vec<(&str, &str)>) -> Self {
+        let mut tester = Self::new();
+        if let Some((name, _)) = files.first() {
+            tester.main(*name);
+        }
+        for (name, text) in files {
+            tester.file(name, text);
         }
+        tester
     }
 
     pub fn main<S: Into<String>>(&mut self, name: S) -> &mut Self {
@@ -224,6 +365,11 @@ impl FeatureTester {
         self
     }
 
+    pub fn workspace_directory<P: Into<PathBuf>>(&mut self, path: P) -> &mut Self {
+        self.workspace_dir = Some(path.into());
+        self
+    }
+
     pub fn root_directory<P: Into<PathBuf>>(&mut self, path: P) -> &mut Self {
         self.root_dir = Some(path.into());
         self
@@ -234,6 +380,36 @@ impl FeatureTester {
         self
     }
 
+    pub fn latex_completion(&mut self, options: LatexCompletionOptions) -> &mut Self {
+        self.latex_completion = Some(options);
+        self
+    }
+
+    pub fn max_file_lines(&mut self, value: usize) -> &mut Self {
+        self.max_file_lines = Some(value);
+        self
+    }
+
+    pub fn surround_selection(&mut self, value: bool) -> &mut Self {
+        self.surround_selection = Some(value);
+        self
+    }
+
+    pub fn completion_context(&mut self, context: CompletionContext) -> &mut Self {
+        self.completion_context = Some(context);
+        self
+    }
+
+    pub fn diagnostics(&mut self, diagnostics: Vec<Diagnostic>) -> &mut Self {
+        self.diagnostics = diagnostics;
+        self
+    }
+
+    pub fn client_capabilities(&mut self, capabilities: ClientCapabilities) -> &mut Self {
+        self.client_capabilities = Arc::new(capabilities);
+        self
+    }
+
     pub fn uri(name: &str) -> Uri {
         let path = env::temp_dir().join(name);
         Uri::from_file_path(path).unwrap()
@@ -251,7 +427,10 @@ impl FeatureTester {
                     output_directory: self.output_dir.clone(),
                     ..LatexBuildOptions::default()
                 }),
+                completion: self.latex_completion.clone(),
                 root_directory: self.root_dir.clone(),
+                max_file_lines: self.max_file_lines,
+                surround_selection: self.surround_selection,
                 ..LatexOptions::default()
             }),
             ..Options::default()
@@ -265,11 +444,7 @@ impl FeatureTester {
         for (name, text) in &self.files {
             let uri = Self::uri(name);
             let path = uri.to_file_path().unwrap();
-            let language = path
-                .extension()
-                .and_then(|ext| ext.to_str())
-                .and_then(Language::by_extension)
-                .unwrap();
+            let language = Language::by_path(&path, &options).unwrap();
             let doc = Document::open(DocumentParams {
                 uri,
                 text: text.trim().into(),
@@ -284,6 +459,10 @@ impl FeatureTester {
         DocumentView::analyze(Arc::new(snapshot), current, &options, &self.current_dir)
     }
 
+    pub async fn test_view(&self) -> DocumentView {
+        self.view().await
+    }
+
     async fn request<P>(&self, params: P) -> FeatureRequest<P> {
         FeatureRequest {
             params,
@@ -291,7 +470,13 @@ impl FeatureTester {
             client_capabilities: Arc::clone(&self.client_capabilities),
             distro: self.distro.clone(),
             options: self.options(),
+            config: Arc::new(Config::new(&self.options())),
             current_dir: Arc::clone(&self.current_dir),
+            root_dir: self
+                .workspace_dir
+                .as_ref()
+                .map(|path| self.current_dir.join(path)),
+            cancellation: CancellationToken::new(),
         }
     }
 
@@ -305,6 +490,68 @@ impl FeatureTester {
         provider.execute(&req).await
     }
 
+    pub async fn test_code_action<F, O>(&self, provider: F, range: Range) -> O
+    where
+        F: FeatureProvider<Params = CodeActionParams, Output = O>,
+    {
+        let text_document = self.identifier();
+        let params = CodeActionParams {
+            text_document,
+            range,
+            context: CodeActionContext {
+                diagnostics: self.diagnostics.clone(),
+                only: None,
+            },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+        let req = self.request(params).await;
+        provider.execute(&req).await
+    }
+
+    pub async fn test_minimal_example<F, O>(&self, provider: F, range: Range) -> O
+    where
+        F: FeatureProvider<Params = MinimalExampleParams, Output = O>,
+    {
+        let text_document = self.identifier();
+        let params = MinimalExampleParams {
+            text_document,
+            range,
+        };
+        let req = self.request(params).await;
+        provider.execute(&req).await
+    }
+
+    pub async fn test_color<F, O>(&self, provider: F) -> O
+    where
+        F: FeatureProvider<Params = DocumentColorParams, Output = O>,
+    {
+        let text_document = self.identifier();
+        let params = DocumentColorParams {
+            text_document,
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+        let req = self.request(params).await;
+        provider.execute(&req).await
+    }
+
+    pub async fn test_color_presentation<F, O>(&self, provider: F, range: Range, color: Color) -> O
+    where
+        F: FeatureProvider<Params = ColorPresentationParams, Output = O>,
+    {
+        let text_document = self.identifier();
+        let params = ColorPresentationParams {
+            text_document,
+            color,
+            range,
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+        let req = self.request(params).await;
+        provider.execute(&req).await
+    }
+
     pub async fn test_completion<F, O>(&self, provider: F) -> O
     where
         F: FeatureProvider<Params = CompletionParams, Output = O>,
@@ -319,7 +566,7 @@ impl FeatureTester {
                 self.identifier(),
                 self.position,
             ),
-            context: None,
+            context: self.completion_context.clone(),
             work_done_progress_params: WorkDoneProgressParams::default(),
             partial_result_params: PartialResultParams::default(),
         };
@@ -389,6 +636,20 @@ impl FeatureTester {
         provider.execute(&req).await
     }
 
+    pub async fn test_file_rename<F, O>(&self, provider: F, old_name: &str, new_name: &str) -> O
+    where
+        F: FeatureProvider<Params = RenameFilesParams, Output = O>,
+    {
+        let params = RenameFilesParams {
+            files: vec![FileRename {
+                old_uri: Self::uri(old_name).to_string(),
+                new_uri: Self::uri(new_name).to_string(),
+            }],
+        };
+        let req = self.request(params).await;
+        provider.execute(&req).await
+    }
+
     pub async fn test_symbol<F, O>(&self, provider: F) -> O
     where
         F: FeatureProvider<Params = DocumentSymbolParams, Output = O>,
@@ -403,3 +664,141 @@ impl FeatureTester {
         provider.execute(&req).await
     }
 }
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct CompletionItemSpec {
+    label: String,
+    kind: Option<CompletionItemKind>,
+    detail: Option<String>,
+    sort_text: Option<String>,
+    insert_text: Option<String>,
+}
+
+impl CompletionItemSpec {
+    pub fn new<S: Into<String>>(label: S) -> Self {
+        Self {
+            label: label.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn kind(mut self, kind: CompletionItemKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    pub fn detail<S: Into<String>>(mut self, detail: S) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    pub fn sort_text<S: Into<String>>(mut self, sort_text: S) -> Self {
+        self.sort_text = Some(sort_text.into());
+        self
+    }
+
+    pub fn insert_text<S: Into<String>>(mut self, insert_text: S) -> Self {
+        self.insert_text = Some(insert_text.into());
+        self
+    }
+
+    pub fn assert_matches(&self, items: &[CompletionItem]) {
+        let item = items
+            .iter()
+            .find(|item| item.label == self.label)
+            .unwrap_or_else(|| {
+                let labels: Vec<_> = items.iter().map(|item| item.label.as_str()).collect();
+                panic!("no completion item `{}` in {:?}", self.label, labels)
+            });
+
+        if let Some(kind) = self.kind {
+            assert_eq!(item.kind, Some(kind), "kind of `{}`", self.label);
+        }
+
+        if let Some(detail) = &self.detail {
+            assert_eq!(
+                item.detail.as_ref(),
+                Some(detail),
+                "detail of `{}`",
+                self.label
+            );
+        }
+
+        if let Some(sort_text) = &self.sort_text {
+            assert_eq!(
+                item.sort_text.as_ref(),
+                Some(sort_text),
+                "sort text of `{}`",
+                self.label
+            );
+        }
+
+        if let Some(insert_text) = &self.insert_text {
+            let actual_text = match &item.text_edit {
+                Some(CompletionTextEdit::Edit(edit)) => Some(&edit.new_text),
+                _ => item.insert_text.as_ref(),
+            };
+            assert_eq!(
+                actual_text,
+                Some(insert_text),
+                "insert text of `{}`",
+                self.label
+            );
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct WorkspaceEditSpec {
+    edits: Vec<(String, TextEdit)>,
+}
+
+impl WorkspaceEditSpec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn edit<S, T>(mut self, name: S, range: Range, new_text: T) -> Self
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        self.edits
+            .push((name.into(), TextEdit::new(range, new_text.into())));
+        self
+    }
+
+    pub fn build(&self) -> WorkspaceEdit {
+        let mut changes = HashMap::new();
+        for (name, edit) in &self.edits {
+            changes
+                .entry(FeatureTester::uri(name).into())
+                .or_insert_with(Vec::new)
+                .push(edit.clone());
+        }
+        WorkspaceEdit::new(changes)
+    }
+
+    pub fn assert_matches(&self, actual: &WorkspaceEdit) {
+        assert_eq!(
+            normalize_workspace_edit(actual),
+            normalize_workspace_edit(&self.build())
+        );
+    }
+}
+
+fn normalize_workspace_edit(edit: &WorkspaceEdit) -> BTreeMap<String, Vec<TextEdit>> {
+    let mut changes = BTreeMap::new();
+    for (uri, edits) in edit.changes.iter().flatten() {
+        let mut edits = edits.clone();
+        edits.sort_by(|a, b| {
+            (a.range.start, a.range.end, &a.new_text).cmp(&(
+                b.range.start,
+                b.range.end,
+                &b.new_text,
+            ))
+        });
+        changes.insert(uri.to_string(), edits);
+    }
+    changes
+}
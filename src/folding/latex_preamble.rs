@@ -0,0 +1,125 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{FoldingRange, FoldingRangeKind, FoldingRangeParams},
+    syntax::SyntaxNode,
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexPreambleFoldingProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexPreambleFoldingProvider {
+    type Params = FoldingRangeParams;
+    type Output = Vec<FoldingRange>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut foldings = Vec::new();
+        if let DocumentContent::Latex(table) = &req.current().content {
+            let class = table
+                .commands
+                .iter()
+                .find(|node| table.as_command(**node).unwrap().name.text() == "\\documentclass");
+
+            let document = table
+                .environments
+                .iter()
+                .find(|env| env.left.name(&table).map(|name| name.text()) == Some("document"));
+
+            if let (Some(class), Some(document)) = (class, document) {
+                let class_node = &table[*class];
+                let begin_node = &table[document.left.parent];
+                if begin_node.start().line > class_node.end().line + 1 {
+                    foldings.push(FoldingRange {
+                        start_line: class_node.end().line,
+                        start_character: Some(class_node.end().character),
+                        end_line: begin_node.start().line - 1,
+                        end_character: Some(0),
+                        kind: Some(FoldingRangeKind::Region),
+                    });
+                }
+            }
+        }
+        foldings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feature::FeatureTester;
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn preamble() {
+        let actual_foldings = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \documentclass{article}
+                        \usepackage{amsmath}
+                        \usepackage{lipsum}
+                        \begin{document}
+                        \end{document}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .test_folding(LatexPreambleFoldingProvider)
+            .await;
+
+        let expected_foldings = vec![FoldingRange {
+            start_line: 0,
+            start_character: Some(23),
+            end_line: 2,
+            end_character: Some(0),
+            kind: Some(FoldingRangeKind::Region),
+        }];
+
+        assert_eq!(actual_foldings, expected_foldings);
+    }
+
+    #[tokio::test]
+    async fn empty_preamble() {
+        let actual_foldings = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \documentclass{article}
+                        \begin{document}
+                        \end{document}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .test_folding(LatexPreambleFoldingProvider)
+            .await;
+
+        assert!(actual_foldings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let actual_foldings = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .test_folding(LatexPreambleFoldingProvider)
+            .await;
+
+        assert!(actual_foldings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let actual_foldings = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .test_folding(LatexPreambleFoldingProvider)
+            .await;
+
+        assert!(actual_foldings.is_empty());
+    }
+}
@@ -1,10 +1,11 @@
 mod bibtex_decl;
 mod latex_env;
+mod latex_preamble;
 mod latex_section;
 
 use self::{
     bibtex_decl::BibtexDeclarationFoldingProvider, latex_env::LatexEnvironmentFoldingProvider,
-    latex_section::LatexSectionFoldingProvider,
+    latex_preamble::LatexPreambleFoldingProvider, latex_section::LatexSectionFoldingProvider,
 };
 use crate::{
     feature::{ConcatProvider, FeatureProvider, FeatureRequest},
@@ -22,6 +23,7 @@ impl FoldingProvider {
             provider: ConcatProvider::new(vec![
                 Box::new(BibtexDeclarationFoldingProvider),
                 Box::new(LatexEnvironmentFoldingProvider),
+                Box::new(LatexPreambleFoldingProvider),
                 Box::new(LatexSectionFoldingProvider),
             ]),
         }
@@ -1,6 +1,6 @@
 use crate::{
     feature::{FeatureProvider, FeatureRequest},
-    protocol::{DocumentHighlight, DocumentHighlightKind, RangeExt, TextDocumentPositionParams},
+    protocol::{DocumentHighlight, DocumentHighlightKind, TextDocumentPositionParams},
     syntax::{latex, LatexLabelKind, SyntaxNode},
     workspace::DocumentContent,
 };
@@ -18,27 +18,20 @@ impl FeatureProvider for LatexLabelHighlightProvider {
         let mut highlights = Vec::new();
         if let DocumentContent::Latex(table) = &req.current().content {
             if let Some(name) = table
-                .labels
-                .iter()
-                .flat_map(|label| label.names(&table))
-                .find(|label| label.range().contains(req.params.position))
-                .map(latex::Token::text)
+                .find_label_name(req.params.position)
+                .map(latex::LabelName::text)
             {
-                for label_group in &table.labels {
-                    for label in label_group.names(&table) {
-                        if label.text() == name {
-                            let kind = match label_group.kind {
-                                LatexLabelKind::Definition => DocumentHighlightKind::Write,
-                                LatexLabelKind::Reference(_) => DocumentHighlightKind::Read,
-                            };
+                for label in table.label_names().filter(|label| label.text() == name) {
+                    let kind = match label.kind() {
+                        LatexLabelKind::Definition => DocumentHighlightKind::Write,
+                        LatexLabelKind::Reference(_) => DocumentHighlightKind::Read,
+                    };
 
-                            let highlight = DocumentHighlight {
-                                range: label.range(),
-                                kind: Some(kind),
-                            };
-                            highlights.push(highlight);
-                        }
-                    }
+                    let highlight = DocumentHighlight {
+                        range: label.name.range(),
+                        kind: Some(kind),
+                    };
+                    highlights.push(highlight);
                 }
             }
         }
@@ -49,7 +42,10 @@ impl FeatureProvider for LatexLabelHighlightProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{feature::FeatureTester, protocol::Range};
+    use crate::{
+        feature::FeatureTester,
+        protocol::{Range, RangeExt},
+    };
     use indoc::indoc;
 
     #[tokio::test]
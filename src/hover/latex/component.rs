@@ -81,6 +81,19 @@ mod tests {
         assert_eq!(actual_hover.range.unwrap(), Range::new_simple(0, 12, 0, 19));
     }
 
+    #[tokio::test]
+    async fn required_package_with_options() {
+        let actual_hover = FeatureTester::new()
+            .file("foo.sty", r#"\RequirePackage[foo]{amsmath}"#)
+            .main("foo.sty")
+            .position(0, 24)
+            .test_position(LatexComponentHoverProvider)
+            .await
+            .unwrap();
+
+        assert_eq!(actual_hover.range.unwrap(), Range::new_simple(0, 21, 0, 28));
+    }
+
     #[tokio::test]
     async fn unknown_class() {
         let actual_hover = FeatureTester::new()
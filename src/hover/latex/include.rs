@@ -0,0 +1,147 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{
+        Hover, HoverContents, MarkupContent, MarkupKind, RangeExt, TextDocumentPositionParams, Uri,
+    },
+    syntax::{LatexIncludeKind, SyntaxNode},
+};
+use async_trait::async_trait;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexIncludeHoverProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexIncludeHoverProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Option<Hover>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let table = req.current().content.as_latex()?;
+        for include in table
+            .includes
+            .iter()
+            .filter(|include| include.kind == LatexIncludeKind::Latex)
+        {
+            let paths = include.paths(&table);
+            for (path, targets) in paths.iter().zip(&include.all_targets) {
+                if !path.range().contains(req.params.position) {
+                    continue;
+                }
+
+                let target = req
+                    .snapshot()
+                    .find_target(targets)
+                    .map(|doc| doc.uri.clone())
+                    .or_else(|| targets.iter().find(|target| target.file_exists()).cloned());
+
+                let value = match target {
+                    Some(target) => format!("`{}`", display_path(&target)),
+                    None => {
+                        let extensions = include.kind.extensions().unwrap_or_default();
+                        let target = targets
+                            .iter()
+                            .find(|target| {
+                                extensions
+                                    .iter()
+                                    .any(|ext| target.path().ends_with(&format!(".{}", ext)))
+                            })
+                            .or_else(|| targets.first())?;
+                        format!("File not found: `{}`", display_path(target))
+                    }
+                };
+
+                return Some(Hover {
+                    contents: HoverContents::Markup(MarkupContent {
+                        kind: MarkupKind::Markdown,
+                        value,
+                    }),
+                    range: Some(path.range()),
+                });
+            }
+        }
+        None
+    }
+}
+
+fn display_path(uri: &Uri) -> String {
+    uri.to_file_path()
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| uri.as_str().to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        feature::FeatureTester,
+        protocol::{Range, RangeExt},
+    };
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let actual_hover = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_position(LatexIncludeHoverProvider)
+            .await;
+
+        assert_eq!(actual_hover, None);
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let actual_hover = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .position(0, 0)
+            .test_position(LatexIncludeHoverProvider)
+            .await;
+
+        assert_eq!(actual_hover, None);
+    }
+
+    #[tokio::test]
+    async fn existing_file() {
+        let actual_hover = FeatureTester::new()
+            .file("main.tex", r#"\input{foo}"#)
+            .file("foo.tex", "")
+            .main("main.tex")
+            .position(0, 8)
+            .test_position(LatexIncludeHoverProvider)
+            .await
+            .unwrap();
+
+        let path = FeatureTester::uri("foo.tex").to_file_path().unwrap();
+        let expected_hover = Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("`{}`", path.to_string_lossy()),
+            }),
+            range: Some(Range::new_simple(0, 7, 0, 10)),
+        };
+        assert_eq!(actual_hover, expected_hover);
+    }
+
+    #[tokio::test]
+    async fn missing_file() {
+        let actual_hover = FeatureTester::new()
+            .file("main.tex", r#"\include{foo,bar}"#)
+            .file("foo.tex", "")
+            .main("main.tex")
+            .position(0, 14)
+            .test_position(LatexIncludeHoverProvider)
+            .await
+            .unwrap();
+
+        let path = FeatureTester::uri("bar.tex").to_file_path().unwrap();
+        let expected_hover = Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("File not found: `{}`", path.to_string_lossy()),
+            }),
+            range: Some(Range::new_simple(0, 13, 0, 16)),
+        };
+        assert_eq!(actual_hover, expected_hover);
+    }
+}
@@ -1,8 +1,12 @@
+use super::label_number::is_page_reference;
 use crate::{
     feature::{DocumentView, FeatureProvider, FeatureRequest},
-    outline::{Outline, OutlineContext},
-    protocol::{Hover, HoverContents, Position, RangeExt, TextDocumentPositionParams},
-    syntax::{latex, LatexLabelKind, SyntaxNode},
+    outline::{cleveref_capitalization, find_label_numbering, CrefNames, Outline, OutlineContext},
+    protocol::{
+        Hover, HoverContents, MarkupContent, MarkupKind, Position, RangeExt,
+        TextDocumentPositionParams,
+    },
+    syntax::{latex, SyntaxNode},
     workspace::{Document, DocumentContent},
 };
 use async_trait::async_trait;
@@ -18,14 +22,33 @@ impl FeatureProvider for LatexLabelHoverProvider {
 
     async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
         let table = req.current().content.as_latex()?;
-        let reference = Self::find_reference(table, req.params.position)?;
+        let (command, reference) = Self::find_reference(table, req.params.position)?;
+        if is_page_reference(command) && has_page_number(&req.view, reference.text()) {
+            return None;
+        }
+
         let (doc, def) = Self::find_definition(&req.view, reference)?;
 
         let snapshot = Arc::clone(&req.view.snapshot);
         let view = DocumentView::analyze(snapshot, doc, &req.options, &req.current_dir);
         let outline = Outline::analyze(&view, &req.options, &req.current_dir);
         let outline_ctx = OutlineContext::parse(&view, &outline, def)?;
-        let markup = outline_ctx.documentation();
+        let cleveref_detail =
+            cleveref_capitalization(command.trim_start_matches('\\')).and_then(|capitalize| {
+                outline_ctx.cleveref_detail(capitalize, &CrefNames::analyze(&req.view))
+            });
+        let markup = match outline_ctx
+            .name()
+            .filter(|_| is_name_reference(command))
+            .map(ToOwned::to_owned)
+            .or(cleveref_detail)
+        {
+            Some(value) => MarkupContent {
+                kind: MarkupKind::PlainText,
+                value,
+            },
+            None => outline_ctx.documentation(),
+        };
         Some(Hover {
             contents: HoverContents::Markup(markup),
             range: Some(reference.range()),
@@ -34,16 +57,17 @@ impl FeatureProvider for LatexLabelHoverProvider {
 }
 
 impl LatexLabelHoverProvider {
-    fn find_reference(table: &latex::SymbolTable, pos: Position) -> Option<&latex::Token> {
+    fn find_reference(table: &latex::SymbolTable, pos: Position) -> Option<(&str, &latex::Token)> {
         for label in &table.labels {
+            let command = table.as_command(label.parent)?.name.text();
             let names = label.names(&table);
             if names.len() == 1 && table[label.parent].range().contains(pos) {
-                return Some(&label.names(&table)[0]);
+                return Some((command, &label.names(&table)[0]));
             }
 
             for name in &names {
                 if name.range().contains(pos) {
-                    return Some(name);
+                    return Some((command, name));
                 }
             }
         }
@@ -56,12 +80,10 @@ impl LatexLabelHoverProvider {
     ) -> Option<(Arc<Document>, latex::Label)> {
         for doc in &view.related {
             if let DocumentContent::Latex(table) = &doc.content {
-                for label in &table.labels {
-                    if label.kind == LatexLabelKind::Definition {
-                        for name in label.names(&table) {
-                            if name.text() == reference.text() {
-                                return Some((Arc::clone(&doc), *label));
-                            }
+                for label in table.label_definitions() {
+                    for name in label.names(&table) {
+                        if name.text() == reference.text() {
+                            return Some((Arc::clone(&doc), *label));
                         }
                     }
                 }
@@ -71,6 +93,16 @@ impl LatexLabelHoverProvider {
     }
 }
 
+fn has_page_number(view: &DocumentView, name: &str) -> bool {
+    find_label_numbering(view, name)
+        .and_then(|numbering| numbering.page.as_ref())
+        .is_some()
+}
+
+fn is_name_reference(name: &str) -> bool {
+    name == "\\nameref" || name == "\\Nameref"
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,6 +110,7 @@ mod tests {
         feature::FeatureTester,
         protocol::{Range, RangeExt},
     };
+    use indoc::indoc;
 
     #[tokio::test]
     async fn empty_latex_document() {
@@ -115,4 +148,90 @@ mod tests {
 
         assert_eq!(actual_hover.range.unwrap(), Range::new_simple(0, 20, 0, 27));
     }
+
+    #[tokio::test]
+    async fn name_reference() {
+        let actual_hover = FeatureTester::new()
+            .file(
+                "main.tex",
+                r#"\section{Foo}\label{sec:foo}\nameref{sec:foo}"#,
+            )
+            .main("main.tex")
+            .position(0, 38)
+            .test_position(LatexLabelHoverProvider)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            actual_hover.contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::PlainText,
+                value: "Foo".into(),
+            })
+        );
+        assert_eq!(actual_hover.range.unwrap(), Range::new_simple(0, 37, 0, 44));
+    }
+
+    #[tokio::test]
+    async fn custom_cref_name() {
+        let actual_hover = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \crefname{figure}{pic}{pics}
+                        \begin{figure}\caption{Foo}\label{fig:foo}\end{figure}
+                        \cref{fig:foo}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(2, 8)
+            .test_position(LatexLabelHoverProvider)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            actual_hover.contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::PlainText,
+                value: "pic".into(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn page_reference() {
+        let actual_hover = FeatureTester::new()
+            .file(
+                "main.tex",
+                r#"\section{Foo}\label{sec:foo}\pageref{sec:foo}"#,
+            )
+            .file(
+                "main.aux",
+                r#"\newlabel{sec:foo}{{\relax 1}{4}{Foo\relax }{section.1}{}}"#,
+            )
+            .main("main.tex")
+            .position(0, 38)
+            .test_position(LatexLabelHoverProvider)
+            .await;
+
+        assert_eq!(actual_hover, None);
+    }
+
+    #[tokio::test]
+    async fn page_reference_without_aux() {
+        let actual_hover = FeatureTester::new()
+            .file(
+                "main.tex",
+                r#"\section{Foo}\label{sec:foo}\pageref{sec:foo}"#,
+            )
+            .main("main.tex")
+            .position(0, 38)
+            .test_position(LatexLabelHoverProvider)
+            .await
+            .unwrap();
+
+        assert_eq!(actual_hover.range.unwrap(), Range::new_simple(0, 37, 0, 44));
+    }
 }
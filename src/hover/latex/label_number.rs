@@ -0,0 +1,171 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    outline::find_label_numbering,
+    protocol::{
+        Hover, HoverContents, MarkupContent, MarkupKind, RangeExt, TextDocumentPositionParams,
+    },
+    syntax::SyntaxNode,
+};
+use async_trait::async_trait;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexLabelNumberHoverProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexLabelNumberHoverProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Option<Hover>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let table = req.current().content.as_latex()?;
+        let pos = req.params.position;
+        for label in table
+            .labels
+            .iter()
+            .filter(|label| label.kind.is_reference())
+        {
+            for name in label.names(&table) {
+                if !name.range().contains(pos) {
+                    continue;
+                }
+
+                let numbering = find_label_numbering(&req.view, name.text())?;
+                let value = if is_page_reference(table.as_command(label.parent)?.name.text()) {
+                    format!("Page {}", numbering.page.as_ref()?)
+                } else {
+                    numbering.number.clone()
+                };
+
+                return Some(Hover {
+                    contents: HoverContents::Markup(MarkupContent {
+                        kind: MarkupKind::PlainText,
+                        value,
+                    }),
+                    range: Some(name.range()),
+                });
+            }
+        }
+        None
+    }
+}
+
+const PAGE_REFERENCE_COMMANDS: &[&str] = &[
+    "\\pageref",
+    "\\pageref*",
+    "\\autopageref",
+    "\\autopageref*",
+    "\\vpageref",
+    "\\vpageref*",
+    "\\cpageref",
+    "\\Cpageref",
+    "\\cpagerefrange",
+    "\\Cpagerefrange",
+    "\\zpageref",
+    "\\zcpageref",
+];
+
+pub fn is_page_reference(name: &str) -> bool {
+    PAGE_REFERENCE_COMMANDS.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::Range};
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let actual_hover = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_position(LatexLabelNumberHoverProvider)
+            .await;
+
+        assert_eq!(actual_hover, None);
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let actual_hover = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .position(0, 0)
+            .test_position(LatexLabelNumberHoverProvider)
+            .await;
+
+        assert_eq!(actual_hover, None);
+    }
+
+    #[tokio::test]
+    async fn reference() {
+        let actual_hover = FeatureTester::new()
+            .file("main.tex", r#"\ref{sec:foo}"#)
+            .file(
+                "main.aux",
+                r#"\newlabel{sec:foo}{{\relax 2.1}{4}{Foo\relax }{section.2.1}{}}"#,
+            )
+            .main("main.tex")
+            .position(0, 7)
+            .test_position(LatexLabelNumberHoverProvider)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            actual_hover.contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::PlainText,
+                value: "2.1".into(),
+            })
+        );
+        assert_eq!(actual_hover.range.unwrap(), Range::new_simple(0, 5, 0, 12));
+    }
+
+    #[tokio::test]
+    async fn page_reference() {
+        let actual_hover = FeatureTester::new()
+            .file("main.tex", r#"\pageref{sec:foo}"#)
+            .file(
+                "main.aux",
+                r#"\newlabel{sec:foo}{{\relax 2.1}{4}{Foo\relax }{section.2.1}{}}"#,
+            )
+            .main("main.tex")
+            .position(0, 11)
+            .test_position(LatexLabelNumberHoverProvider)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            actual_hover.contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::PlainText,
+                value: "Page 4".into(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn unresolved() {
+        let actual_hover = FeatureTester::new()
+            .file("main.tex", r#"\ref{sec:foo}"#)
+            .file("main.aux", r#"\newlabel{sec:bar}{{1}{1}}"#)
+            .main("main.tex")
+            .position(0, 7)
+            .test_position(LatexLabelNumberHoverProvider)
+            .await;
+
+        assert_eq!(actual_hover, None);
+    }
+
+    #[tokio::test]
+    async fn without_aux() {
+        let actual_hover = FeatureTester::new()
+            .file("main.tex", r#"\ref{sec:foo}"#)
+            .main("main.tex")
+            .position(0, 7)
+            .test_position(LatexLabelNumberHoverProvider)
+            .await;
+
+        assert_eq!(actual_hover, None);
+    }
+}
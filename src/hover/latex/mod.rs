@@ -2,5 +2,7 @@
 pub mod citation;
 
 pub mod component;
+pub mod include;
 pub mod label;
+pub mod label_number;
 pub mod preview;
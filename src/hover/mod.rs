@@ -10,7 +10,8 @@ use self::{
         string_reference::BibtexStringReferenceHoverProvider,
     },
     latex::{
-        component::LatexComponentHoverProvider, label::LatexLabelHoverProvider,
+        component::LatexComponentHoverProvider, include::LatexIncludeHoverProvider,
+        label::LatexLabelHoverProvider, label_number::LatexLabelNumberHoverProvider,
         preview::LatexPreviewHoverProvider,
     },
 };
@@ -34,7 +35,9 @@ impl HoverProvider {
                 #[cfg(feature = "citation")]
                 Box::new(LatexCitationHoverProvider),
                 Box::new(LatexComponentHoverProvider),
+                Box::new(LatexIncludeHoverProvider),
                 Box::new(LatexLabelHoverProvider),
+                Box::new(LatexLabelNumberHoverProvider),
                 Box::new(LatexPreviewHoverProvider),
             ]),
         }
@@ -13,6 +13,9 @@ cfg_if::cfg_if! {
     }
 }
 
+pub mod bibliography;
+pub mod code_action;
+pub mod color;
 pub mod completion;
 pub mod components;
 pub mod definition;
@@ -23,6 +26,8 @@ pub mod forward_search;
 pub mod highlight;
 pub mod hover;
 pub mod link;
+pub mod metrics;
+pub mod minimal_example;
 pub mod outline;
 pub mod protocol;
 pub mod reference;
@@ -30,4 +35,5 @@ pub mod rename;
 pub mod symbol;
 pub mod syntax;
 pub mod tex;
+pub mod todo;
 pub mod workspace;
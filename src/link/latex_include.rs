@@ -36,15 +36,12 @@ impl LatexIncludeLinkProvider {
         let mut links = Vec::new();
         let paths = include.paths(&table);
         for (i, targets) in include.all_targets.iter().enumerate() {
-            for target in targets {
-                if let Some(link) = req.snapshot().find(target).map(|doc| DocumentLink {
+            if let Some(doc) = req.snapshot().find_target(targets) {
+                links.push(DocumentLink {
                     range: paths[i].range(),
                     target: doc.uri.clone().into(),
                     tooltip: None,
-                }) {
-                    links.push(link);
-                    break;
-                }
+                });
             }
         }
         links
@@ -0,0 +1,129 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{DocumentLink, DocumentLinkParams, Range, Uri},
+    syntax::{latex, AstNodeIndex, CharStream, SyntaxNode, LANGUAGE_DATA},
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexUrlLinkProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexUrlLinkProvider {
+    type Params = DocumentLinkParams;
+    type Output = Vec<DocumentLink>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        if let DocumentContent::Latex(table) = &req.current().content {
+            table
+                .commands
+                .iter()
+                .filter_map(|node| Self::resolve(req, table, *node))
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl LatexUrlLinkProvider {
+    fn resolve(
+        req: &FeatureRequest<DocumentLinkParams>,
+        table: &latex::SymbolTable,
+        node: AstNodeIndex,
+    ) -> Option<DocumentLink> {
+        let cmd = table.as_command(node)?;
+        let desc = LANGUAGE_DATA
+            .url_commands
+            .iter()
+            .find(|desc| desc.name == cmd.name.text())?;
+
+        let group =
+            table.as_group(table.extract_group(node, latex::GroupKind::Group, desc.index)?)?;
+        let range = Range::new(group.left.end(), group.right.as_ref()?.start());
+        let url = CharStream::extract(&req.current().text, range);
+        let target = Uri::parse(url.trim()).ok()?;
+        Some(DocumentLink {
+            range,
+            target: target.into(),
+            tooltip: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::RangeExt};
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let actual_links = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .test_link(LatexUrlLinkProvider)
+            .await;
+
+        assert!(actual_links.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let actual_links = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .test_link(LatexUrlLinkProvider)
+            .await;
+
+        assert!(actual_links.is_empty());
+    }
+
+    #[tokio::test]
+    async fn url() {
+        let actual_links = FeatureTester::new()
+            .file("main.tex", r#"\url{https://www.example.com/}"#)
+            .main("main.tex")
+            .test_link(LatexUrlLinkProvider)
+            .await;
+
+        let expected_links = vec![DocumentLink {
+            range: Range::new_simple(0, 5, 0, 29),
+            target: Uri::parse("https://www.example.com/").unwrap().into(),
+            tooltip: None,
+        }];
+
+        assert_eq!(actual_links, expected_links);
+    }
+
+    #[tokio::test]
+    async fn href_text_argument() {
+        let actual_links = FeatureTester::new()
+            .file(
+                "main.tex",
+                r#"\href{https://www.example.com/}{https://www.example.org/}"#,
+            )
+            .main("main.tex")
+            .test_link(LatexUrlLinkProvider)
+            .await;
+
+        let expected_links = vec![DocumentLink {
+            range: Range::new_simple(0, 6, 0, 30),
+            target: Uri::parse("https://www.example.com/").unwrap().into(),
+            tooltip: None,
+        }];
+
+        assert_eq!(actual_links, expected_links);
+    }
+
+    #[tokio::test]
+    async fn invalid_url() {
+        let actual_links = FeatureTester::new()
+            .file("main.tex", r#"\url{foo}"#)
+            .main("main.tex")
+            .test_link(LatexUrlLinkProvider)
+            .await;
+
+        assert!(actual_links.is_empty());
+    }
+}
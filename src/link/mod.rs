@@ -1,7 +1,11 @@
 mod latex_import;
 mod latex_include;
+mod latex_url;
 
-use self::{latex_import::LatexImportLinkProvider, latex_include::LatexIncludeLinkProvider};
+use self::{
+    latex_import::LatexImportLinkProvider, latex_include::LatexIncludeLinkProvider,
+    latex_url::LatexUrlLinkProvider,
+};
 use crate::{
     feature::{ConcatProvider, FeatureProvider, FeatureRequest},
     protocol::{DocumentLink, DocumentLinkParams},
@@ -18,6 +22,7 @@ impl LinkProvider {
             provider: ConcatProvider::new(vec![
                 Box::new(LatexImportLinkProvider),
                 Box::new(LatexIncludeLinkProvider),
+                Box::new(LatexUrlLinkProvider),
             ]),
         }
     }
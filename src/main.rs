@@ -1,12 +1,13 @@
 use futures::{channel::mpsc, prelude::*};
 use jsonrpc::MessageHandler;
-use log::LevelFilter;
+use log::{error, LevelFilter};
 use std::path::PathBuf;
 use std::{env, error, fs::OpenOptions, sync::Arc};
 use structopt::StructOpt;
 use texlab::{
     protocol::{LatexLspClient, LspCodec},
     server::LatexLspServer,
+    syntax::load_user_language_data,
     tex::Distribution,
 };
 use tokio_util::codec::{FramedRead, FramedWrite};
@@ -25,13 +26,28 @@ struct Opts {
     /// Write the logging output to FILE
     #[structopt(long, name = "FILE", parse(from_os_str))]
     log_file: Option<PathBuf>,
+
+    /// Merge additional completion data from the JSON file LANGUAGE_DATA
+    #[structopt(long, name = "LANGUAGE_DATA", parse(from_os_str))]
+    language_data: Option<PathBuf>,
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn error::Error>> {
     let opts = Opts::from_args();
+    let language_data = opts.language_data.clone();
     setup_logger(opts);
 
+    if let Some(path) = language_data {
+        if let Err(why) = load_user_language_data(&path) {
+            error!(
+                "Failed to load language data from {}: {}",
+                path.display(),
+                why
+            );
+        }
+    }
+
     let mut stdin = FramedRead::new(tokio::io::stdin(), LspCodec);
     let (stdout_tx, mut stdout_rx) = mpsc::channel(0);
 
@@ -41,11 +57,7 @@ async fn main() -> Result<(), Box<dyn error::Error>> {
         Arc::clone(&client),
         Arc::new(env::current_dir().expect("failed to get working directory")),
     ));
-    let mut handler = MessageHandler {
-        server,
-        client,
-        output: stdout_tx,
-    };
+    let mut handler = MessageHandler::new(server, client, stdout_tx);
 
     tokio::spawn(async move {
         let mut stdout = FramedWrite::new(tokio::io::stdout(), LspCodec);
@@ -0,0 +1,110 @@
+use crate::protocol::{FeatureTiming, Options};
+use futures::lock::Mutex;
+use log::debug;
+use std::{
+    collections::HashMap,
+    future::Future,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+struct Timing {
+    count: u64,
+    total: Duration,
+    max: Duration,
+}
+
+#[derive(Debug, Default)]
+pub struct MetricsManager {
+    timings: Mutex<HashMap<&'static str, Timing>>,
+}
+
+impl MetricsManager {
+    pub async fn measure<F, T>(&self, feature: &'static str, options: &Options, future: F) -> T
+    where
+        F: Future<Output = T>,
+    {
+        let is_enabled = options
+            .latex
+            .as_ref()
+            .map_or(false, |opts| opts.feature_timings());
+
+        if !is_enabled {
+            return future.await;
+        }
+
+        let start = Instant::now();
+        let output = future.await;
+        let elapsed = start.elapsed();
+        debug!("{} took {:?}", feature, elapsed);
+        self.record(feature, elapsed).await;
+        output
+    }
+
+    async fn record(&self, feature: &'static str, elapsed: Duration) {
+        let mut timings = self.timings.lock().await;
+        let timing = timings.entry(feature).or_default();
+        timing.count += 1;
+        timing.total += elapsed;
+        timing.max = timing.max.max(elapsed);
+    }
+
+    pub async fn get(&self) -> Vec<FeatureTiming> {
+        let timings = self.timings.lock().await;
+        let mut results: Vec<_> = timings
+            .iter()
+            .map(|(feature, timing)| FeatureTiming {
+                feature: (*feature).into(),
+                count: timing.count,
+                total_milliseconds: timing.total.as_secs_f64() * 1000.0,
+                max_milliseconds: timing.max.as_secs_f64() * 1000.0,
+            })
+            .collect();
+        results.sort_by(|a, b| a.feature.cmp(&b.feature));
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::LatexOptions;
+
+    fn options(feature_timings: bool) -> Options {
+        Options {
+            latex: Some(LatexOptions {
+                feature_timings: Some(feature_timings),
+                ..LatexOptions::default()
+            }),
+            ..Options::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn disabled() {
+        let metrics = MetricsManager::default();
+        let output = metrics.measure("foo", &options(false), async { 42 }).await;
+        assert_eq!(output, 42);
+        assert!(metrics.get().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn enabled() {
+        let metrics = MetricsManager::default();
+        let options = options(true);
+        metrics.measure("foo", &options, async {}).await;
+        metrics.measure("bar", &options, async {}).await;
+        metrics.measure("foo", &options, async {}).await;
+
+        let actual_counts: Vec<_> = metrics
+            .get()
+            .await
+            .into_iter()
+            .map(|timing| (timing.feature, timing.count))
+            .collect();
+        assert_eq!(
+            actual_counts,
+            vec![("bar".to_owned(), 1), ("foo".to_owned(), 2)]
+        );
+    }
+}
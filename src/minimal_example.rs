@@ -0,0 +1,284 @@
+use crate::{
+    components::{Component, COMPONENT_DATABASE},
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{MinimalExampleParams, Position, Range, RangeExt},
+    syntax::{latex, CharStream, LatexIncludeKind, SyntaxNode},
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+use std::{cmp, collections::HashSet};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct MinimalExampleProvider;
+
+impl MinimalExampleProvider {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for MinimalExampleProvider {
+    type Params = MinimalExampleParams;
+    type Output = Option<String>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let text = &req.current().text;
+        let table = match &req.current().content {
+            DocumentContent::Latex(table) => table,
+            DocumentContent::Bibtex(_) => return None,
+        };
+
+        let document = table
+            .environments
+            .iter()
+            .find(|env| env.left.name(&table).map(|name| name.text()) == Some("document"))?;
+
+        let body_start = table[document.left.parent].end();
+        let body_end = table[document.right.parent].start();
+        let range = Range::new(
+            cmp::max(req.params.range.start, body_start),
+            cmp::min(req.params.range.end, body_end),
+        );
+        if range.start > range.end {
+            return None;
+        }
+
+        let preamble = Range::new(Position::new(0, 0), table[document.left.parent].start());
+        let usage = Usage::analyze(table, preamble, range);
+
+        let mut edits = Vec::new();
+        for include in table
+            .includes
+            .iter()
+            .filter(|include| include.kind == LatexIncludeKind::Package)
+            .filter(|include| preamble.contains(table[include.parent].end()))
+        {
+            let paths = include.paths(&table);
+            let used_paths: Vec<_> = paths
+                .iter()
+                .map(|path| path.text())
+                .filter(|name| usage.is_package_used(name))
+                .collect();
+
+            if used_paths.len() == paths.len() {
+                continue;
+            }
+
+            if used_paths.is_empty() {
+                edits.push((
+                    line_range(text, table[include.parent].range()),
+                    String::new(),
+                ));
+            } else {
+                let group = table
+                    .extract_group(include.parent, latex::GroupKind::Group, include.arg_index)
+                    .unwrap();
+                edits.push((
+                    table[group].range(),
+                    format!("{{{}}}", used_paths.join(",")),
+                ));
+            }
+        }
+
+        edits.sort_by_key(|(range, _)| range.start);
+        let mut example = String::new();
+        let mut last = preamble.start;
+        for (range, new_text) in edits {
+            example.push_str(&CharStream::extract(text, Range::new(last, range.start)));
+            example.push_str(&new_text);
+            last = range.end;
+        }
+        example.push_str(&CharStream::extract(text, Range::new(last, preamble.end)));
+        example.push_str("\\begin{document}\n");
+        example.push_str(CharStream::extract(text, range).trim());
+        example.push_str("\n\\end{document}\n");
+        Some(example)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Usage<'a> {
+    commands: HashSet<&'a str>,
+    environments: HashSet<&'a str>,
+}
+
+impl<'a> Usage<'a> {
+    fn analyze(table: &'a latex::SymbolTable, preamble: Range, selection: Range) -> Self {
+        let mut usage = Self::default();
+        for cmd in table
+            .commands
+            .iter()
+            .filter_map(|node| table.as_command(*node))
+        {
+            let is_import = table
+                .includes
+                .iter()
+                .any(|include| table[include.parent].start() == cmd.start());
+
+            if selection.contains(cmd.start()) || (preamble.contains(cmd.start()) && !is_import) {
+                usage.commands.insert(&cmd.name.text()[1..]);
+            }
+        }
+
+        for env in &table.environments {
+            if selection.contains(table[env.left.parent].start()) {
+                if let Some(name) = env.left.name(table) {
+                    usage.environments.insert(name.text());
+                }
+            }
+        }
+        usage
+    }
+
+    fn is_package_used(&self, name: &str) -> bool {
+        let component = match COMPONENT_DATABASE.find(&format!("{}.sty", name)) {
+            Some(component) => component,
+            None => return true,
+        };
+
+        let mut components = vec![component];
+        component
+            .references
+            .iter()
+            .filter(|file| file.ends_with(".sty"))
+            .flat_map(|file| COMPONENT_DATABASE.find(file))
+            .for_each(|component| components.push(component));
+
+        components
+            .into_iter()
+            .any(|component| self.is_component_used(component))
+    }
+
+    fn is_component_used(&self, component: &Component) -> bool {
+        component
+            .commands
+            .iter()
+            .any(|cmd| self.commands.contains(cmd.name.as_str()))
+            || component
+                .environments
+                .iter()
+                .any(|env| self.environments.contains(env.as_str()))
+    }
+}
+
+fn line_range(text: &str, range: Range) -> Range {
+    let line = text
+        .lines()
+        .nth(range.start.line as usize)
+        .unwrap_or_default();
+    let line_start = Range::new(Position::new(range.start.line, 0), range.start);
+    let line_end = Position::new(range.end.line, line.chars().count() as u64);
+    if range.start.line == range.end.line
+        && CharStream::extract(text, line_start).trim().is_empty()
+        && CharStream::extract(text, Range::new(range.end, line_end))
+            .trim()
+            .is_empty()
+    {
+        Range::new_simple(range.start.line, 0, range.start.line + 1, 0)
+    } else {
+        range
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feature::FeatureTester;
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let actual_example = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .test_minimal_example(MinimalExampleProvider, Range::new_simple(0, 0, 0, 0))
+            .await;
+
+        assert_eq!(actual_example, None);
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let actual_example = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .test_minimal_example(MinimalExampleProvider, Range::new_simple(0, 0, 0, 0))
+            .await;
+
+        assert_eq!(actual_example, None);
+    }
+
+    #[tokio::test]
+    async fn unused_packages() {
+        let actual_example = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \documentclass{article}
+                        \usepackage{amsmath}
+                        \usepackage{hyperref,xcolor}
+                        \newcommand{\foo}{\textcolor{red}{foo}}
+                        \begin{document}
+                        \section{Foo}
+                        \begin{align}
+                            a = b
+                        \end{align}
+                        \href{https://www.example.com}{Bar}
+                        \end{document}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .test_minimal_example(MinimalExampleProvider, Range::new_simple(6, 0, 8, 11))
+            .await;
+
+        let expected_example = indoc!(
+            r#"
+                \documentclass{article}
+                \usepackage{amsmath}
+                \usepackage{xcolor}
+                \newcommand{\foo}{\textcolor{red}{foo}}
+                \begin{document}
+                \begin{align}
+                    a = b
+                \end{align}
+                \end{document}
+            "#
+        );
+        assert_eq!(actual_example.as_deref(), Some(expected_example));
+    }
+
+    #[tokio::test]
+    async fn unknown_package() {
+        let actual_example = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \documentclass{article}
+                        \usepackage{foo-bar-baz}
+                        \usepackage{amsmath}
+                        \begin{document}
+                        Foo
+                        \end{document}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .test_minimal_example(MinimalExampleProvider, Range::new_simple(4, 0, 4, 3))
+            .await;
+
+        let expected_example = indoc!(
+            r#"
+                \documentclass{article}
+                \usepackage{foo-bar-baz}
+                \begin{document}
+                Foo
+                \end{document}
+            "#
+        );
+        assert_eq!(actual_example.as_deref(), Some(expected_example));
+    }
+}
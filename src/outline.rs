@@ -4,7 +4,11 @@ use crate::{
     syntax::{latex, SyntaxNode},
     workspace::{Document, DocumentContent},
 };
-use std::{borrow::Cow, collections::HashSet, path::Path};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    path::Path,
+};
 use titlecase::titlecase;
 use OutlineContextItem::*;
 
@@ -107,6 +111,54 @@ impl<'a> OutlineSectionFinder<'a> {
     }
 }
 
+pub fn cleveref_capitalization(command_name: &str) -> Option<bool> {
+    match command_name {
+        "cref" | "cref*" | "crefrange" | "crefrange*" | "cpageref" | "cpagerefrange"
+        | "namecref" | "namecrefs" | "lcnamecref" | "lcnamecrefs" => Some(false),
+        "Cref" | "Cref*" | "Crefrange" | "Crefrange*" | "Cpageref" | "Cpagerefrange"
+        | "nameCref" | "nameCrefs" => Some(true),
+        _ => None,
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct CrefNames {
+    names: HashMap<(String, bool), String>,
+}
+
+impl CrefNames {
+    pub fn analyze(view: &DocumentView) -> Self {
+        let mut names = HashMap::new();
+        for doc in &view.related {
+            if let DocumentContent::Latex(table) = &doc.content {
+                for cref_name in &table.cref_names {
+                    if let Some(singular) = cref_name.singular(&table) {
+                        let type_name = cref_name.type_name(&table).text().to_owned();
+                        names.insert((type_name, cref_name.capitalized), singular);
+                    }
+                }
+            }
+        }
+        Self { names }
+    }
+
+    pub fn get(&self, type_name: &str, capitalize: bool) -> Option<String> {
+        if let Some(name) = self.names.get(&(type_name.to_owned(), capitalize)) {
+            return Some(name.clone());
+        }
+
+        let name = self.names.get(&(type_name.to_owned(), !capitalize))?;
+        let mut chars = name.chars();
+        let first = chars.next()?;
+        let result = if capitalize {
+            first.to_uppercase().chain(chars).collect()
+        } else {
+            first.to_lowercase().chain(chars).collect()
+        };
+        Some(result)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum OutlineCaptionKind {
     Figure,
@@ -203,6 +255,15 @@ impl OutlineContext {
         }
     }
 
+    pub fn reference_prefix(&self) -> Option<&'static str> {
+        match &self.item {
+            Caption {
+                kind: Some(kind), ..
+            } => Some(kind.as_str()),
+            _ => None,
+        }
+    }
+
     pub fn detail(&self) -> Option<String> {
         match &self.item {
             Section { .. } | Theorem { .. } | Equation | Item => Some(self.reference()),
@@ -219,6 +280,98 @@ impl OutlineContext {
         }
     }
 
+    pub fn cleveref_type(&self) -> Option<String> {
+        let name = match &self.item {
+            Section { prefix, .. } => prefix.to_lowercase(),
+            Caption {
+                kind: Some(kind), ..
+            } => match kind {
+                OutlineCaptionKind::Figure => "figure",
+                OutlineCaptionKind::Table => "table",
+                OutlineCaptionKind::Listing => "lstlisting",
+                OutlineCaptionKind::Algorithm => "algorithm",
+            }
+            .to_owned(),
+            Caption { kind: None, .. } => return None,
+            Theorem { kind, .. } => kind.to_lowercase(),
+            Equation => "equation".into(),
+            Item => "enumi".into(),
+        };
+        Some(name)
+    }
+
+    pub fn cleveref_detail(&self, capitalize: bool, cref_names: &CrefNames) -> Option<String> {
+        let custom_name = self
+            .cleveref_type()
+            .and_then(|type_name| cref_names.get(&type_name, capitalize));
+
+        let name = match &self.item {
+            Section { prefix, .. } => match prefix.as_ref() {
+                "Part" => "part",
+                "Chapter" => "chapter",
+                "Paragraph" | "Subparagraph" => "paragraph",
+                _ => "section",
+            }
+            .to_owned(),
+            Caption {
+                kind: Some(kind), ..
+            } => match kind {
+                OutlineCaptionKind::Figure => "fig.",
+                OutlineCaptionKind::Table => "table",
+                OutlineCaptionKind::Listing => "listing",
+                OutlineCaptionKind::Algorithm => "algorithm",
+            }
+            .to_owned(),
+            Caption { kind: None, .. } => return None,
+            Theorem { kind, .. } => kind.to_lowercase(),
+            Equation => "eq.".into(),
+            Item => "item".into(),
+        };
+
+        let name = match (custom_name, capitalize, &self.item) {
+            (Some(custom_name), _, _) => custom_name,
+            (None, false, _) => name,
+            (
+                None,
+                true,
+                Caption {
+                    kind: Some(kind), ..
+                },
+            ) => kind.as_str().into(),
+            (None, true, Equation) => "Equation".into(),
+            (None, true, _) => {
+                let mut chars = name.chars();
+                chars
+                    .next()
+                    .map(|c| c.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
+        };
+
+        let result = match (&self.number, &self.item) {
+            (Some(number), Equation) => format!("{} ({})", name, number),
+            (Some(number), _) => format!("{} {}", name, number),
+            (None, _) => name,
+        };
+
+        match &self.item {
+            Section { text, .. } => Some(format!("{} ({})", result, text)),
+            Theorem {
+                description: Some(description),
+                ..
+            } => Some(format!("{} ({})", result, description)),
+            _ => Some(result),
+        }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        match &self.item {
+            Section { text, .. } | Caption { text, .. } => Some(text),
+            Theorem { description, .. } => description.as_deref(),
+            Equation | Item => None,
+        }
+    }
+
     pub fn documentation(&self) -> MarkupContent {
         MarkupContent {
             kind: MarkupKind::PlainText,
@@ -244,6 +397,10 @@ impl OutlineContext {
         label: latex::Label,
     ) -> Option<Self> {
         let label_range = table[label.parent].range();
+        if let Some(caption) = Self::find_caption_box(view, table, label) {
+            return Some(caption);
+        }
+
         let caption_env = table
             .environments
             .iter()
@@ -277,6 +434,43 @@ impl OutlineContext {
         })
     }
 
+    fn find_caption_box(
+        view: &DocumentView,
+        table: &latex::SymbolTable,
+        label: latex::Label,
+    ) -> Option<Self> {
+        let label_range = table[label.parent].range();
+        let caption_box = table.commands.iter().copied().find(|node| {
+            table
+                .as_command(*node)
+                .filter(|cmd| cmd.name.text() == "\\subcaptionbox")
+                .filter(|cmd| cmd.range().contains(label_range.start))
+                .is_some()
+        })?;
+
+        let caption_text = table
+            .print_group_content(caption_box, latex::GroupKind::Group, 0)?
+            .replace(&table.print(label.parent), "")
+            .trim()
+            .to_owned();
+        let caption_kind = table
+            .environments
+            .iter()
+            .find(|env| table.is_direct_child(**env, table[caption_box].start()))
+            .and_then(|env| env.left.name(&table))
+            .map(latex::Token::text)
+            .and_then(OutlineCaptionKind::parse);
+
+        Some(Self {
+            range: table[caption_box].range(),
+            number: Self::find_number(view, table, label),
+            item: Caption {
+                kind: caption_kind,
+                text: caption_text,
+            },
+        })
+    }
+
     fn find_theorem(
         view: &DocumentView,
         main_table: &latex::SymbolTable,
@@ -423,15 +617,22 @@ impl OutlineContext {
             return None;
         }
 
-        for doc in &view.related {
-            if let DocumentContent::Latex(table) = &doc.content {
-                for numbering in &table.label_numberings {
-                    if numbering.name(&table).text() == label_names[0].text() {
-                        return Some(numbering.number.clone());
-                    }
+        find_label_numbering(view, label_names[0].text()).map(|numbering| numbering.number.clone())
+    }
+}
+
+pub fn find_label_numbering<'a>(
+    view: &'a DocumentView,
+    name: &str,
+) -> Option<&'a latex::LabelNumbering> {
+    for doc in &view.related {
+        if let DocumentContent::Latex(table) = &doc.content {
+            for numbering in &table.label_numberings {
+                if numbering.name(&table).text() == name {
+                    return Some(numbering);
                 }
             }
         }
-        None
     }
+    None
 }
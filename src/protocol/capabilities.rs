@@ -1,4 +1,5 @@
-use lsp_types::{ClientCapabilities, MarkupKind};
+use lsp_types::{ClientCapabilities, CompletionItemTag, MarkupKind};
+use serde_json::Value;
 
 pub trait ClientCapabilitiesExt {
     fn has_definition_link_support(&self) -> bool;
@@ -12,6 +13,8 @@ pub trait ClientCapabilitiesExt {
     fn has_pull_configuration_support(&self) -> bool;
 
     fn has_push_configuration_support(&self) -> bool;
+
+    fn has_completion_deprecated_tag_support(&self) -> bool;
 }
 
 impl ClientCapabilitiesExt for ClientCapabilities {
@@ -55,6 +58,34 @@ impl ClientCapabilitiesExt for ClientCapabilities {
             .and_then(|cap| cap.dynamic_registration)
             == Some(true)
     }
+
+    fn has_completion_deprecated_tag_support(&self) -> bool {
+        self.text_document
+            .as_ref()
+            .and_then(|cap| cap.completion.as_ref())
+            .and_then(|cap| cap.completion_item.as_ref())
+            .and_then(|cap| cap.tag_support.as_ref())
+            .filter(|cap| cap.value_set.contains(&CompletionItemTag::Deprecated))
+            .is_some()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct ProposedClientCapabilities {
+    pub will_rename_files: bool,
+    pub pull_diagnostics: bool,
+}
+
+impl ProposedClientCapabilities {
+    pub fn parse(capabilities: &Value) -> Self {
+        let is_enabled =
+            |pointer| capabilities.pointer(pointer).and_then(Value::as_bool) == Some(true);
+        Self {
+            will_rename_files: is_enabled("/workspace/fileOperations/dynamicRegistration")
+                && is_enabled("/workspace/fileOperations/willRename"),
+            pull_diagnostics: is_enabled("/textDocument/diagnostic/dynamicRegistration"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -142,4 +173,59 @@ mod tests {
         let capabilities = ClientCapabilities::default();
         assert!(!capabilities.has_hover_markdown_support());
     }
+
+    #[test]
+    fn has_completion_deprecated_tag_support_true() {
+        let capabilities = ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                completion: Some(CompletionCapability {
+                    completion_item: Some(CompletionItemCapability {
+                        tag_support: Some(TagSupport {
+                            value_set: vec![CompletionItemTag::Deprecated],
+                        }),
+                        ..CompletionItemCapability::default()
+                    }),
+                    ..CompletionCapability::default()
+                }),
+                ..TextDocumentClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        assert!(capabilities.has_completion_deprecated_tag_support());
+    }
+
+    #[test]
+    fn has_completion_deprecated_tag_support_false() {
+        let capabilities = ClientCapabilities::default();
+        assert!(!capabilities.has_completion_deprecated_tag_support());
+    }
+
+    #[test]
+    fn proposed_will_rename_files_true() {
+        let capabilities = serde_json::json!({
+            "workspace": {
+                "fileOperations": {
+                    "dynamicRegistration": true,
+                    "willRename": true
+                }
+            }
+        });
+        assert!(ProposedClientCapabilities::parse(&capabilities).will_rename_files);
+    }
+
+    #[test]
+    fn proposed_pull_diagnostics() {
+        let capabilities = serde_json::json!({
+            "textDocument": { "diagnostic": { "dynamicRegistration": true } }
+        });
+        let capabilities = ProposedClientCapabilities::parse(&capabilities);
+        assert!(capabilities.pull_diagnostics);
+        assert!(!capabilities.will_rename_files);
+    }
+
+    #[test]
+    fn proposed_will_rename_files_false() {
+        let capabilities = serde_json::json!({ "workspace": {} });
+        assert!(!ProposedClientCapabilities::parse(&capabilities).will_rename_files);
+    }
 }
@@ -17,7 +17,7 @@ mod range;
 mod uri;
 
 pub use self::{
-    capabilities::ClientCapabilitiesExt,
+    capabilities::{ClientCapabilitiesExt, ProposedClientCapabilities},
     edit::*,
     options::*,
     range::RangeExt,
@@ -53,6 +53,7 @@ pub struct ForwardSearchResult {
 #[serde(rename_all = "camelCase")]
 pub struct BuildParams {
     pub text_document: TextDocumentIdentifier,
+    pub timeout: Option<u64>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize_repr, Deserialize_repr)]
@@ -62,6 +63,7 @@ pub enum BuildStatus {
     Error = 1,
     Failure = 2,
     Cancelled = 3,
+    Timeout = 4,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -69,3 +71,81 @@ pub enum BuildStatus {
 pub struct BuildResult {
     pub status: BuildStatus,
 }
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutlineParams {
+    pub text_document: TextDocumentIdentifier,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutlineNode {
+    pub kind: String,
+    pub name: String,
+    pub label: Option<String>,
+    pub caption: Option<String>,
+    pub entry_type: Option<String>,
+    pub range: Range,
+    pub selection_range: Range,
+    pub children: Vec<OutlineNode>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MinimalExampleParams {
+    pub text_document: TextDocumentIdentifier,
+    pub range: Range,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TodoItem {
+    pub location: Location,
+    pub marker: String,
+    pub text: String,
+    pub color: Option<String>,
+    pub inline: bool,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureTiming {
+    pub feature: String,
+    pub count: u64,
+    pub total_milliseconds: f64,
+    pub max_milliseconds: f64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameFilesParams {
+    pub files: Vec<FileRename>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileRename {
+    pub old_uri: String,
+    pub new_uri: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentDiagnosticParams {
+    pub text_document: TextDocumentIdentifier,
+    pub identifier: Option<String>,
+    pub previous_result_id: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum DocumentDiagnosticReport {
+    Full(FullDocumentDiagnosticReport),
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FullDocumentDiagnosticReport {
+    pub items: Vec<Diagnostic>,
+}
@@ -1,5 +1,8 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use log::warn;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use thiserror::Error;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -14,6 +17,32 @@ impl Default for BibtexFormatter {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LabelSortOrder {
+    Relevance,
+    Document,
+}
+
+impl Default for LabelSortOrder {
+    fn default() -> Self {
+        Self::Relevance
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiagnosticsScope {
+    Open,
+    Workspace,
+}
+
+impl Default for DiagnosticsScope {
+    fn default() -> Self {
+        Self::Open
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BibtexFormattingOptions {
@@ -32,6 +61,8 @@ pub struct LatexForwardSearchOptions {
 pub struct LatexLintOptions {
     pub on_change: Option<bool>,
     pub on_save: Option<bool>,
+    pub deprecated_commands: Option<Vec<String>>,
+    pub list_environments: Option<Vec<String>>,
 }
 
 impl LatexLintOptions {
@@ -81,13 +112,158 @@ impl LatexBuildOptions {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CitationCommand {
+    pub name: String,
+    #[serde(default)]
+    pub index: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexCompletionOptions {
+    pub sort_labels_by_proximity: Option<bool>,
+    pub insert_reference_prefix: Option<bool>,
+    pub trigger_on_backslash: Option<bool>,
+    pub group_labels_by_file: Option<bool>,
+    pub citation_commands: Option<Vec<CitationCommand>>,
+    pub reference_commands: Option<Vec<String>>,
+    pub label_sort_order: Option<LabelSortOrder>,
+    pub snippets: Option<Vec<LatexSnippet>>,
+}
+
+impl LatexCompletionOptions {
+    pub fn sort_labels_by_proximity(&self) -> bool {
+        self.sort_labels_by_proximity.unwrap_or(false)
+    }
+
+    pub fn insert_reference_prefix(&self) -> bool {
+        self.insert_reference_prefix.unwrap_or(false)
+    }
+
+    pub fn trigger_on_backslash(&self) -> bool {
+        self.trigger_on_backslash.unwrap_or(true)
+    }
+
+    pub fn group_labels_by_file(&self) -> bool {
+        self.group_labels_by_file.unwrap_or(false)
+    }
+
+    pub fn citation_commands(&self) -> &[CitationCommand] {
+        self.citation_commands.as_deref().unwrap_or_default()
+    }
+
+    pub fn reference_commands(&self) -> &[String] {
+        self.reference_commands.as_deref().unwrap_or_default()
+    }
+
+    pub fn label_sort_order(&self) -> LabelSortOrder {
+        self.label_sort_order.unwrap_or_default()
+    }
+
+    pub fn snippets(&self) -> &[LatexSnippet] {
+        self.snippets.as_deref().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexSnippet {
+    pub label: String,
+    pub body: String,
+    pub commands: Option<Vec<String>>,
+    pub environments: Option<Vec<String>>,
+}
+
+impl LatexSnippet {
+    pub fn commands(&self) -> &[String] {
+        self.commands.as_deref().unwrap_or_default()
+    }
+
+    pub fn environments(&self) -> &[String] {
+        self.environments.as_deref().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexFeatureOptions {
+    pub completion: Option<bool>,
+    pub hover: Option<bool>,
+    pub diagnostics: Option<bool>,
+}
+
+impl LatexFeatureOptions {
+    pub fn completion(&self) -> bool {
+        self.completion.unwrap_or(true)
+    }
+
+    pub fn hover(&self) -> bool {
+        self.hover.unwrap_or(true)
+    }
+
+    pub fn diagnostics(&self) -> bool {
+        self.diagnostics.unwrap_or(true)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LatexOptions {
     pub forward_search: Option<LatexForwardSearchOptions>,
     pub lint: Option<LatexLintOptions>,
     pub build: Option<LatexBuildOptions>,
+    pub completion: Option<LatexCompletionOptions>,
+    pub extensions: Option<Vec<String>>,
     pub root_directory: Option<PathBuf>,
+    pub root_file: Option<PathBuf>,
+    pub max_file_size: Option<usize>,
+    pub max_file_lines: Option<usize>,
+    pub exclude_directories: Option<Vec<String>>,
+    pub surround_selection: Option<bool>,
+    pub feature_timings: Option<bool>,
+    pub diagnostics_scope: Option<DiagnosticsScope>,
+    pub features: Option<LatexFeatureOptions>,
+}
+
+impl LatexOptions {
+    pub fn extensions(&self) -> &[String] {
+        self.extensions.as_deref().unwrap_or_default()
+    }
+
+    pub fn max_file_size(&self) -> usize {
+        self.max_file_size.unwrap_or(5_000_000)
+    }
+
+    pub fn max_file_lines(&self) -> usize {
+        self.max_file_lines.unwrap_or(100_000)
+    }
+
+    pub fn surround_selection(&self) -> bool {
+        self.surround_selection.unwrap_or(false)
+    }
+
+    pub fn feature_timings(&self) -> bool {
+        self.feature_timings.unwrap_or(false)
+    }
+
+    pub fn diagnostics_scope(&self) -> DiagnosticsScope {
+        self.diagnostics_scope.unwrap_or_default()
+    }
+
+    pub fn exclude_directories(&self) -> GlobSet {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in self.exclude_directories.iter().flatten() {
+            match Glob::new(pattern) {
+                Ok(glob) => {
+                    builder.add(glob);
+                }
+                Err(why) => warn!("Invalid exclude pattern {}: {}", pattern, why),
+            }
+        }
+        builder.build().unwrap_or_else(|_| GlobSet::empty())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
@@ -102,3 +278,259 @@ pub struct Options {
     pub latex: Option<LatexOptions>,
     pub bibtex: Option<BibtexOptions>,
 }
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FeatureConfig {
+    pub completion: bool,
+    pub hover: bool,
+    pub diagnostics: bool,
+}
+
+impl Default for FeatureConfig {
+    fn default() -> Self {
+        Self {
+            completion: true,
+            hover: true,
+            diagnostics: true,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CompletionConfig {
+    pub sort_labels_by_proximity: bool,
+    pub group_labels_by_file: bool,
+    pub label_sort_order: LabelSortOrder,
+    pub insert_reference_prefix: bool,
+    pub trigger_on_backslash: bool,
+    pub snippets: Vec<LatexSnippet>,
+}
+
+impl Default for CompletionConfig {
+    fn default() -> Self {
+        Self {
+            sort_labels_by_proximity: false,
+            group_labels_by_file: false,
+            label_sort_order: LabelSortOrder::default(),
+            insert_reference_prefix: false,
+            trigger_on_backslash: true,
+            snippets: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Config {
+    pub root_file: Option<PathBuf>,
+    pub citation_commands: Vec<CitationCommand>,
+    pub reference_commands: Vec<String>,
+    pub completion: CompletionConfig,
+    pub features: FeatureConfig,
+}
+
+impl Config {
+    pub fn new(options: &Options) -> Self {
+        let latex = options.latex.clone().unwrap_or_default();
+        let completion = latex.completion.unwrap_or_default();
+        let features = latex.features.unwrap_or_default();
+        let citation_commands = completion
+            .citation_commands()
+            .iter()
+            .map(|cmd| CitationCommand {
+                name: cmd.name.trim_start_matches('\\').to_owned(),
+                index: cmd.index,
+            })
+            .collect();
+        let reference_commands = completion
+            .reference_commands()
+            .iter()
+            .map(|name| name.trim_start_matches('\\').to_owned())
+            .collect();
+
+        Self {
+            root_file: latex.root_file,
+            citation_commands,
+            reference_commands,
+            completion: CompletionConfig {
+                sort_labels_by_proximity: completion.sort_labels_by_proximity(),
+                group_labels_by_file: completion.group_labels_by_file(),
+                label_sort_order: completion.label_sort_order(),
+                insert_reference_prefix: completion.insert_reference_prefix(),
+                trigger_on_backslash: completion.trigger_on_backslash(),
+                snippets: completion.snippets().to_vec(),
+            },
+            features: FeatureConfig {
+                completion: features.completion(),
+                hover: features.hover(),
+                diagnostics: features.diagnostics(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum OptionsError {
+    #[error("latex.build.executable must not be empty")]
+    EmptyBuildExecutable,
+    #[error("latex.forwardSearch.executable must not be empty")]
+    EmptyForwardSearchExecutable,
+    #[error("latex.forwardSearch.args is set but latex.forwardSearch.executable is missing")]
+    MissingForwardSearchExecutable,
+    #[error("latex.maxFileSize must be greater than zero")]
+    ZeroMaxFileSize,
+    #[error("latex.maxFileLines must be greater than zero")]
+    ZeroMaxFileLines,
+    #[error("latex.excludeDirectories contains an invalid pattern: {0}")]
+    InvalidExcludePattern(String),
+    #[error("latex.rootFile must be a .tex file: {0}")]
+    InvalidRootFile(String),
+}
+
+impl Options {
+    pub fn validate(&self) -> Vec<OptionsError> {
+        let mut errors = Vec::new();
+        if let Some(latex) = &self.latex {
+            let build_executable = latex
+                .build
+                .as_ref()
+                .and_then(|opts| opts.executable.as_ref());
+            if build_executable.map_or(false, |exe| exe.trim().is_empty()) {
+                errors.push(OptionsError::EmptyBuildExecutable);
+            }
+
+            if let Some(forward_search) = &latex.forward_search {
+                match &forward_search.executable {
+                    Some(exe) if exe.trim().is_empty() => {
+                        errors.push(OptionsError::EmptyForwardSearchExecutable)
+                    }
+                    None if forward_search.args.is_some() => {
+                        errors.push(OptionsError::MissingForwardSearchExecutable)
+                    }
+                    _ => (),
+                }
+            }
+
+            if latex.max_file_size == Some(0) {
+                errors.push(OptionsError::ZeroMaxFileSize);
+            }
+
+            if latex.max_file_lines == Some(0) {
+                errors.push(OptionsError::ZeroMaxFileLines);
+            }
+
+            for pattern in latex.exclude_directories.iter().flatten() {
+                if Glob::new(pattern).is_err() {
+                    errors.push(OptionsError::InvalidExcludePattern(pattern.clone()));
+                }
+            }
+
+            if let Some(root_file) = &latex.root_file {
+                if root_file.extension().map_or(true, |ext| ext != "tex") {
+                    let root_file = root_file.to_string_lossy().into_owned();
+                    errors.push(OptionsError::InvalidRootFile(root_file));
+                }
+            }
+        }
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_default() {
+        assert!(Options::default().validate().is_empty());
+    }
+
+    #[test]
+    fn diagnostics_scope_default() {
+        assert_eq!(
+            LatexOptions::default().diagnostics_scope(),
+            DiagnosticsScope::Open
+        );
+    }
+
+    #[test]
+    fn validate_invalid() {
+        let options = Options {
+            latex: Some(LatexOptions {
+                build: Some(LatexBuildOptions {
+                    executable: Some("".into()),
+                    ..LatexBuildOptions::default()
+                }),
+                forward_search: Some(LatexForwardSearchOptions {
+                    executable: None,
+                    args: Some(vec!["%f".into()]),
+                }),
+                max_file_lines: Some(0),
+                exclude_directories: Some(vec!["build".into(), "[".into()]),
+                root_file: Some("main.pdf".into()),
+                ..LatexOptions::default()
+            }),
+            ..Options::default()
+        };
+
+        assert_eq!(
+            options.validate(),
+            vec![
+                OptionsError::EmptyBuildExecutable,
+                OptionsError::MissingForwardSearchExecutable,
+                OptionsError::ZeroMaxFileLines,
+                OptionsError::InvalidExcludePattern("[".into()),
+                OptionsError::InvalidRootFile("main.pdf".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn config_default() {
+        assert_eq!(Config::new(&Options::default()), Config::default());
+    }
+
+    #[test]
+    fn config_custom() {
+        let options = Options {
+            latex: Some(LatexOptions {
+                root_file: Some("main.tex".into()),
+                completion: Some(LatexCompletionOptions {
+                    citation_commands: Some(vec![CitationCommand {
+                        name: "\\foocite".into(),
+                        index: 1,
+                    }]),
+                    reference_commands: Some(vec!["fooref".into()]),
+                    trigger_on_backslash: Some(false),
+                    ..LatexCompletionOptions::default()
+                }),
+                features: Some(LatexFeatureOptions {
+                    hover: Some(false),
+                    ..LatexFeatureOptions::default()
+                }),
+                ..LatexOptions::default()
+            }),
+            ..Options::default()
+        };
+
+        assert_eq!(
+            Config::new(&options),
+            Config {
+                root_file: Some("main.tex".into()),
+                citation_commands: vec![CitationCommand {
+                    name: "foocite".into(),
+                    index: 1,
+                }],
+                reference_commands: vec!["fooref".into()],
+                completion: CompletionConfig {
+                    trigger_on_backslash: false,
+                    ..CompletionConfig::default()
+                },
+                features: FeatureConfig {
+                    completion: true,
+                    hover: false,
+                    diagnostics: true,
+                },
+            }
+        );
+    }
+}
@@ -28,7 +28,13 @@ impl Uri {
     }
 
     pub fn from_file_path<P: AsRef<Path>>(path: P) -> Result<Self, ()> {
-        Url::from_file_path(path).map(|url| url.into())
+        Url::from_file_path(path).map(normalize)
+    }
+
+    pub fn file_exists(&self) -> bool {
+        self.to_file_path()
+            .map(|path| path.is_file())
+            .unwrap_or_default()
     }
 }
 
@@ -80,6 +86,11 @@ impl fmt::Display for Uri {
     }
 }
 
+fn normalize(url: Url) -> Uri {
+    // Reparsing the URL removes `.` and `..` segments from the path.
+    Url::parse(url.as_str()).unwrap_or(url).into()
+}
+
 pub trait AsUri {
     fn as_uri(&self) -> Uri;
 }
@@ -95,3 +106,17 @@ impl AsUri for TextDocumentPositionParams {
         self.text_document.as_uri()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn from_file_path_parent_directory() {
+        let dir = env::temp_dir();
+        let actual_uri = Uri::from_file_path(dir.join("foo").join("..").join("bar.tex")).unwrap();
+        let expected_uri = Uri::from_file_path(dir.join("bar.tex")).unwrap();
+        assert_eq!(actual_uri, expected_uri);
+    }
+}
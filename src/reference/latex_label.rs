@@ -1,6 +1,6 @@
 use crate::{
     feature::{FeatureProvider, FeatureRequest},
-    protocol::{Location, RangeExt, ReferenceParams},
+    protocol::{Location, ReferenceParams},
     syntax::{latex, LatexLabelKind, SyntaxNode},
     workspace::DocumentContent,
 };
@@ -20,12 +20,10 @@ impl FeatureProvider for LatexLabelReferenceProvider {
             for doc in req.related() {
                 if let DocumentContent::Latex(table) = &doc.content {
                     table
-                        .labels
-                        .iter()
-                        .filter(|label| Self::is_included(req, label))
-                        .flat_map(|label| label.names(&table))
+                        .label_names()
+                        .filter(|label| Self::is_included(req, label.kind()))
                         .filter(|label| label.text() == def)
-                        .map(|label| Location::new(doc.uri.clone().into(), label.range()))
+                        .map(|label| Location::new(doc.uri.clone().into(), label.name.range()))
                         .for_each(|location| refs.push(location));
                 }
             }
@@ -38,19 +36,14 @@ impl LatexLabelReferenceProvider {
     fn find_name(req: &FeatureRequest<ReferenceParams>) -> Option<&str> {
         let pos = req.params.text_document_position.position;
         if let DocumentContent::Latex(table) = &req.current().content {
-            table
-                .labels
-                .iter()
-                .flat_map(|label| label.names(&table))
-                .find(|label| label.range().contains(pos))
-                .map(latex::Token::text)
+            table.find_label_name(pos).map(latex::LabelName::text)
         } else {
             None
         }
     }
 
-    fn is_included(req: &FeatureRequest<ReferenceParams>, label: &latex::Label) -> bool {
-        match label.kind {
+    fn is_included(req: &FeatureRequest<ReferenceParams>, kind: LatexLabelKind) -> bool {
+        match kind {
             LatexLabelKind::Reference(_) => true,
             LatexLabelKind::Definition => req.params.context.include_declaration,
         }
@@ -60,7 +53,10 @@ impl LatexLabelReferenceProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{feature::FeatureTester, protocol::Range};
+    use crate::{
+        feature::FeatureTester,
+        protocol::{Range, RangeExt},
+    };
     use indoc::indoc;
 
     #[tokio::test]
@@ -151,6 +147,38 @@ mod tests {
         assert_eq!(actual_refs, expected_refs);
     }
 
+    #[tokio::test]
+    async fn cleveref_name_reference() {
+        let actual_refs = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \label{foo}
+                        \namecref{foo}
+                        \cpageref{foo}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(0, 8)
+            .test_reference(LatexLabelReferenceProvider)
+            .await;
+
+        let expected_refs = vec![
+            Location::new(
+                FeatureTester::uri("main.tex").into(),
+                Range::new_simple(1, 10, 1, 13),
+            ),
+            Location::new(
+                FeatureTester::uri("main.tex").into(),
+                Range::new_simple(2, 10, 2, 13),
+            ),
+        ];
+
+        assert_eq!(actual_refs, expected_refs);
+    }
+
     #[tokio::test]
     async fn reference_include_declaration() {
         let actual_refs = FeatureTester::new()
@@ -1,8 +1,9 @@
+use super::merge_edits;
 use crate::{
     feature::{FeatureProvider, FeatureRequest},
     protocol::{
-        Position, Range, RangeExt, RenameParams, TextDocumentPositionParams, TextEdit,
-        WorkspaceEdit,
+        Position, PrepareRenameResponse, RangeExt, RenameParams, TextDocumentPositionParams,
+        TextEdit, WorkspaceEdit,
     },
     syntax::{Span, SyntaxNode},
     workspace::DocumentContent,
@@ -16,10 +17,14 @@ pub struct BibtexEntryPrepareRenameProvider;
 #[async_trait]
 impl FeatureProvider for BibtexEntryPrepareRenameProvider {
     type Params = TextDocumentPositionParams;
-    type Output = Option<Range>;
+    type Output = Option<PrepareRenameResponse>;
 
     async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
-        find_key(&req.current().content, req.params.position).map(Span::range)
+        let key = find_key(&req.current().content, req.params.position)?;
+        Some(PrepareRenameResponse::RangeWithPlaceholder {
+            range: key.range(),
+            placeholder: key.text.clone(),
+        })
     }
 }
 
@@ -38,6 +43,10 @@ impl FeatureProvider for BibtexEntryRenameProvider {
         )?;
         let mut changes = HashMap::new();
         for doc in req.related() {
+            if req.cancellation.is_cancelled() {
+                return None;
+            }
+
             let edits = match &doc.content {
                 DocumentContent::Latex(table) => table
                     .citations
@@ -54,7 +63,7 @@ impl FeatureProvider for BibtexEntryRenameProvider {
                     .map(|entry_key| TextEdit::new(entry_key.range(), req.params.new_name.clone()))
                     .collect(),
             };
-            changes.insert(doc.uri.clone().into(), edits);
+            merge_edits(&mut changes, doc.uri.clone().into(), edits);
         }
         Some(WorkspaceEdit::new(changes))
     }
@@ -80,7 +89,10 @@ fn find_key(content: &DocumentContent, pos: Position) -> Option<&Span> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{feature::FeatureTester, protocol::RangeExt};
+    use crate::{
+        feature::FeatureTester,
+        protocol::{Range, RangeExt},
+    };
     use indoc::indoc;
 
     #[tokio::test]
@@ -0,0 +1,266 @@
+use super::merge_edits;
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{
+        Position, PrepareRenameResponse, RangeExt, RenameParams, TextDocumentPositionParams,
+        TextEdit, WorkspaceEdit,
+    },
+    syntax::{
+        bibtex::{self, Visitor},
+        SyntaxNode,
+    },
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+use petgraph::graph::NodeIndex;
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct BibtexStringPrepareRenameProvider;
+
+#[async_trait]
+impl FeatureProvider for BibtexStringPrepareRenameProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Option<PrepareRenameResponse>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let name = find_name(&req.current().content, req.params.position)?;
+        Some(PrepareRenameResponse::RangeWithPlaceholder {
+            range: name.range(),
+            placeholder: name.text().into(),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct BibtexStringRenameProvider;
+
+#[async_trait]
+impl FeatureProvider for BibtexStringRenameProvider {
+    type Params = RenameParams;
+    type Output = Option<WorkspaceEdit>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let name = find_name(
+            &req.current().content,
+            req.params.text_document_position.position,
+        )?;
+        let mut changes = HashMap::new();
+        for doc in req.related() {
+            if req.cancellation.is_cancelled() {
+                return None;
+            }
+
+            if let DocumentContent::Bibtex(tree) = &doc.content {
+                let mut visitor = BibtexStringVisitor::default();
+                visitor.visit(tree, tree.root);
+                let edits = visitor
+                    .names
+                    .into_iter()
+                    .filter(|string| string.text() == name.text())
+                    .map(|string| TextEdit::new(string.range(), req.params.new_name.clone()))
+                    .collect();
+                merge_edits(&mut changes, doc.uri.clone().into(), edits);
+            }
+        }
+        Some(WorkspaceEdit::new(changes))
+    }
+}
+
+fn find_name(content: &DocumentContent, pos: Position) -> Option<&bibtex::Token> {
+    if let DocumentContent::Bibtex(tree) = content {
+        let mut nodes = tree.find(pos);
+        nodes.reverse();
+        let node0 = &tree.graph[*nodes.get(0)?];
+        let node1 = nodes.get(1).map(|node| &tree.graph[*node]);
+        match (node0, node1) {
+            (bibtex::Node::Word(word), Some(bibtex::Node::Field(_)))
+            | (bibtex::Node::Word(word), Some(bibtex::Node::Concat(_)))
+                if is_reference(&word.token) =>
+            {
+                Some(&word.token)
+            }
+            (bibtex::Node::String(string), _) => string
+                .name
+                .as_ref()
+                .filter(|name| name.range().contains(pos)),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+fn is_reference(word: &bibtex::Token) -> bool {
+    !word.text().chars().all(|c| c.is_ascii_digit())
+}
+
+#[derive(Debug, Default)]
+struct BibtexStringVisitor<'a> {
+    names: Vec<&'a bibtex::Token>,
+}
+
+impl<'a> bibtex::Visitor<'a> for BibtexStringVisitor<'a> {
+    fn visit(&mut self, tree: &'a bibtex::Tree, node: NodeIndex) {
+        match &tree.graph[node] {
+            bibtex::Node::String(string) => {
+                if let Some(name) = &string.name {
+                    self.names.push(name);
+                }
+            }
+            bibtex::Node::Field(_) | bibtex::Node::Concat(_) => {
+                for word in tree
+                    .children(node)
+                    .filter_map(|child| tree.as_word(child))
+                    .filter(|word| is_reference(&word.token))
+                {
+                    self.names.push(&word.token);
+                }
+            }
+            bibtex::Node::Root(_)
+            | bibtex::Node::Comment(_)
+            | bibtex::Node::Preamble(_)
+            | bibtex::Node::Entry(_)
+            | bibtex::Node::Word(_)
+            | bibtex::Node::Command(_)
+            | bibtex::Node::QuotedContent(_)
+            | bibtex::Node::BracedContent(_) => (),
+        }
+        tree.walk(self, node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::Range};
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn definition() {
+        let actual_edit = FeatureTester::new()
+            .file(
+                "main.bib",
+                indoc!(
+                    r#"
+                        @string{foo = {Foo}}
+                        @article{bar, author = foo # " and " # foo}
+                    "#
+                ),
+            )
+            .main("main.bib")
+            .position(0, 9)
+            .new_name("qux")
+            .test_rename(BibtexStringRenameProvider)
+            .await
+            .unwrap();
+
+        let mut expected_changes = HashMap::new();
+        expected_changes.insert(
+            FeatureTester::uri("main.bib").into(),
+            vec![
+                TextEdit::new(Range::new_simple(0, 8, 0, 11), "qux".into()),
+                TextEdit::new(Range::new_simple(1, 23, 1, 26), "qux".into()),
+                TextEdit::new(Range::new_simple(1, 39, 1, 42), "qux".into()),
+            ],
+        );
+        let expected_edit = WorkspaceEdit::new(expected_changes);
+
+        assert_eq!(actual_edit, expected_edit);
+    }
+
+    #[tokio::test]
+    async fn multiple_files() {
+        let actual_edit = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \addbibresource{strings.bib}
+                        \addbibresource{main.bib}
+                    "#
+                ),
+            )
+            .file("strings.bib", r#"@string{foo = {Foo}}"#)
+            .file("main.bib", r#"@article{bar, author = foo}"#)
+            .main("main.bib")
+            .position(0, 24)
+            .new_name("qux")
+            .test_rename(BibtexStringRenameProvider)
+            .await
+            .unwrap();
+
+        let mut expected_changes = HashMap::new();
+        expected_changes.insert(
+            FeatureTester::uri("strings.bib").into(),
+            vec![TextEdit::new(Range::new_simple(0, 8, 0, 11), "qux".into())],
+        );
+        expected_changes.insert(
+            FeatureTester::uri("main.bib").into(),
+            vec![TextEdit::new(Range::new_simple(0, 23, 0, 26), "qux".into())],
+        );
+        let expected_edit = WorkspaceEdit::new(expected_changes);
+
+        assert_eq!(actual_edit, expected_edit);
+    }
+
+    #[tokio::test]
+    async fn numeric_value() {
+        let actual_edit = FeatureTester::new()
+            .file(
+                "main.bib",
+                indoc!(
+                    r#"
+                        @string{foo = {Foo}}
+                        @article{bar, year = 2020}
+                    "#
+                ),
+            )
+            .main("main.bib")
+            .position(1, 23)
+            .new_name("qux")
+            .test_rename(BibtexStringRenameProvider)
+            .await;
+
+        assert_eq!(actual_edit, None);
+    }
+
+    #[tokio::test]
+    async fn field_name() {
+        let actual_edit = FeatureTester::new()
+            .file("main.bib", r#"@article{bar, author = foo}"#)
+            .main("main.bib")
+            .position(0, 16)
+            .new_name("qux")
+            .test_rename(BibtexStringRenameProvider)
+            .await;
+
+        assert_eq!(actual_edit, None);
+    }
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let actual_edit = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .new_name("")
+            .test_rename(BibtexStringRenameProvider)
+            .await;
+
+        assert_eq!(actual_edit, None);
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let actual_edit = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .position(0, 0)
+            .new_name("")
+            .test_rename(BibtexStringRenameProvider)
+            .await;
+
+        assert_eq!(actual_edit, None);
+    }
+}
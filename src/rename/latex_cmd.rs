@@ -1,7 +1,9 @@
+use super::merge_edits;
 use crate::{
     feature::{FeatureProvider, FeatureRequest},
     protocol::{
-        Position, Range, RenameParams, TextDocumentPositionParams, TextEdit, WorkspaceEdit,
+        Position, PrepareRenameResponse, RenameParams, TextDocumentPositionParams, TextEdit,
+        WorkspaceEdit,
     },
     syntax::{latex, SyntaxNode},
     workspace::DocumentContent,
@@ -15,11 +17,15 @@ pub struct LatexCommandPrepareRenameProvider;
 #[async_trait]
 impl FeatureProvider for LatexCommandPrepareRenameProvider {
     type Params = TextDocumentPositionParams;
-    type Output = Option<Range>;
+    type Output = Option<PrepareRenameResponse>;
 
     async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
         let pos = req.params.position;
-        find_command(&req.current().content, pos).map(SyntaxNode::range)
+        let cmd = find_command(&req.current().content, pos)?;
+        Some(PrepareRenameResponse::RangeWithPlaceholder {
+            range: cmd.range(),
+            placeholder: cmd.name.text().into(),
+        })
     }
 }
 
@@ -36,6 +42,10 @@ impl FeatureProvider for LatexCommandRenameProvider {
         let cmd_name = find_command(&req.current().content, pos)?.name.text();
         let mut changes = HashMap::new();
         for doc in req.related() {
+            if req.cancellation.is_cancelled() {
+                return None;
+            }
+
             if let DocumentContent::Latex(table) = &doc.content {
                 let edits = table
                     .commands
@@ -46,7 +56,7 @@ impl FeatureProvider for LatexCommandRenameProvider {
                         TextEdit::new(cmd.name.range(), format!("\\{}", req.params.new_name))
                     })
                     .collect();
-                changes.insert(doc.uri.clone().into(), edits);
+                merge_edits(&mut changes, doc.uri.clone().into(), edits);
             }
         }
         Some(WorkspaceEdit::new(changes))
@@ -64,7 +74,10 @@ fn find_command(content: &DocumentContent, pos: Position) -> Option<&latex::Comm
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{feature::FeatureTester, protocol::RangeExt};
+    use crate::{
+        feature::FeatureTester,
+        protocol::{Range, RangeExt},
+    };
     use indoc::indoc;
 
     #[tokio::test]
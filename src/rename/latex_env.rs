@@ -1,8 +1,8 @@
 use crate::{
     feature::{FeatureProvider, FeatureRequest},
     protocol::{
-        Position, Range, RangeExt, RenameParams, TextDocumentPositionParams, TextEdit,
-        WorkspaceEdit,
+        Position, PrepareRenameResponse, RangeExt, RenameParams, TextDocumentPositionParams,
+        TextEdit, WorkspaceEdit,
     },
     syntax::{latex, SyntaxNode},
     workspace::DocumentContent,
@@ -16,17 +16,20 @@ pub struct LatexEnvironmentPrepareRenameProvider;
 #[async_trait]
 impl FeatureProvider for LatexEnvironmentPrepareRenameProvider {
     type Params = TextDocumentPositionParams;
-    type Output = Option<Range>;
+    type Output = Option<PrepareRenameResponse>;
 
     async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
         let pos = req.params.position;
         let (left_name, right_name) = find_environment(&req.current().content, pos)?;
-        let range = if left_name.range().contains(pos) {
-            left_name.range()
+        let name = if left_name.range().contains(pos) {
+            left_name
         } else {
-            right_name.range()
+            right_name
         };
-        Some(range)
+        Some(PrepareRenameResponse::RangeWithPlaceholder {
+            range: name.range(),
+            placeholder: name.text().into(),
+        })
     }
 }
 
@@ -74,7 +77,7 @@ fn find_environment(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::feature::FeatureTester;
+    use crate::{feature::FeatureTester, protocol::Range};
     use indoc::indoc;
 
     #[tokio::test]
@@ -154,4 +157,21 @@ mod tests {
 
         assert_eq!(actual_edit, None);
     }
+
+    #[tokio::test]
+    async fn prepare_placeholder() {
+        let actual_response = FeatureTester::new()
+            .file("main.tex", "\\begin{foo}\n\\end{bar}")
+            .main("main.tex")
+            .position(1, 6)
+            .test_position(LatexEnvironmentPrepareRenameProvider)
+            .await
+            .unwrap();
+
+        let expected_response = PrepareRenameResponse::RangeWithPlaceholder {
+            range: Range::new_simple(1, 5, 1, 8),
+            placeholder: "bar".into(),
+        };
+        assert_eq!(actual_response, expected_response);
+    }
 }
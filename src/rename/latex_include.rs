@@ -0,0 +1,275 @@
+use super::merge_edits;
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{RenameFilesParams, TextEdit, Uri, WorkspaceEdit},
+    syntax::{latex, SyntaxNode},
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+use std::{
+    collections::HashMap,
+    path::{Component, Path, PathBuf},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexIncludeFileRenameProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexIncludeFileRenameProvider {
+    type Params = RenameFilesParams;
+    type Output = Option<WorkspaceEdit>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let files: Vec<_> = req
+            .params
+            .files
+            .iter()
+            .filter_map(|file| {
+                let old_uri = Uri::parse(&file.old_uri).ok()?;
+                let new_uri = Uri::parse(&file.new_uri).ok()?;
+                Some((old_uri, new_uri))
+            })
+            .collect();
+
+        let mut changes = HashMap::new();
+        for doc in &req.snapshot().0 {
+            if req.cancellation.is_cancelled() {
+                return None;
+            }
+
+            if let DocumentContent::Latex(table) = &doc.content {
+                let base_url =
+                    match latex::include_base_url(&doc.uri, &req.options, &req.current_dir) {
+                        Some(base_url) => base_url,
+                        None => continue,
+                    };
+
+                let mut edits = Vec::new();
+                for include in &table.includes {
+                    for (path, targets) in
+                        include.paths(&table).into_iter().zip(&include.all_targets)
+                    {
+                        for (old_uri, new_uri) in &files {
+                            if let Some((index, new_target)) =
+                                targets.iter().enumerate().find_map(|(index, target)| {
+                                    renamed_target(target, old_uri, new_uri)
+                                        .map(|new_target| (index, new_target))
+                                })
+                            {
+                                if let Some(text) =
+                                    include_path(&base_url, &targets[index], &new_target, index > 0)
+                                {
+                                    edits.push(TextEdit::new(path.range(), text));
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if !edits.is_empty() {
+                    merge_edits(&mut changes, doc.uri.clone().into(), edits);
+                }
+            }
+        }
+
+        if changes.is_empty() {
+            None
+        } else {
+            Some(WorkspaceEdit::new(changes))
+        }
+    }
+}
+
+fn renamed_target(target: &Uri, old_uri: &Uri, new_uri: &Uri) -> Option<Uri> {
+    if target == old_uri {
+        return Some(new_uri.clone());
+    }
+
+    let old_dir = format!("{}/", old_uri.as_str().trim_end_matches('/'));
+    let new_dir = format!("{}/", new_uri.as_str().trim_end_matches('/'));
+    if target.as_str().starts_with(&old_dir) {
+        Uri::parse(&format!("{}{}", new_dir, &target.as_str()[old_dir.len()..])).ok()
+    } else {
+        None
+    }
+}
+
+fn include_path(
+    base_url: &Uri,
+    old_uri: &Uri,
+    new_uri: &Uri,
+    implicit_extension: bool,
+) -> Option<String> {
+    let base_dir = base_url.to_file_path().ok()?.parent()?.to_owned();
+    let old_path = old_uri.to_file_path().ok()?;
+    let mut new_path = new_uri.to_file_path().ok()?;
+    if implicit_extension && old_path.extension() == new_path.extension() {
+        new_path.set_extension("");
+    }
+
+    let relative_path = relative_path(&base_dir, &new_path)?;
+    let components: Vec<_> = relative_path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect();
+    Some(components.join("/"))
+}
+
+fn relative_path(base_dir: &Path, path: &Path) -> Option<PathBuf> {
+    let mut base_components = base_dir.components().peekable();
+    let mut path_components = path.components().peekable();
+    while let (Some(base), Some(component)) = (base_components.peek(), path_components.peek()) {
+        if base != component {
+            break;
+        }
+        base_components.next();
+        path_components.next();
+    }
+
+    let mut relative_path = PathBuf::new();
+    for component in base_components {
+        match component {
+            Component::Normal(_) => relative_path.push(".."),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    relative_path.extend(path_components);
+    Some(relative_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        feature::FeatureTester,
+        protocol::{Range, RangeExt},
+    };
+
+    #[tokio::test]
+    async fn same_directory() {
+        let actual_edit = FeatureTester::new()
+            .file("foo.tex", r#"\include{bar}\input{bar.tex}"#)
+            .file("bar.tex", "")
+            .main("foo.tex")
+            .test_file_rename(LatexIncludeFileRenameProvider, "bar.tex", "baz.tex")
+            .await;
+
+        let mut expected_changes = HashMap::new();
+        expected_changes.insert(
+            FeatureTester::uri("foo.tex").into(),
+            vec![
+                TextEdit::new(Range::new_simple(0, 9, 0, 12), "baz".into()),
+                TextEdit::new(Range::new_simple(0, 20, 0, 27), "baz.tex".into()),
+            ],
+        );
+        assert_eq!(actual_edit, Some(WorkspaceEdit::new(expected_changes)));
+    }
+
+    #[tokio::test]
+    async fn different_directory() {
+        let actual_edit = FeatureTester::new()
+            .file("foo.tex", r#"\input{chapters/bar}"#)
+            .file("chapters/bar.tex", "")
+            .main("foo.tex")
+            .test_file_rename(
+                LatexIncludeFileRenameProvider,
+                "chapters/bar.tex",
+                "appendix/bar.tex",
+            )
+            .await;
+
+        let mut expected_changes = HashMap::new();
+        expected_changes.insert(
+            FeatureTester::uri("foo.tex").into(),
+            vec![TextEdit::new(
+                Range::new_simple(0, 7, 0, 19),
+                "appendix/bar".into(),
+            )],
+        );
+        assert_eq!(actual_edit, Some(WorkspaceEdit::new(expected_changes)));
+    }
+
+    #[tokio::test]
+    async fn parent_directory() {
+        let actual_edit = FeatureTester::new()
+            .file("chapters/foo.tex", r#"\input{bar}"#)
+            .file("chapters/bar.tex", "")
+            .main("chapters/foo.tex")
+            .test_file_rename(
+                LatexIncludeFileRenameProvider,
+                "chapters/bar.tex",
+                "bar.tex",
+            )
+            .await;
+
+        let mut expected_changes = HashMap::new();
+        expected_changes.insert(
+            FeatureTester::uri("chapters/foo.tex").into(),
+            vec![TextEdit::new(
+                Range::new_simple(0, 7, 0, 10),
+                "../bar".into(),
+            )],
+        );
+        assert_eq!(actual_edit, Some(WorkspaceEdit::new(expected_changes)));
+    }
+
+    #[tokio::test]
+    async fn changed_extension() {
+        let actual_edit = FeatureTester::new()
+            .file("foo.tex", r#"\input{bar}"#)
+            .file("bar.tex", "")
+            .main("foo.tex")
+            .test_file_rename(LatexIncludeFileRenameProvider, "bar.tex", "bar.sty")
+            .await;
+
+        let mut expected_changes = HashMap::new();
+        expected_changes.insert(
+            FeatureTester::uri("foo.tex").into(),
+            vec![TextEdit::new(
+                Range::new_simple(0, 7, 0, 10),
+                "bar.sty".into(),
+            )],
+        );
+        assert_eq!(actual_edit, Some(WorkspaceEdit::new(expected_changes)));
+    }
+
+    #[tokio::test]
+    async fn unreferenced_file() {
+        let actual_edit = FeatureTester::new()
+            .file("foo.tex", r#"\input{bar}"#)
+            .file("baz.tex", "")
+            .main("foo.tex")
+            .test_file_rename(LatexIncludeFileRenameProvider, "baz.tex", "qux.tex")
+            .await;
+
+        assert_eq!(actual_edit, None);
+    }
+
+    #[tokio::test]
+    async fn renamed_directory() {
+        let actual_edit = FeatureTester::new()
+            .file(
+                "foo.tex",
+                r#"\input{chapters/bar}\input{chapters/sub/baz.tex}"#,
+            )
+            .file("chapters/bar.tex", "")
+            .file("chapters/sub/baz.tex", "")
+            .main("foo.tex")
+            .test_file_rename(LatexIncludeFileRenameProvider, "chapters", "appendix")
+            .await;
+
+        let mut expected_changes = HashMap::new();
+        expected_changes.insert(
+            FeatureTester::uri("foo.tex").into(),
+            vec![
+                TextEdit::new(Range::new_simple(0, 7, 0, 19), "appendix/bar".into()),
+                TextEdit::new(
+                    Range::new_simple(0, 27, 0, 47),
+                    "appendix/sub/baz.tex".into(),
+                ),
+            ],
+        );
+        assert_eq!(actual_edit, Some(WorkspaceEdit::new(expected_changes)));
+    }
+}
@@ -1,7 +1,8 @@
+use super::merge_edits;
 use crate::{
     feature::{FeatureProvider, FeatureRequest},
     protocol::{
-        Position, Range, RangeExt, RenameParams, TextDocumentPositionParams, TextEdit,
+        Position, PrepareRenameResponse, RenameParams, TextDocumentPositionParams, TextEdit,
         WorkspaceEdit,
     },
     syntax::{Span, SyntaxNode},
@@ -16,11 +17,15 @@ pub struct LatexLabelPrepareRenameProvider;
 #[async_trait]
 impl FeatureProvider for LatexLabelPrepareRenameProvider {
     type Params = TextDocumentPositionParams;
-    type Output = Option<Range>;
+    type Output = Option<PrepareRenameResponse>;
 
     async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
         let pos = req.params.position;
-        find_label(&req.current().content, pos).map(Span::range)
+        let name = find_label(&req.current().content, pos)?;
+        Some(PrepareRenameResponse::RangeWithPlaceholder {
+            range: name.range(),
+            placeholder: name.text.clone(),
+        })
     }
 }
 
@@ -37,15 +42,19 @@ impl FeatureProvider for LatexLabelRenameProvider {
         let name = find_label(&req.current().content, pos)?;
         let mut changes = HashMap::new();
         for doc in req.related() {
+            if req.cancellation.is_cancelled() {
+                return None;
+            }
+
             if let DocumentContent::Latex(table) = &doc.content {
                 let edits = table
-                    .labels
-                    .iter()
-                    .flat_map(|label| label.names(&table))
+                    .label_names()
                     .filter(|label| label.text() == name.text)
-                    .map(|label| TextEdit::new(label.range(), req.params.new_name.clone()))
-                    .collect();
-                changes.insert(doc.uri.clone().into(), edits);
+                    .map(|label| TextEdit::new(label.name.range(), req.params.new_name.clone()))
+                    .collect::<Vec<_>>();
+                if !edits.is_empty() {
+                    merge_edits(&mut changes, doc.uri.clone().into(), edits);
+                }
             }
         }
         Some(WorkspaceEdit::new(changes))
@@ -54,12 +63,7 @@ impl FeatureProvider for LatexLabelRenameProvider {
 
 fn find_label(content: &DocumentContent, pos: Position) -> Option<&Span> {
     if let DocumentContent::Latex(table) = content {
-        table
-            .labels
-            .iter()
-            .flat_map(|label| label.names(&table))
-            .find(|label| label.range().contains(pos))
-            .map(|label| &label.span)
+        table.find_label_name(pos).map(|label| &label.name.span)
     } else {
         None
     }
@@ -68,7 +72,10 @@ fn find_label(content: &DocumentContent, pos: Position) -> Option<&Span> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::feature::FeatureTester;
+    use crate::{
+        feature::{FeatureTester, WorkspaceEditSpec},
+        protocol::{Range, RangeExt},
+    };
     use indoc::indoc;
 
     #[tokio::test]
@@ -92,17 +99,144 @@ mod tests {
             .await
             .unwrap();
 
-        let mut expected_changes = HashMap::new();
-        expected_changes.insert(
-            FeatureTester::uri("foo.tex").into(),
-            vec![TextEdit::new(Range::new_simple(0, 7, 0, 10), "bar".into())],
-        );
-        expected_changes.insert(
-            FeatureTester::uri("bar.tex").into(),
-            vec![TextEdit::new(Range::new_simple(0, 5, 0, 8), "bar".into())],
-        );
-
-        assert_eq!(actual_edit, WorkspaceEdit::new(expected_changes));
+        WorkspaceEditSpec::new()
+            .edit("foo.tex", Range::new_simple(0, 7, 0, 10), "bar")
+            .edit("bar.tex", Range::new_simple(0, 5, 0, 8), "bar")
+            .assert_matches(&actual_edit);
+    }
+
+    #[tokio::test]
+    async fn nested_includes() {
+        let actual_edit = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \begin{document}
+                        \label{foo}
+                        \include{chapters/one}
+                        \end{document}
+                    "#
+                ),
+            )
+            .file("chapters/one.tex", r#"\input{sections/two}"#)
+            .file("sections/two.tex", r#"\ref{foo}"#)
+            .main("main.tex")
+            .position(1, 7)
+            .new_name("bar")
+            .test_rename(LatexLabelRenameProvider)
+            .await
+            .unwrap();
+
+        WorkspaceEditSpec::new()
+            .edit("main.tex", Range::new_simple(1, 7, 1, 10), "bar")
+            .edit("sections/two.tex", Range::new_simple(0, 5, 0, 8), "bar")
+            .assert_matches(&actual_edit);
+    }
+
+    #[tokio::test]
+    async fn diamond_includes() {
+        let actual_edit = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \label{foo}
+                        \input{a}
+                        \input{b}
+                    "#
+                ),
+            )
+            .file("a.tex", r#"\input{c}"#)
+            .file("b.tex", r#"\input{c}"#)
+            .file("c.tex", r#"\ref{foo}"#)
+            .main("main.tex")
+            .position(0, 7)
+            .new_name("bar")
+            .test_rename(LatexLabelRenameProvider)
+            .await
+            .unwrap();
+
+        WorkspaceEditSpec::new()
+            .edit("main.tex", Range::new_simple(0, 7, 0, 10), "bar")
+            .edit("c.tex", Range::new_simple(0, 5, 0, 8), "bar")
+            .assert_matches(&actual_edit);
+    }
+
+    #[tokio::test]
+    async fn inside_caption() {
+        let actual_edit = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \caption{Foo\label{fig:foo}}
+                        \ref{fig:foo}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(0, 20)
+            .new_name("fig:bar")
+            .test_rename(LatexLabelRenameProvider)
+            .await
+            .unwrap();
+
+        WorkspaceEditSpec::new()
+            .edit("main.tex", Range::new_simple(0, 19, 0, 26), "fig:bar")
+            .edit("main.tex", Range::new_simple(1, 5, 1, 12), "fig:bar")
+            .assert_matches(&actual_edit);
+    }
+
+    #[tokio::test]
+    async fn optional_argument() {
+        let actual_edit = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \label{foo}
+                        \hyperref[foo]{bar}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(1, 11)
+            .new_name("baz")
+            .test_rename(LatexLabelRenameProvider)
+            .await
+            .unwrap();
+
+        WorkspaceEditSpec::new()
+            .edit("main.tex", Range::new_simple(0, 7, 0, 10), "baz")
+            .edit("main.tex", Range::new_simple(1, 10, 1, 13), "baz")
+            .assert_matches(&actual_edit);
+    }
+
+    #[tokio::test]
+    async fn second_argument() {
+        let actual_edit = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \label{foo}
+                        \label{bar}
+                        \crefrange{foo}{bar}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(2, 17)
+            .new_name("baz")
+            .test_rename(LatexLabelRenameProvider)
+            .await
+            .unwrap();
+
+        WorkspaceEditSpec::new()
+            .edit("main.tex", Range::new_simple(1, 7, 1, 10), "baz")
+            .edit("main.tex", Range::new_simple(2, 16, 2, 19), "baz")
+            .assert_matches(&actual_edit);
     }
 
     #[tokio::test]
@@ -143,4 +277,21 @@ mod tests {
 
         assert_eq!(actual_edit, None);
     }
+
+    #[tokio::test]
+    async fn prepare_placeholder() {
+        let actual_response = FeatureTester::new()
+            .file("main.tex", "\\label{foo}\n\\ref{foo}")
+            .main("main.tex")
+            .position(1, 6)
+            .test_position(LatexLabelPrepareRenameProvider)
+            .await
+            .unwrap();
+
+        let expected_response = PrepareRenameResponse::RangeWithPlaceholder {
+            range: Range::new_simple(1, 5, 1, 8),
+            placeholder: "foo".into(),
+        };
+        assert_eq!(actual_response, expected_response);
+    }
 }
@@ -1,22 +1,30 @@
 mod bibtex_entry;
+mod bibtex_string;
 mod latex_cmd;
 mod latex_env;
+mod latex_include;
 mod latex_label;
 
 use self::{
     bibtex_entry::{BibtexEntryPrepareRenameProvider, BibtexEntryRenameProvider},
+    bibtex_string::{BibtexStringPrepareRenameProvider, BibtexStringRenameProvider},
     latex_cmd::{LatexCommandPrepareRenameProvider, LatexCommandRenameProvider},
     latex_env::{LatexEnvironmentPrepareRenameProvider, LatexEnvironmentRenameProvider},
+    latex_include::LatexIncludeFileRenameProvider,
     latex_label::{LatexLabelPrepareRenameProvider, LatexLabelRenameProvider},
 };
 use crate::{
     feature::{ChoiceProvider, FeatureProvider, FeatureRequest},
-    protocol::{Range, RenameParams, TextDocumentPositionParams, WorkspaceEdit},
+    protocol::{
+        PrepareRenameResponse, RenameFilesParams, RenameParams, TextDocumentPositionParams,
+        TextEdit, Url, WorkspaceEdit,
+    },
 };
 use async_trait::async_trait;
+use std::collections::HashMap;
 
 pub struct PrepareRenameProvider {
-    provider: ChoiceProvider<TextDocumentPositionParams, Range>,
+    provider: ChoiceProvider<TextDocumentPositionParams, PrepareRenameResponse>,
 }
 
 impl PrepareRenameProvider {
@@ -24,6 +32,7 @@ impl PrepareRenameProvider {
         Self {
             provider: ChoiceProvider::new(vec![
                 Box::new(BibtexEntryPrepareRenameProvider),
+                Box::new(BibtexStringPrepareRenameProvider),
                 Box::new(LatexCommandPrepareRenameProvider),
                 Box::new(LatexEnvironmentPrepareRenameProvider),
                 Box::new(LatexLabelPrepareRenameProvider),
@@ -41,12 +50,12 @@ impl Default for PrepareRenameProvider {
 #[async_trait]
 impl FeatureProvider for PrepareRenameProvider {
     type Params = TextDocumentPositionParams;
-    type Output = Option<Range>;
+    type Output = Option<PrepareRenameResponse>;
 
     async fn execute<'a>(
         &'a self,
         req: &'a FeatureRequest<TextDocumentPositionParams>,
-    ) -> Option<Range> {
+    ) -> Option<PrepareRenameResponse> {
         self.provider.execute(req).await
     }
 }
@@ -60,6 +69,7 @@ impl RenameProvider {
         Self {
             provider: ChoiceProvider::new(vec![
                 Box::new(BibtexEntryRenameProvider),
+                Box::new(BibtexStringRenameProvider),
                 Box::new(LatexCommandRenameProvider),
                 Box::new(LatexEnvironmentRenameProvider),
                 Box::new(LatexLabelRenameProvider),
@@ -86,3 +96,109 @@ impl FeatureProvider for RenameProvider {
         self.provider.execute(request).await
     }
 }
+
+pub struct FileRenameProvider {
+    provider: ChoiceProvider<RenameFilesParams, WorkspaceEdit>,
+}
+
+impl FileRenameProvider {
+    pub fn new() -> Self {
+        Self {
+            provider: ChoiceProvider::new(vec![Box::new(LatexIncludeFileRenameProvider)]),
+        }
+    }
+}
+
+impl Default for FileRenameProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for FileRenameProvider {
+    type Params = RenameFilesParams;
+    type Output = Option<WorkspaceEdit>;
+
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<RenameFilesParams>,
+    ) -> Option<WorkspaceEdit> {
+        self.provider.execute(request).await
+    }
+}
+
+fn merge_edits(changes: &mut HashMap<Url, Vec<TextEdit>>, uri: Url, edits: Vec<TextEdit>) {
+    let key = document_key(&uri);
+    let uri = changes
+        .keys()
+        .find(|other| document_key(other) == key)
+        .cloned()
+        .unwrap_or(uri);
+
+    let doc_edits = changes.entry(uri).or_default();
+    for edit in edits {
+        if !doc_edits.contains(&edit) {
+            doc_edits.push(edit);
+        }
+    }
+}
+
+fn document_key(uri: &Url) -> String {
+    if cfg!(any(windows, target_os = "macos")) {
+        uri.as_str().to_lowercase()
+    } else {
+        uri.as_str().to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{Range, RangeExt};
+
+    fn merge_mixed_case() -> (HashMap<Url, Vec<TextEdit>>, Url, Url, TextEdit, TextEdit) {
+        let edit1 = TextEdit::new(Range::new_simple(0, 0, 0, 1), "foo".into());
+        let edit2 = TextEdit::new(Range::new_simple(1, 0, 1, 1), "bar".into());
+        let upper_uri = Url::parse("file:///dir/Main.tex").unwrap();
+        let lower_uri = Url::parse("file:///dir/main.tex").unwrap();
+
+        let mut changes = HashMap::new();
+        merge_edits(&mut changes, upper_uri.clone(), vec![edit1.clone()]);
+        merge_edits(
+            &mut changes,
+            lower_uri.clone(),
+            vec![edit1.clone(), edit2.clone()],
+        );
+        (changes, upper_uri, lower_uri, edit1, edit2)
+    }
+
+    #[cfg(any(windows, target_os = "macos"))]
+    #[test]
+    fn merge_mixed_case_uris() {
+        let (changes, upper_uri, _, edit1, edit2) = merge_mixed_case();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[&upper_uri], vec![edit1, edit2]);
+    }
+
+    #[cfg(not(any(windows, target_os = "macos")))]
+    #[test]
+    fn keep_mixed_case_uris() {
+        let (changes, upper_uri, lower_uri, edit1, edit2) = merge_mixed_case();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[&upper_uri], vec![edit1.clone()]);
+        assert_eq!(changes[&lower_uri], vec![edit1, edit2]);
+    }
+
+    #[test]
+    fn merge_same_uri() {
+        let edit = TextEdit::new(Range::new_simple(0, 0, 0, 0), "foo".into());
+        let uri = Url::parse("file:///dir/main.tex").unwrap();
+        let mut changes = HashMap::new();
+        merge_edits(&mut changes, uri.clone(), vec![edit.clone()]);
+        merge_edits(&mut changes, uri.clone(), vec![edit.clone()]);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[&uri], vec![edit]);
+    }
+}
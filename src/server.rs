@@ -2,8 +2,11 @@
 use crate::citeproc::render_citation;
 
 use crate::{
+    bibliography::BibliographyProvider,
     build::BuildProvider,
-    completion::{CompletionItemData, CompletionProvider},
+    code_action::CodeActionProvider,
+    color::{ColorPresentationProvider, ColorProvider},
+    completion::{citation_detail, resolve_documentation, CompletionItemData, CompletionProvider},
     components::COMPONENT_DATABASE,
     config::ConfigManager,
     definition::DefinitionProvider,
@@ -14,12 +17,15 @@ use crate::{
     highlight::HighlightProvider,
     hover::HoverProvider,
     link::LinkProvider,
+    metrics::MetricsManager,
+    minimal_example::MinimalExampleProvider,
     protocol::*,
     reference::ReferenceProvider,
-    rename::{PrepareRenameProvider, RenameProvider},
+    rename::{FileRenameProvider, PrepareRenameProvider, RenameProvider},
     symbol::{document_symbols, workspace_symbols, SymbolProvider},
     syntax::{bibtex, latexindent, CharStream, SyntaxNode},
-    tex::{Distribution, DistributionKind, KpsewhichError},
+    tex::{Distribution, DistributionKind, KpsewhichError, Language},
+    todo::find_todos,
     workspace::{DocumentContent, Workspace},
 };
 use async_trait::async_trait;
@@ -29,56 +35,85 @@ use jsonrpc::{server::Result, Middleware};
 use jsonrpc_derive::{jsonrpc_method, jsonrpc_server};
 use log::{debug, error, info, warn};
 use once_cell::sync::{Lazy, OnceCell};
-use std::{mem, path::PathBuf, sync::Arc};
+use std::{
+    collections::HashSet,
+    mem,
+    path::{Path, PathBuf},
+    sync::{atomic::AtomicBool, Arc},
+};
+use tokio::task;
+use uuid::Uuid;
+
+const INDEX_BATCH_SIZE: usize = 20;
 
 pub struct LatexLspServer<C> {
     distro: Arc<dyn Distribution>,
     client: Arc<C>,
     client_capabilities: OnceCell<Arc<ClientCapabilities>>,
+    proposed_capabilities: OnceCell<ProposedClientCapabilities>,
     current_dir: Arc<PathBuf>,
+    root_dir: OnceCell<PathBuf>,
     config_manager: OnceCell<ConfigManager<C>>,
     action_manager: ActionManager,
-    workspace: Workspace,
+    workspace: Arc<Workspace>,
     build_provider: BuildProvider<C>,
+    bibliography_provider: BibliographyProvider,
+    code_action_provider: CodeActionProvider,
+    color_provider: ColorProvider,
+    color_presentation_provider: ColorPresentationProvider,
     completion_provider: CompletionProvider,
     definition_provider: DefinitionProvider,
     folding_provider: FoldingProvider,
     highlight_provider: HighlightProvider,
     link_provider: LinkProvider,
+    minimal_example_provider: MinimalExampleProvider,
     reference_provider: ReferenceProvider,
     prepare_rename_provider: PrepareRenameProvider,
     rename_provider: RenameProvider,
+    file_rename_provider: FileRenameProvider,
     symbol_provider: SymbolProvider,
     hover_provider: HoverProvider,
     diagnostics_manager: DiagnosticsManager,
+    metrics: MetricsManager,
     last_position_by_uri: CHashMap<Uri, Position>,
+    open_documents: Mutex<HashSet<Uri>>,
 }
 
 #[jsonrpc_server]
 impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
     pub fn new(distro: Arc<dyn Distribution>, client: Arc<C>, current_dir: Arc<PathBuf>) -> Self {
-        let workspace = Workspace::new(distro.clone(), Arc::clone(&current_dir));
+        let workspace = Arc::new(Workspace::new(distro.clone(), Arc::clone(&current_dir)));
         Self {
             distro,
             client: Arc::clone(&client),
             client_capabilities: OnceCell::new(),
+            proposed_capabilities: OnceCell::new(),
             current_dir,
+            root_dir: OnceCell::new(),
             config_manager: OnceCell::new(),
             action_manager: ActionManager::default(),
             workspace,
             build_provider: BuildProvider::new(client),
+            bibliography_provider: BibliographyProvider::new(),
+            code_action_provider: CodeActionProvider::new(),
+            color_provider: ColorProvider::new(),
+            color_presentation_provider: ColorPresentationProvider::new(),
             completion_provider: CompletionProvider::new(),
             definition_provider: DefinitionProvider::new(),
             folding_provider: FoldingProvider::new(),
             highlight_provider: HighlightProvider::new(),
             link_provider: LinkProvider::new(),
+            minimal_example_provider: MinimalExampleProvider::new(),
             reference_provider: ReferenceProvider::new(),
             prepare_rename_provider: PrepareRenameProvider::new(),
             rename_provider: RenameProvider::new(),
+            file_rename_provider: FileRenameProvider::new(),
             symbol_provider: SymbolProvider::new(),
             hover_provider: HoverProvider::new(),
             diagnostics_manager: DiagnosticsManager::default(),
+            metrics: MetricsManager::default(),
             last_position_by_uri: CHashMap::new(),
+            open_documents: Mutex::default(),
         }
     }
 
@@ -97,11 +132,28 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
     }
 
     #[jsonrpc_method("initialize", kind = "request")]
-    pub async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+    pub async fn initialize(&self, params: serde_json::Value) -> Result<InitializeResult> {
+        let proposed_capabilities = params
+            .get("capabilities")
+            .map(ProposedClientCapabilities::parse)
+            .unwrap_or_default();
+        let _ = self.proposed_capabilities.set(proposed_capabilities);
+
+        let params: InitializeParams =
+            serde_json::from_value(params).map_err(|why| why.to_string())?;
         self.client_capabilities
             .set(Arc::new(params.capabilities))
             .expect("initialize was called two times");
 
+        let root_dir = params
+            .root_uri
+            .and_then(|uri| uri.to_file_path().ok())
+            .or_else(|| params.root_path.map(PathBuf::from));
+        if let Some(root_dir) = root_dir {
+            self.workspace.set_root_dir(root_dir.clone());
+            let _ = self.root_dir.set(root_dir);
+        }
+
         let _ = self.config_manager.set(ConfigManager::new(
             Arc::clone(&self.client),
             self.client_capabilities(),
@@ -147,6 +199,8 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
                 work_done_progress_options: WorkDoneProgressOptions::default(),
             }),
             folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+            color_provider: Some(ColorProviderCapability::Simple(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
             ..ServerCapabilities::default()
         };
 
@@ -165,6 +219,7 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         self.action_manager.push(Action::PullConfiguration).await;
         self.action_manager.push(Action::RegisterCapabilities).await;
         self.action_manager.push(Action::LoadDistribution).await;
+        self.action_manager.push(Action::IndexWorkspace).await;
         self.action_manager.push(Action::PublishDiagnostics).await;
     }
 
@@ -176,14 +231,14 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
     #[jsonrpc_method("exit", kind = "notification")]
     pub async fn exit(&self, _params: ()) {}
 
-    #[jsonrpc_method("$/cancelRequest", kind = "notification")]
-    pub async fn cancel_request(&self, _params: CancelParams) {}
-
     #[jsonrpc_method("textDocument/didOpen", kind = "notification")]
     pub async fn did_open(&self, params: DidOpenTextDocumentParams) {
         let uri = params.text_document.uri.clone();
         let options = self.config_manager().get().await;
         self.workspace.add(params.text_document, &options).await;
+        self.open_documents.lock().await.insert(uri.clone().into());
+        self.update_environment_diagnostics(&uri.clone().into())
+            .await;
         self.action_manager
             .push(Action::DetectRoot(uri.clone().into()))
             .await;
@@ -213,6 +268,9 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
 
     #[jsonrpc_method("textDocument/didSave", kind = "notification")]
     pub async fn did_save(&self, params: DidSaveTextDocumentParams) {
+        self.update_environment_diagnostics(&params.text_document.uri.clone().into())
+            .await;
+
         self.action_manager
             .push(Action::Build(params.text_document.uri.clone().into()))
             .await;
@@ -227,7 +285,20 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
     }
 
     #[jsonrpc_method("textDocument/didClose", kind = "notification")]
-    pub async fn did_close(&self, _params: DidCloseTextDocumentParams) {}
+    pub async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri: Uri = params.text_document.uri.into();
+        self.open_documents.lock().await.remove(&uri);
+
+        let options = self.config_manager().get().await;
+        if diagnostics_scope(&options) == DiagnosticsScope::Open && !self.has_pull_diagnostics() {
+            let params = PublishDiagnosticsParams {
+                uri: uri.into(),
+                diagnostics: Vec::new(),
+                version: None,
+            };
+            self.client.publish_diagnostics(params).await;
+        }
+    }
 
     #[jsonrpc_method("workspace/didChangeConfiguration", kind = "notification")]
     pub async fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
@@ -243,9 +314,17 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
     }
 
     #[jsonrpc_method("textDocument/completion", kind = "request")]
-    pub async fn completion(&self, params: CompletionParams) -> Result<CompletionList> {
+    pub async fn completion(
+        &self,
+        params: CompletionParams,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<CompletionList> {
         let req = self
-            .make_feature_request(params.text_document_position.text_document.as_uri(), params)
+            .make_feature_request(
+                params.text_document_position.text_document.as_uri(),
+                params,
+                cancelled,
+            )
             .await?;
 
         self.last_position_by_uri.insert(
@@ -253,9 +332,23 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
             req.params.text_document_position.position,
         );
 
+        if !req.config.features.completion {
+            return Ok(CompletionList {
+                is_incomplete: false,
+                items: Vec::new(),
+            });
+        }
+
         Ok(CompletionList {
             is_incomplete: true,
-            items: self.completion_provider.execute(&req).await,
+            items: self
+                .metrics
+                .measure(
+                    "textDocument/completion",
+                    &req.options,
+                    self.completion_provider.execute(&req),
+                )
+                .await,
         })
     }
 
@@ -268,42 +361,78 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
                     .documentation(&item.label)
                     .map(Documentation::MarkupContent);
             }
-            #[cfg(feature = "citation")]
             CompletionItemData::Citation { uri, key } => {
                 let snapshot = self.workspace.get().await;
                 if let Some(doc) = snapshot.find(&uri) {
                     if let DocumentContent::Bibtex(tree) = &doc.content {
-                        let markup = render_citation(&tree, &key);
-                        item.documentation = markup.map(Documentation::MarkupContent);
+                        if item.detail.is_none() {
+                            item.detail = citation_detail(&tree, &key);
+                        }
+
+                        #[cfg(feature = "citation")]
+                        {
+                            let markup = render_citation(&tree, &key);
+                            item.documentation = markup.map(Documentation::MarkupContent);
+                        }
                     }
                 }
             }
+            CompletionItemData::ComponentCommand { .. }
+            | CompletionItemData::Argument { .. }
+            | CompletionItemData::EntryType
+            | CompletionItemData::FieldName => {
+                item.documentation =
+                    resolve_documentation(&self.client_capabilities(), &item.label, &data);
+            }
             _ => {}
         };
         Ok(item)
     }
 
     #[jsonrpc_method("textDocument/hover", kind = "request")]
-    pub async fn hover(&self, params: TextDocumentPositionParams) -> Result<Option<Hover>> {
+    pub async fn hover(
+        &self,
+        params: TextDocumentPositionParams,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<Option<Hover>> {
         let req = self
-            .make_feature_request(params.text_document.as_uri(), params)
+            .make_feature_request(params.text_document.as_uri(), params, cancelled)
             .await?;
 
         self.last_position_by_uri
             .insert(req.current().uri.clone(), req.params.position);
 
-        Ok(self.hover_provider.execute(&req).await)
+        if !req.config.features.hover {
+            return Ok(None);
+        }
+
+        Ok(self
+            .metrics
+            .measure(
+                "textDocument/hover",
+                &req.options,
+                self.hover_provider.execute(&req),
+            )
+            .await)
     }
 
     #[jsonrpc_method("textDocument/definition", kind = "request")]
     pub async fn definition(
         &self,
         params: TextDocumentPositionParams,
+        cancelled: Arc<AtomicBool>,
     ) -> Result<DefinitionResponse> {
         let req = self
-            .make_feature_request(params.text_document.as_uri(), params)
+            .make_feature_request(params.text_document.as_uri(), params, cancelled)
             .await?;
-        let results = self.definition_provider.execute(&req).await;
+        let results = self
+            .metrics
+            .measure(
+                "textDocument/definition",
+                &req.options,
+                self.definition_provider.execute(&req),
+            )
+            .await;
         let response = if req.client_capabilities.has_definition_link_support() {
             DefinitionResponse::LocationLinks(results)
         } else {
@@ -319,22 +448,41 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
     }
 
     #[jsonrpc_method("textDocument/references", kind = "request")]
-    pub async fn references(&self, params: ReferenceParams) -> Result<Vec<Location>> {
+    pub async fn references(
+        &self,
+        params: ReferenceParams,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<Vec<Location>> {
         let req = self
-            .make_feature_request(params.text_document_position.as_uri(), params)
+            .make_feature_request(params.text_document_position.as_uri(), params, cancelled)
             .await?;
-        Ok(self.reference_provider.execute(&req).await)
+        Ok(self
+            .metrics
+            .measure(
+                "textDocument/references",
+                &req.options,
+                self.reference_provider.execute(&req),
+            )
+            .await)
     }
 
     #[jsonrpc_method("textDocument/documentHighlight", kind = "request")]
     pub async fn document_highlight(
         &self,
         params: TextDocumentPositionParams,
+        cancelled: Arc<AtomicBool>,
     ) -> Result<Vec<DocumentHighlight>> {
         let req = self
-            .make_feature_request(params.text_document.as_uri(), params)
+            .make_feature_request(params.text_document.as_uri(), params, cancelled)
             .await?;
-        Ok(self.highlight_provider.execute(&req).await)
+        Ok(self
+            .metrics
+            .measure(
+                "textDocument/documentHighlight",
+                &req.options,
+                self.highlight_provider.execute(&req),
+            )
+            .await)
     }
 
     #[jsonrpc_method("workspace/symbol", kind = "request")]
@@ -346,11 +494,13 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         let client_capabilities = self.client_capabilities();
         let snapshot = self.workspace.get().await;
         let options = self.config_manager().get().await;
+        let excludes = self.workspace.excludes(&options).await;
         let symbols = workspace_symbols(
             distro,
             client_capabilities,
             snapshot,
             &options,
+            &excludes,
             Arc::clone(&self.current_dir),
             &params,
         )
@@ -358,16 +508,37 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         Ok(symbols)
     }
 
+    #[jsonrpc_method("workspace/todos", kind = "request")]
+    pub async fn todos(&self, _params: ()) -> Result<Vec<TodoItem>> {
+        let snapshot = self.workspace.get().await;
+        let options = self.config_manager().get().await;
+        let excludes = self.workspace.excludes(&options).await;
+        Ok(find_todos(&snapshot, &excludes))
+    }
+
+    #[jsonrpc_method("workspace/featureTimings", kind = "request")]
+    pub async fn feature_timings(&self, _params: ()) -> Result<Vec<FeatureTiming>> {
+        Ok(self.metrics.get().await)
+    }
+
     #[jsonrpc_method("textDocument/documentSymbol", kind = "request")]
     pub async fn document_symbol(
         &self,
         params: DocumentSymbolParams,
+        cancelled: Arc<AtomicBool>,
     ) -> Result<DocumentSymbolResponse> {
         let req = self
-            .make_feature_request(params.text_document.as_uri(), params)
+            .make_feature_request(params.text_document.as_uri(), params, cancelled)
             .await?;
 
-        let symbols = self.symbol_provider.execute(&req).await;
+        let symbols = self
+            .metrics
+            .measure(
+                "textDocument/documentSymbol",
+                &req.options,
+                self.symbol_provider.execute(&req),
+            )
+            .await;
         let response = document_symbols(
             &req.client_capabilities,
             req.snapshot(),
@@ -379,18 +550,119 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         Ok(response)
     }
 
+    #[jsonrpc_method("textDocument/outline", kind = "request")]
+    pub async fn outline(
+        &self,
+        params: OutlineParams,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<Vec<OutlineNode>> {
+        let uri = params.text_document.as_uri();
+        let params = DocumentSymbolParams {
+            text_document: params.text_document,
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+        let req = self.make_feature_request(uri, params, cancelled).await?;
+
+        let symbols = self
+            .metrics
+            .measure(
+                "textDocument/outline",
+                &req.options,
+                self.symbol_provider.execute(&req),
+            )
+            .await;
+        Ok(symbols.into_iter().map(Into::into).collect())
+    }
+
     #[jsonrpc_method("textDocument/documentLink", kind = "request")]
-    pub async fn document_link(&self, params: DocumentLinkParams) -> Result<Vec<DocumentLink>> {
+    pub async fn document_link(
+        &self,
+        params: DocumentLinkParams,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<Vec<DocumentLink>> {
+        let req = self
+            .make_feature_request(params.text_document.as_uri(), params, cancelled)
+            .await?;
+        Ok(self
+            .metrics
+            .measure(
+                "textDocument/documentLink",
+                &req.options,
+                self.link_provider.execute(&req),
+            )
+            .await)
+    }
+
+    #[jsonrpc_method("textDocument/codeAction", kind = "request")]
+    pub async fn code_action(
+        &self,
+        params: CodeActionParams,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<CodeActionResponse> {
+        let req = self
+            .make_feature_request(params.text_document.as_uri(), params, cancelled)
+            .await?;
+        let actions = self
+            .metrics
+            .measure(
+                "textDocument/codeAction",
+                &req.options,
+                self.code_action_provider.execute(&req),
+            )
+            .await;
+        Ok(actions
+            .into_iter()
+            .map(CodeActionOrCommand::CodeAction)
+            .collect())
+    }
+
+    #[jsonrpc_method("textDocument/documentColor", kind = "request")]
+    pub async fn document_color(
+        &self,
+        params: DocumentColorParams,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<Vec<ColorInformation>> {
+        let req = self
+            .make_feature_request(params.text_document.as_uri(), params, cancelled)
+            .await?;
+        Ok(self
+            .metrics
+            .measure(
+                "textDocument/documentColor",
+                &req.options,
+                self.color_provider.execute(&req),
+            )
+            .await)
+    }
+
+    #[jsonrpc_method("textDocument/colorPresentation", kind = "request")]
+    pub async fn color_presentation(
+        &self,
+        params: ColorPresentationParams,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<Vec<ColorPresentation>> {
         let req = self
-            .make_feature_request(params.text_document.as_uri(), params)
+            .make_feature_request(params.text_document.as_uri(), params, cancelled)
             .await?;
-        Ok(self.link_provider.execute(&req).await)
+        Ok(self
+            .metrics
+            .measure(
+                "textDocument/colorPresentation",
+                &req.options,
+                self.color_presentation_provider.execute(&req),
+            )
+            .await)
     }
 
     #[jsonrpc_method("textDocument/formatting", kind = "request")]
-    pub async fn formatting(&self, params: DocumentFormattingParams) -> Result<Vec<TextEdit>> {
+    pub async fn formatting(
+        &self,
+        params: DocumentFormattingParams,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<Vec<TextEdit>> {
         let req = self
-            .make_feature_request(params.text_document.as_uri(), params)
+            .make_feature_request(params.text_document.as_uri(), params, cancelled)
             .await?;
         let mut edits = Vec::new();
         match &req.current().content {
@@ -452,33 +724,130 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
     pub async fn prepare_rename(
         &self,
         params: TextDocumentPositionParams,
-    ) -> Result<Option<Range>> {
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<Option<PrepareRenameResponse>> {
         let req = self
-            .make_feature_request(params.text_document.as_uri(), params)
+            .make_feature_request(params.text_document.as_uri(), params, cancelled)
             .await?;
-        Ok(self.prepare_rename_provider.execute(&req).await)
+        Ok(self
+            .metrics
+            .measure(
+                "textDocument/prepareRename",
+                &req.options,
+                self.prepare_rename_provider.execute(&req),
+            )
+            .await)
     }
 
     #[jsonrpc_method("textDocument/rename", kind = "request")]
-    pub async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+    pub async fn rename(
+        &self,
+        params: RenameParams,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<Option<WorkspaceEdit>> {
         let req = self
-            .make_feature_request(params.text_document_position.text_document.as_uri(), params)
+            .make_feature_request(
+                params.text_document_position.text_document.as_uri(),
+                params,
+                cancelled,
+            )
             .await?;
-        Ok(self.rename_provider.execute(&req).await)
+        let edit = self
+            .metrics
+            .measure(
+                "textDocument/rename",
+                &req.options,
+                self.rename_provider.execute(&req),
+            )
+            .await;
+
+        if let Some(edit) = &edit {
+            self.diagnostics_manager
+                .renamed_label
+                .update(
+                    req.current(),
+                    req.params.text_document_position.position,
+                    &req.params.new_name,
+                    edit,
+                )
+                .await;
+        }
+        Ok(edit)
+    }
+
+    #[jsonrpc_method("workspace/willRenameFiles", kind = "request")]
+    pub async fn will_rename_files(
+        &self,
+        params: RenameFilesParams,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<Option<WorkspaceEdit>> {
+        let options = self.config_manager().get().await;
+        let old_uris: Vec<_> = params
+            .files
+            .iter()
+            .filter_map(|file| Uri::parse(&file.old_uri).ok())
+            .collect();
+        for uri in &old_uris {
+            let snapshot = self.workspace.get().await;
+            if snapshot.find(uri).is_none() {
+                if let Some(path) = uri
+                    .to_file_path()
+                    .ok()
+                    .filter(|path| Language::by_path(path, &options).is_some())
+                {
+                    let _ = self.workspace.load(&path, &options).await;
+                }
+            }
+        }
+
+        let snapshot = self.workspace.get().await;
+        let uri = match old_uris
+            .iter()
+            .find(|uri| snapshot.find(uri).is_some())
+            .or_else(|| snapshot.0.first().map(|doc| &doc.uri))
+        {
+            Some(uri) => uri.clone(),
+            None => return Ok(None),
+        };
+
+        let req = self.make_feature_request(uri, params, cancelled).await?;
+        Ok(self
+            .metrics
+            .measure(
+                "workspace/willRenameFiles",
+                &req.options,
+                self.file_rename_provider.execute(&req),
+            )
+            .await)
     }
 
     #[jsonrpc_method("textDocument/foldingRange", kind = "request")]
-    pub async fn folding_range(&self, params: FoldingRangeParams) -> Result<Vec<FoldingRange>> {
+    pub async fn folding_range(
+        &self,
+        params: FoldingRangeParams,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<Vec<FoldingRange>> {
         let req = self
-            .make_feature_request(params.text_document.as_uri(), params)
+            .make_feature_request(params.text_document.as_uri(), params, cancelled)
             .await?;
-        Ok(self.folding_provider.execute(&req).await)
+        Ok(self
+            .metrics
+            .measure(
+                "textDocument/foldingRange",
+                &req.options,
+                self.folding_provider.execute(&req),
+            )
+            .await)
     }
 
     #[jsonrpc_method("textDocument/build", kind = "request")]
-    pub async fn build(&self, params: BuildParams) -> Result<BuildResult> {
+    pub async fn build(
+        &self,
+        params: BuildParams,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<BuildResult> {
         let req = self
-            .make_feature_request(params.text_document.as_uri(), params)
+            .make_feature_request(params.text_document.as_uri(), params, cancelled)
             .await?;
 
         let pos = self
@@ -488,6 +857,11 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
             .unwrap_or_default();
 
         let res = self.build_provider.execute(&req).await;
+        let diagnostics_by_uri = self.build_provider.take_diagnostics().await;
+        if !diagnostics_by_uri.is_empty() {
+            self.diagnostics_manager.build.set(diagnostics_by_uri).await;
+            self.action_manager.push(Action::PublishDiagnostics).await;
+        }
 
         if req
             .options
@@ -498,7 +872,7 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
             && !self.build_provider.is_building()
         {
             let params = TextDocumentPositionParams::new(req.params.text_document, pos);
-            self.forward_search(params).await?;
+            self.forward_search(params, Arc::default()).await?;
         }
 
         Ok(res)
@@ -508,9 +882,10 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
     pub async fn forward_search(
         &self,
         params: TextDocumentPositionParams,
+        cancelled: Arc<AtomicBool>,
     ) -> Result<ForwardSearchResult> {
         let req = self
-            .make_feature_request(params.text_document.as_uri(), params)
+            .make_feature_request(params.text_document.as_uri(), params, cancelled)
             .await?;
 
         forward_search::search(
@@ -524,6 +899,71 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         .ok_or_else(|| "Unable to execute forward search".into())
     }
 
+    #[jsonrpc_method("textDocument/diagnostic", kind = "request")]
+    pub async fn diagnostic(
+        &self,
+        params: DocumentDiagnosticParams,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<DocumentDiagnosticReport> {
+        let req = self
+            .make_feature_request(params.text_document.as_uri(), params, cancelled)
+            .await?;
+        if !req.config.features.diagnostics {
+            let items = Vec::new();
+            return Ok(DocumentDiagnosticReport::Full(
+                FullDocumentDiagnosticReport { items },
+            ));
+        }
+
+        let report = self
+            .metrics
+            .measure(
+                "textDocument/diagnostic",
+                &req.options,
+                self.diagnostics_manager.report(&req.view, &req.options),
+            )
+            .await;
+        Ok(report)
+    }
+
+    #[jsonrpc_method("textDocument/bibliography", kind = "request")]
+    pub async fn bibliography(
+        &self,
+        params: TextDocumentPositionParams,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<Vec<Location>> {
+        let req = self
+            .make_feature_request(params.text_document.as_uri(), params, cancelled)
+            .await?;
+        Ok(self
+            .metrics
+            .measure(
+                "textDocument/bibliography",
+                &req.options,
+                self.bibliography_provider.execute(&req),
+            )
+            .await)
+    }
+
+    #[jsonrpc_method("textDocument/minimalExample", kind = "request")]
+    pub async fn minimal_example(
+        &self,
+        params: MinimalExampleParams,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<Option<String>> {
+        let req = self
+            .make_feature_request(params.text_document.as_uri(), params, cancelled)
+            .await?;
+        Ok(self
+            .metrics
+            .measure(
+                "textDocument/minimalExample",
+                &req.options,
+                self.minimal_example_provider.execute(&req),
+            )
+            .await)
+    }
+
     #[jsonrpc_method("$/detectRoot", kind = "request")]
     pub async fn detect_root(&self, params: TextDocumentIdentifier) -> Result<()> {
         let options = self.config_manager().get().await;
@@ -531,8 +971,14 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         Ok(())
     }
 
-    async fn make_feature_request<P>(&self, uri: Uri, params: P) -> Result<FeatureRequest<P>> {
+    async fn make_feature_request<P>(
+        &self,
+        uri: Uri,
+        params: P,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<FeatureRequest<P>> {
         let options = self.pull_configuration().await;
+        let config = self.config_manager().config().await;
         let snapshot = self.workspace.get().await;
         let client_capabilities = self.client_capabilities();
         match snapshot.find(&uri) {
@@ -542,7 +988,10 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
                 distro: self.distro.clone(),
                 client_capabilities,
                 options,
+                config,
                 current_dir: Arc::clone(&self.current_dir),
+                root_dir: self.root_dir.get().cloned(),
+                cancellation: cancelled.into(),
             }),
             None => {
                 let msg = format!("Unknown document: {}", uri);
@@ -551,6 +1000,66 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         }
     }
 
+    async fn register_file_operations(&self) {
+        let proposed_capabilities = self.proposed_capabilities.get();
+        if !proposed_capabilities.map_or(false, |cap| cap.will_rename_files) {
+            return;
+        }
+
+        let registration = Registration {
+            id: "will-rename-files".into(),
+            method: "workspace/willRenameFiles".into(),
+            register_options: Some(serde_json::json!({
+                "filters": [
+                    { "scheme": "file", "pattern": { "glob": "**/*.tex", "matches": "file" } },
+                    { "scheme": "file", "pattern": { "glob": "**", "matches": "folder" } }
+                ]
+            })),
+        };
+        let params = RegistrationParams {
+            registrations: vec![registration],
+        };
+
+        if let Err(why) = self.client.register_capability(params).await {
+            warn!(
+                "Failed to register \"workspace/willRenameFiles\": {}",
+                why.message
+            );
+        }
+    }
+
+    fn has_pull_diagnostics(&self) -> bool {
+        self.proposed_capabilities
+            .get()
+            .map_or(false, |cap| cap.pull_diagnostics)
+    }
+
+    async fn register_pull_diagnostics(&self) {
+        if !self.has_pull_diagnostics() {
+            return;
+        }
+
+        let registration = Registration {
+            id: "pull-diagnostics".into(),
+            method: "textDocument/diagnostic".into(),
+            register_options: Some(serde_json::json!({
+                "documentSelector": [{ "language": "latex" }, { "language": "bibtex" }],
+                "interFileDependencies": true,
+                "workspaceDiagnostics": false
+            })),
+        };
+        let params = RegistrationParams {
+            registrations: vec![registration],
+        };
+
+        if let Err(why) = self.client.register_capability(params).await {
+            warn!(
+                "Failed to register \"textDocument/diagnostic\": {}",
+                why.message
+            );
+        }
+    }
+
     async fn pull_configuration(&self) -> Options {
         let config_manager = self.config_manager();
         let has_changed = config_manager.pull().await;
@@ -585,6 +1094,30 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         }
     }
 
+    async fn update_environment_diagnostics(&self, uri: &Uri) {
+        let snapshot = self.workspace.get().await;
+        let options = self.config_manager().get().await;
+        if let Some(doc) = snapshot.find(uri) {
+            let view = DocumentView::analyze(snapshot, doc, &options, &self.current_dir);
+            self.diagnostics_manager.environment.update(&view);
+        }
+    }
+
+    async fn index_workspace(&self) {
+        let root_dir = match self.root_dir.get() {
+            Some(root_dir) => root_dir.clone(),
+            None => return,
+        };
+
+        let workspace = Arc::clone(&self.workspace);
+        let client = Arc::clone(&self.client);
+        let options = self.config_manager().get().await;
+        let has_progress = self.client_capabilities().has_work_done_progress_support();
+        tokio::spawn(async move {
+            index_files(&workspace, client, &root_dir, &options, has_progress).await;
+        });
+    }
+
     async fn load_distribution(&self) {
         info!("Detected TeX distribution: {}", self.distro.kind());
         if self.distro.kind() == DistributionKind::Unknown {
@@ -642,9 +1175,14 @@ impl<C: LspClient + Send + Sync + 'static> Middleware for LatexLspServer<C> {
                 Action::LoadDistribution => {
                     self.load_distribution().await;
                 }
+                Action::IndexWorkspace => {
+                    self.index_workspace().await;
+                }
                 Action::RegisterCapabilities => {
                     let config_manager = self.config_manager();
                     config_manager.register().await;
+                    self.register_file_operations().await;
+                    self.register_pull_diagnostics().await;
                 }
                 Action::PullConfiguration => {
                     self.pull_configuration().await;
@@ -653,10 +1191,37 @@ impl<C: LspClient + Send + Sync + 'static> Middleware for LatexLspServer<C> {
                     let options = self.config_manager().get().await;
                     let _ = self.workspace.detect_root(&uri, &options).await;
                 }
+                Action::PublishDiagnostics if self.has_pull_diagnostics() => (),
                 Action::PublishDiagnostics => {
                     let snapshot = self.workspace.get().await;
-                    for doc in &snapshot.0 {
-                        let diagnostics = self.diagnostics_manager.get(doc).await;
+                    let options = self.config_manager().get().await;
+                    let config = self.config_manager().config().await;
+                    let open_documents = self.open_documents.lock().await.clone();
+                    let components = snapshot.components(&options, &self.current_dir);
+                    for (doc, related) in components
+                        .iter()
+                        .flat_map(|related| related.iter().map(move |doc| (doc, related)))
+                        .filter(|(doc, _)| {
+                            diagnostics_scope(&options) == DiagnosticsScope::Workspace
+                                || open_documents.contains(&doc.uri)
+                        })
+                    {
+                        let view = DocumentView {
+                            snapshot: Arc::clone(&snapshot),
+                            current: Arc::clone(doc),
+                            related: related.clone(),
+                        };
+                        let diagnostics = if config.features.diagnostics {
+                            self.metrics
+                                .measure(
+                                    "textDocument/publishDiagnostics",
+                                    &options,
+                                    self.diagnostics_manager.get(&view, &options),
+                                )
+                                .await
+                        } else {
+                            Vec::new()
+                        };
                         let params = PublishDiagnosticsParams {
                             uri: doc.uri.clone().into(),
                             diagnostics,
@@ -676,7 +1241,11 @@ impl<C: LspClient + Send + Sync + 'static> Middleware for LatexLspServer<C> {
 
                     if options.on_save() {
                         let text_document = TextDocumentIdentifier::new(uri.into());
-                        self.build(BuildParams { text_document }).await.unwrap();
+                        let params = BuildParams {
+                            text_document,
+                            timeout: None,
+                        };
+                        self.build(params, Arc::default()).await.unwrap();
                     }
                 }
                 Action::RunLinter(uri, reason) => {
@@ -707,6 +1276,88 @@ impl<C: LspClient + Send + Sync + 'static> Middleware for LatexLspServer<C> {
     }
 }
 
+async fn index_files<C: LspClient>(
+    workspace: &Workspace,
+    client: Arc<C>,
+    root_dir: &Path,
+    options: &Options,
+    has_progress: bool,
+) {
+    let paths = workspace.find_files(root_dir, options).await;
+    let token = ProgressToken::String(format!("texlab-index-{}", Uuid::new_v4()));
+    let has_progress = has_progress
+        && client
+            .work_done_progress_create(WorkDoneProgressCreateParams {
+                token: token.clone(),
+            })
+            .await
+            .is_ok();
+
+    if has_progress {
+        let params = ProgressParams {
+            token: token.clone(),
+            value: ProgressParamsValue::WorkDone(WorkDoneProgress::Begin(WorkDoneProgressBegin {
+                title: "Indexing".into(),
+                cancellable: Some(false),
+                message: None,
+                percentage: Some(0.0),
+            })),
+        };
+        client.progress(params).await;
+    }
+
+    info!("Indexing {} files in {}", paths.len(), root_dir.display());
+    for (i, path) in paths.iter().enumerate() {
+        let is_loaded = match Uri::from_file_path(path) {
+            Ok(uri) => workspace.get().await.find(&uri).is_some(),
+            Err(()) => true,
+        };
+        if !is_loaded {
+            if let Err(why) = workspace.load(path, options).await {
+                warn!("Unable to index {}: {}", path.display(), why);
+            }
+        }
+
+        if (i + 1) % INDEX_BATCH_SIZE != 0 {
+            continue;
+        }
+
+        // Give pending requests a chance to run between batches.
+        task::yield_now().await;
+        if has_progress {
+            let params = ProgressParams {
+                token: token.clone(),
+                value: ProgressParamsValue::WorkDone(WorkDoneProgress::Report(
+                    WorkDoneProgressReport {
+                        cancellable: Some(false),
+                        message: Some(format!("{}/{}", i + 1, paths.len())),
+                        percentage: Some(((i + 1) * 100 / paths.len()) as f64),
+                    },
+                )),
+            };
+            client.progress(params).await;
+        }
+    }
+
+    if has_progress {
+        let params = ProgressParams {
+            token,
+            value: ProgressParamsValue::WorkDone(WorkDoneProgress::End(WorkDoneProgressEnd {
+                message: None,
+            })),
+        };
+        client.progress(params).await;
+    }
+}
+
+fn diagnostics_scope(options: &Options) -> DiagnosticsScope {
+    options
+        .latex
+        .as_ref()
+        .map(LatexOptions::diagnostics_scope)
+        .unwrap_or_default()
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum LintReason {
     Change,
@@ -716,6 +1367,7 @@ enum LintReason {
 #[derive(Debug, PartialEq, Clone)]
 enum Action {
     LoadDistribution,
+    IndexWorkspace,
     RegisterCapabilities,
     PullConfiguration,
     DetectRoot(Uri),
@@ -36,6 +36,8 @@ impl FeatureProvider for BibtexEntrySymbolProvider {
                         label: None,
                         kind: LatexSymbolKind::Entry(category),
                         deprecated: false,
+                        caption: None,
+                        entry_type: Some(entry.ty.text()[1..].to_lowercase()),
                         full_range: entry.range(),
                         selection_range: key.range(),
                         children: Self::field_symbols(tree, entry_node),
@@ -60,6 +62,8 @@ impl BibtexEntrySymbolProvider {
                 label: None,
                 kind: LatexSymbolKind::Field,
                 deprecated: false,
+                caption: None,
+                entry_type: None,
                 full_range: field.range(),
                 selection_range: field.name.range(),
                 children: Vec::new(),
@@ -77,6 +81,7 @@ mod tests {
         feature::FeatureTester,
         protocol::{Range, RangeExt},
     };
+    use indoc::indoc;
 
     #[tokio::test]
     async fn empty_latex_document() {
@@ -113,6 +118,8 @@ mod tests {
             label: None,
             kind: LatexSymbolKind::Entry(BibtexEntryTypeCategory::Article),
             deprecated: false,
+            caption: None,
+            entry_type: Some("article".into()),
             full_range: Range::new_simple(0, 0, 0, 35),
             selection_range: Range::new_simple(0, 9, 0, 12),
             children: vec![
@@ -121,6 +128,8 @@ mod tests {
                     label: None,
                     kind: LatexSymbolKind::Field,
                     deprecated: false,
+                    caption: None,
+                    entry_type: None,
                     full_range: Range::new_simple(0, 14, 0, 24),
                     selection_range: Range::new_simple(0, 14, 0, 17),
                     children: Vec::new(),
@@ -130,6 +139,8 @@ mod tests {
                     label: None,
                     kind: LatexSymbolKind::Field,
                     deprecated: false,
+                    caption: None,
+                    entry_type: None,
                     full_range: Range::new_simple(0, 25, 0, 34),
                     selection_range: Range::new_simple(0, 25, 0, 28),
                     children: Vec::new(),
@@ -140,6 +151,43 @@ mod tests {
         assert_eq!(actual_symbols, expected_symbols);
     }
 
+    #[tokio::test]
+    async fn multiple_entries() {
+        let actual_symbols = FeatureTester::new()
+            .file(
+                "main.bib",
+                indoc!(
+                    r#"
+                        @Book{foo,
+                            title = {Foo},
+                        }
+                        @inproceedings{bar,}
+                    "#
+                ),
+            )
+            .main("main.bib")
+            .test_symbol(BibtexEntrySymbolProvider)
+            .await;
+
+        let actual_entries: Vec<_> = actual_symbols
+            .iter()
+            .map(|symbol| {
+                (
+                    symbol.name.as_str(),
+                    symbol.entry_type.as_deref(),
+                    symbol.full_range,
+                )
+            })
+            .collect();
+        assert_eq!(
+            actual_entries,
+            vec![
+                ("foo", Some("book"), Range::new_simple(0, 0, 2, 1)),
+                ("bar", Some("inproceedings"), Range::new_simple(3, 0, 3, 20)),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn comment() {
         let actual_symbols = FeatureTester::new()
@@ -26,6 +26,8 @@ impl FeatureProvider for BibtexStringSymbolProvider {
                             label: None,
                             kind: LatexSymbolKind::String,
                             deprecated: false,
+                            caption: None,
+                            entry_type: None,
                             full_range: string.range(),
                             selection_range: name.range(),
                             children: Vec::new(),
@@ -81,6 +83,8 @@ mod tests {
             label: None,
             kind: LatexSymbolKind::String,
             deprecated: false,
+            caption: None,
+            entry_type: None,
             full_range: Range::new_simple(0, 0, 0, 22),
             selection_range: Range::new_simple(0, 8, 0, 11),
             children: Vec::new(),
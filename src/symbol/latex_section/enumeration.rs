@@ -51,6 +51,8 @@ fn make_symbol(
             label: label_name(table, label),
             kind: LatexSymbolKind::EnumerationItem,
             deprecated: false,
+            caption: None,
+            entry_type: None,
             full_range: range,
             selection_range: selection_range(table, table[items[i].parent].range(), label),
             children: Vec::new(),
@@ -62,6 +64,8 @@ fn make_symbol(
         label: None,
         kind: LatexSymbolKind::Enumeration,
         deprecated: false,
+        caption: None,
+        entry_type: None,
         full_range: enumeration.range(&table),
         selection_range: enumeration.range(&table),
         children,
@@ -34,6 +34,8 @@ fn make_symbol(view: &DocumentView, table: &latex::SymbolTable, full_range: Rang
         label: label_name(table, label),
         kind: LatexSymbolKind::Equation,
         deprecated: false,
+        caption: None,
+        entry_type: None,
         full_range,
         selection_range: selection_range(table, full_range, label),
         children: Vec::new(),
@@ -51,6 +51,8 @@ fn make_symbol(
             OutlineCaptionKind::Algorithm => LatexSymbolKind::Algorithm,
         },
         deprecated: false,
+        caption: Some(text),
+        entry_type: None,
         full_range: env.range(&table),
         selection_range: selection_range(table, env.range(&table), label),
         children: Vec::new(),
@@ -8,7 +8,7 @@ use crate::{
     feature::{DocumentView, FeatureProvider, FeatureRequest},
     outline::{Outline, OutlineContext, OutlineContextItem},
     protocol::{DocumentSymbolParams, Options, Position, Range, RangeExt},
-    syntax::{latex, CharStream, LatexLabelKind, SyntaxNode},
+    syntax::{latex, CharStream, SyntaxNode},
     workspace::DocumentContent,
 };
 use async_trait::async_trait;
@@ -160,9 +160,7 @@ impl<'a> LatexSectionNode<'a> {
     fn set_label(&mut self, view: &DocumentView, outline: &Outline) {
         if let Some(label) = self
             .table
-            .labels
-            .iter()
-            .filter(|label| label.kind == LatexLabelKind::Definition)
+            .label_definitions()
             .find(|label| self.full_range.contains(self.table[label.parent].start()))
         {
             if let Some(ctx) = OutlineContext::parse(view, outline, *label) {
@@ -257,6 +255,8 @@ impl<'a> Into<LatexSymbol> for LatexSectionNode<'a> {
             label: self.label,
             kind: LatexSymbolKind::Section,
             deprecated: false,
+            caption: None,
+            entry_type: None,
             full_range: self.full_range,
             selection_range: self.table[self.section.parent].range(),
             children,
@@ -370,6 +370,8 @@ mod tests {
                 label: None,
                 kind: LatexSymbolKind::Section,
                 deprecated: false,
+                caption: None,
+                entry_type: None,
                 full_range: Range::new_simple(0, 0, 3, 0),
                 selection_range: Range::new_simple(0, 0, 0, 13),
                 children: vec![
@@ -378,6 +380,8 @@ mod tests {
                         label: Some("sec:bar".into()),
                         kind: LatexSymbolKind::Section,
                         deprecated: false,
+                        caption: None,
+                        entry_type: None,
                         full_range: Range::new_simple(1, 0, 2, 0),
                         selection_range: Range::new_simple(1, 0, 1, 16),
                         children: Vec::new(),
@@ -387,6 +391,8 @@ mod tests {
                         label: None,
                         kind: LatexSymbolKind::Section,
                         deprecated: false,
+                        caption: None,
+                        entry_type: None,
                         full_range: Range::new_simple(2, 0, 3, 0),
                         selection_range: Range::new_simple(2, 0, 2, 16),
                         children: Vec::new(),
@@ -398,6 +404,8 @@ mod tests {
                 label: None,
                 kind: LatexSymbolKind::Section,
                 deprecated: false,
+                caption: None,
+                entry_type: None,
                 full_range: Range::new_simple(3, 0, 3, 13),
                 selection_range: Range::new_simple(3, 0, 3, 13),
                 children: Vec::new(),
@@ -428,6 +436,8 @@ mod tests {
             label: None,
             kind: LatexSymbolKind::Section,
             deprecated: false,
+            caption: None,
+            entry_type: None,
             full_range: Range::new_simple(0, 16, 1, 0),
             selection_range: Range::new_simple(0, 16, 0, 29),
             children: Vec::new(),
@@ -458,6 +468,8 @@ mod tests {
             label: None,
             kind: LatexSymbolKind::Section,
             deprecated: false,
+            caption: None,
+            entry_type: None,
             full_range: Range::new_simple(0, 0, 2, 15),
             selection_range: Range::new_simple(0, 0, 0, 13),
             children: vec![LatexSymbol {
@@ -465,6 +477,8 @@ mod tests {
                 label: None,
                 kind: LatexSymbolKind::Enumeration,
                 deprecated: false,
+                caption: None,
+                entry_type: None,
                 full_range: Range::new_simple(1, 0, 2, 15),
                 selection_range: Range::new_simple(1, 0, 2, 15),
                 children: Vec::new(),
@@ -496,6 +510,8 @@ mod tests {
                 label: None,
                 kind: LatexSymbolKind::Equation,
                 deprecated: false,
+                caption: None,
+                entry_type: None,
                 full_range: Range::new_simple(0, 0, 0, 7),
                 selection_range: Range::new_simple(0, 0, 0, 7),
                 children: Vec::new(),
@@ -505,6 +521,8 @@ mod tests {
                 label: Some("eq:foo".into()),
                 kind: LatexSymbolKind::Equation,
                 deprecated: false,
+                caption: None,
+                entry_type: None,
                 full_range: Range::new_simple(1, 0, 1, 44),
                 selection_range: Range::new_simple(1, 16, 1, 30),
                 children: Vec::new(),
@@ -531,6 +549,8 @@ mod tests {
             label: Some("eq:foo".into()),
             kind: LatexSymbolKind::Equation,
             deprecated: false,
+            caption: None,
+            entry_type: None,
             full_range: Range::new_simple(0, 0, 0, 18),
             selection_range: Range::new_simple(0, 2, 0, 16),
             children: Vec::new(),
@@ -552,6 +572,8 @@ mod tests {
             label: None,
             kind: LatexSymbolKind::Table,
             deprecated: false,
+            caption: Some("Foo".into()),
+            entry_type: None,
             full_range: Range::new_simple(0, 0, 0, 37),
             selection_range: Range::new_simple(0, 0, 0, 37),
             children: Vec::new(),
@@ -580,6 +602,8 @@ mod tests {
             label: Some("fig:foo".into()),
             kind: LatexSymbolKind::Figure,
             deprecated: false,
+            caption: Some("Foo".into()),
+            entry_type: None,
             full_range: Range::new_simple(0, 0, 0, 54),
             selection_range: Range::new_simple(0, 27, 0, 42),
             children: Vec::new(),
@@ -604,6 +628,8 @@ mod tests {
             label: None,
             kind: LatexSymbolKind::Theorem,
             deprecated: false,
+            caption: None,
+            entry_type: None,
             full_range: Range::new_simple(0, 25, 0, 49),
             selection_range: Range::new_simple(0, 25, 0, 49),
             children: Vec::new(),
@@ -637,6 +663,8 @@ mod tests {
             label: Some("thm:foo".into()),
             kind: LatexSymbolKind::Theorem,
             deprecated: false,
+            caption: None,
+            entry_type: None,
             full_range: Range::new_simple(1, 0, 1, 39),
             selection_range: Range::new_simple(1, 13, 1, 28),
             children: Vec::new(),
@@ -661,6 +689,8 @@ mod tests {
             label: None,
             kind: LatexSymbolKind::Theorem,
             deprecated: false,
+            caption: None,
+            entry_type: None,
             full_range: Range::new_simple(0, 25, 0, 54),
             selection_range: Range::new_simple(0, 25, 0, 54),
             children: Vec::new(),
@@ -57,6 +57,8 @@ fn make_symbol(
                         label: label_name(main_table, label),
                         kind: LatexSymbolKind::Theorem,
                         deprecated: false,
+                        caption: None,
+                        entry_type: None,
                         full_range: env.range(&main_table),
                         selection_range: selection_range(main_table, env.range(&main_table), label),
                         children: Vec::new(),
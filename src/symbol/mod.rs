@@ -11,14 +11,14 @@ use self::{
     latex_section::LatexSectionSymbolProvider, project_order::ProjectOrdering, types::LatexSymbol,
 };
 use crate::{
-    feature::{ConcatProvider, DocumentView, FeatureProvider, FeatureRequest},
+    feature::{CancellationToken, ConcatProvider, DocumentView, FeatureProvider, FeatureRequest},
     protocol::{
-        ClientCapabilities, ClientCapabilitiesExt, DocumentSymbolParams, DocumentSymbolResponse,
-        Options, PartialResultParams, SymbolInformation, TextDocumentIdentifier, Uri,
-        WorkDoneProgressParams, WorkspaceSymbolParams,
+        ClientCapabilities, ClientCapabilitiesExt, Config, DocumentSymbolParams,
+        DocumentSymbolResponse, Options, PartialResultParams, SymbolInformation,
+        TextDocumentIdentifier, Uri, WorkDoneProgressParams, WorkspaceSymbolParams,
     },
     tex::Distribution,
-    workspace::Snapshot,
+    workspace::{ExcludeFilter, Snapshot},
 };
 use async_trait::async_trait;
 use std::{
@@ -55,6 +55,10 @@ impl FeatureProvider for SymbolProvider {
     type Output = Vec<LatexSymbol>;
 
     async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        if req.current().is_large(&req.options) {
+            return Vec::new();
+        }
+
         self.provider.execute(req).await
     }
 }
@@ -93,13 +97,23 @@ pub async fn workspace_symbols<'a>(
     client_capabilities: Arc<ClientCapabilities>,
     snapshot: Arc<Snapshot>,
     options: &'a Options,
+    excludes: &'a ExcludeFilter,
     current_dir: Arc<PathBuf>,
     params: &'a WorkspaceSymbolParams,
 ) -> Vec<SymbolInformation> {
     let provider = SymbolProvider::new();
+    let config = Arc::new(Config::new(options));
     let mut symbols = Vec::new();
 
     for doc in &snapshot.0 {
+        let is_excluded = doc
+            .uri
+            .to_file_path()
+            .map_or(false, |path| excludes.is_excluded(&path));
+        if is_excluded {
+            continue;
+        }
+
         let uri: Uri = doc.uri.clone();
         let req = FeatureRequest {
             params: DocumentSymbolParams {
@@ -116,7 +130,10 @@ pub async fn workspace_symbols<'a>(
             distro: distro.clone(),
             client_capabilities: Arc::clone(&client_capabilities),
             options: options.clone(),
+            config: Arc::clone(&config),
             current_dir: Arc::clone(&current_dir),
+            root_dir: None,
+            cancellation: CancellationToken::new(),
         };
 
         let mut buffer = Vec::new();
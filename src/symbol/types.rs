@@ -1,5 +1,5 @@
 use crate::{
-    protocol::{DocumentSymbol, Location, Range, SymbolInformation, SymbolKind, Uri},
+    protocol::{DocumentSymbol, Location, OutlineNode, Range, SymbolInformation, SymbolKind, Uri},
     syntax::{BibtexEntryTypeCategory, Structure},
 };
 
@@ -37,12 +37,33 @@ impl Into<SymbolKind> for LatexSymbolKind {
     }
 }
 
+impl LatexSymbolKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Section => "section",
+            Self::Figure => "figure",
+            Self::Algorithm => "algorithm",
+            Self::Table => "table",
+            Self::Listing => "listing",
+            Self::Enumeration => "enumeration",
+            Self::EnumerationItem => "item",
+            Self::Theorem => "theorem",
+            Self::Equation => "equation",
+            Self::Entry(_) => "entry",
+            Self::Field => "field",
+            Self::String => "string",
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct LatexSymbol {
     pub name: String,
     pub label: Option<String>,
     pub kind: LatexSymbolKind,
     pub deprecated: bool,
+    pub caption: Option<String>,
+    pub entry_type: Option<String>,
     pub full_range: Range,
     pub selection_range: Range,
     pub children: Vec<LatexSymbol>,
@@ -94,7 +115,7 @@ impl Into<DocumentSymbol> for LatexSymbol {
         DocumentSymbol {
             name: self.name,
             deprecated: Some(self.deprecated),
-            detail: self.label,
+            detail: self.label.or(self.entry_type),
             kind: self.kind.into(),
             selection_range: self.selection_range,
             range: self.full_range,
@@ -102,3 +123,72 @@ impl Into<DocumentSymbol> for LatexSymbol {
         }
     }
 }
+
+impl Into<OutlineNode> for LatexSymbol {
+    fn into(self) -> OutlineNode {
+        OutlineNode {
+            kind: self.kind.as_str().into(),
+            name: self.name,
+            label: self.label,
+            caption: self.caption,
+            entry_type: self.entry_type,
+            range: self.full_range,
+            selection_range: self.selection_range,
+            children: self.children.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::RangeExt;
+
+    #[test]
+    fn outline_node() {
+        let symbol = LatexSymbol {
+            name: "Foo".into(),
+            label: Some("sec:foo".into()),
+            kind: LatexSymbolKind::Section,
+            deprecated: false,
+            caption: None,
+            entry_type: None,
+            full_range: Range::new_simple(0, 0, 2, 0),
+            selection_range: Range::new_simple(0, 0, 0, 13),
+            children: vec![LatexSymbol {
+                name: "Figure 1: Bar".into(),
+                label: None,
+                kind: LatexSymbolKind::Figure,
+                deprecated: false,
+                caption: Some("Bar".into()),
+                entry_type: None,
+                full_range: Range::new_simple(1, 0, 1, 10),
+                selection_range: Range::new_simple(1, 0, 1, 10),
+                children: Vec::new(),
+            }],
+        };
+
+        let actual_node: OutlineNode = symbol.into();
+
+        let expected_node = OutlineNode {
+            kind: "section".into(),
+            name: "Foo".into(),
+            label: Some("sec:foo".into()),
+            caption: None,
+            entry_type: None,
+            range: Range::new_simple(0, 0, 2, 0),
+            selection_range: Range::new_simple(0, 0, 0, 13),
+            children: vec![OutlineNode {
+                kind: "figure".into(),
+                name: "Figure 1: Bar".into(),
+                label: None,
+                caption: Some("Bar".into()),
+                entry_type: None,
+                range: Range::new_simple(1, 0, 1, 10),
+                selection_range: Range::new_simple(1, 0, 1, 10),
+                children: Vec::new(),
+            }],
+        };
+        assert_eq!(actual_node, expected_node);
+    }
+}
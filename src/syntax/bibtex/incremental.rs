@@ -0,0 +1,354 @@
+use super::{
+    ast::{Node, Root, Token, Tree},
+    lexer::Lexer,
+    parser::Parser,
+};
+use crate::{
+    protocol::{Position, Range, RangeExt},
+    syntax::{text::CharStream, Span, SyntaxNode},
+};
+use petgraph::graph::{Graph, NodeIndex};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Edit {
+    prefix: Vec<NodeIndex>,
+    suffix: Vec<NodeIndex>,
+    start: usize,
+    end: usize,
+    start_position: Position,
+    old_end_position: Position,
+    new_end_position: Position,
+}
+
+pub fn reparse(old_tree: &Tree, old_text: &str, new_text: &str) -> Tree {
+    find_edit(old_tree, old_text, new_text)
+        .and_then(|edit| splice(old_tree, new_text, &edit))
+        .unwrap_or_else(|| super::open(new_text))
+}
+
+fn find_edit(old_tree: &Tree, old_text: &str, new_text: &str) -> Option<Edit> {
+    let prefix_len = old_text
+        .char_indices()
+        .zip(new_text.chars())
+        .find(|((_, a), b)| a != b)
+        .map(|((i, _), _)| i)
+        .unwrap_or_else(|| old_text.len().min(new_text.len()));
+
+    let max_suffix_len = old_text.len().min(new_text.len()) - prefix_len;
+    let suffix_len = old_text[prefix_len..]
+        .chars()
+        .rev()
+        .zip(new_text[prefix_len..].chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(c, _)| c.len_utf8())
+        .scan(0, |len, c_len| {
+            *len += c_len;
+            Some(*len)
+        })
+        .take_while(|len| *len <= max_suffix_len)
+        .last()
+        .unwrap_or(0);
+
+    let change_start = position_at(old_text, prefix_len);
+    let change_end = position_at(old_text, old_text.len() - suffix_len);
+
+    let children: Vec<_> = old_tree.children(old_tree.root).collect();
+    let mut first = children
+        .iter()
+        .position(|child| old_tree.graph[*child].end() >= change_start)
+        .unwrap_or_else(|| children.len());
+
+    // An unterminated node may extend into the edited text.
+    while first > 0 && !is_closed(&old_tree.graph[children[first - 1]]) {
+        first -= 1;
+    }
+
+    let start_position = if first > 0 {
+        old_tree.graph[children[first - 1]].end()
+    } else {
+        Position::new(0, 0)
+    };
+    let start = offset_at(old_text, start_position);
+
+    // The node must be preceded by whitespace so that the reparsed tokens cannot merge with it.
+    let last = children[first..]
+        .iter()
+        .position(|child| {
+            let child_start = old_tree.graph[*child].start();
+            child_start > change_end && {
+                let offset = offset_at(old_text, child_start);
+                old_text[..offset]
+                    .chars()
+                    .last()
+                    .map_or(false, char::is_whitespace)
+            }
+        })
+        .map(|i| first + i)
+        .unwrap_or_else(|| children.len());
+
+    let (end, old_end_position, new_end_position) = match children.get(last) {
+        Some(child) => {
+            let old_end_position = old_tree.graph[*child].start();
+            let end = offset_at(old_text, old_end_position) + new_text.len() - old_text.len();
+            (end, old_end_position, position_at(new_text, end))
+        }
+        None => (
+            new_text.len(),
+            position_at(old_text, old_text.len()),
+            position_at(new_text, new_text.len()),
+        ),
+    };
+
+    if start > end {
+        return None;
+    }
+
+    Some(Edit {
+        prefix: children[..first].to_vec(),
+        suffix: children[last..].to_vec(),
+        start,
+        end,
+        start_position,
+        old_end_position,
+        new_end_position,
+    })
+}
+
+fn splice(old_tree: &Tree, new_text: &str, edit: &Edit) -> Option<Tree> {
+    let middle_tree = Parser::new(Lexer::new(&new_text[edit.start..edit.end])).parse();
+    let middle: Vec<_> = middle_tree.children(middle_tree.root).collect();
+    if let Some(child) = middle.last() {
+        if !is_closed(&middle_tree.graph[*child]) {
+            return None;
+        }
+    }
+
+    let mut graph = Graph::new();
+    let mut children = Vec::new();
+    for child in &edit.prefix {
+        children.push(copy(&mut graph, old_tree, *child, &|pos| pos));
+    }
+
+    let origin = edit.start_position;
+    for child in middle {
+        children.push(copy(&mut graph, &middle_tree, child, &|pos| {
+            if pos.line == 0 {
+                Position::new(origin.line, origin.character + pos.character)
+            } else {
+                Position::new(origin.line + pos.line, pos.character)
+            }
+        }));
+    }
+
+    let old_end = edit.old_end_position;
+    let new_end = edit.new_end_position;
+    for child in &edit.suffix {
+        children.push(copy(&mut graph, old_tree, *child, &|pos| {
+            if pos.line == old_end.line {
+                Position::new(
+                    new_end.line,
+                    pos.character - old_end.character + new_end.character,
+                )
+            } else {
+                Position::new(pos.line - old_end.line + new_end.line, pos.character)
+            }
+        }));
+    }
+
+    let range = match (children.first(), children.last()) {
+        (Some(first), Some(last)) => Range::new(graph[*first].start(), graph[*last].end()),
+        _ => Range::new_simple(0, 0, 0, 0),
+    };
+
+    let root = graph.add_node(Node::Root(Root { range }));
+    for child in children {
+        graph.add_edge(root, child, ());
+    }
+    Some(Tree { graph, root })
+}
+
+fn is_closed(node: &Node) -> bool {
+    match node {
+        Node::Preamble(preamble) => preamble.right.is_some(),
+        Node::String(string) => string.right.is_some(),
+        Node::Entry(entry) => entry.right.is_some(),
+        _ => true,
+    }
+}
+
+fn copy(
+    graph: &mut Graph<Node, ()>,
+    tree: &Tree,
+    node: NodeIndex,
+    translate: &dyn Fn(Position) -> Position,
+) -> NodeIndex {
+    let parent = graph.add_node(translate_node(&tree.graph[node], translate));
+    for child in tree.children(node) {
+        let child = copy(graph, tree, child, translate);
+        graph.add_edge(parent, child, ());
+    }
+    parent
+}
+
+fn translate_node(node: &Node, translate: &dyn Fn(Position) -> Position) -> Node {
+    let range = |range: Range| Range::new(translate(range.start), translate(range.end));
+    let token = |token: &Token| {
+        let span = Span::new(range(token.span.range), token.span.text.clone());
+        Token::new(span, token.kind)
+    };
+    let opt_token = |opt: &Option<Token>| opt.as_ref().map(token);
+
+    let mut node = node.clone();
+    match &mut node {
+        Node::Root(root) => root.range = range(root.range),
+        Node::Comment(comment) => comment.token = token(&comment.token),
+        Node::Preamble(preamble) => {
+            preamble.range = range(preamble.range);
+            preamble.ty = token(&preamble.ty);
+            preamble.left = opt_token(&preamble.left);
+            preamble.right = opt_token(&preamble.right);
+        }
+        Node::String(string) => {
+            string.range = range(string.range);
+            string.ty = token(&string.ty);
+            string.left = opt_token(&string.left);
+            string.name = opt_token(&string.name);
+            string.assign = opt_token(&string.assign);
+            string.right = opt_token(&string.right);
+        }
+        Node::Entry(entry) => {
+            entry.range = range(entry.range);
+            entry.ty = token(&entry.ty);
+            entry.left = opt_token(&entry.left);
+            entry.key = opt_token(&entry.key);
+            entry.comma = opt_token(&entry.comma);
+            entry.right = opt_token(&entry.right);
+        }
+        Node::Field(field) => {
+            field.range = range(field.range);
+            field.name = token(&field.name);
+            field.assign = opt_token(&field.assign);
+            field.comma = opt_token(&field.comma);
+        }
+        Node::Word(word) => word.token = token(&word.token),
+        Node::Command(cmd) => cmd.token = token(&cmd.token),
+        Node::QuotedContent(content) => {
+            content.range = range(content.range);
+            content.left = token(&content.left);
+            content.right = opt_token(&content.right);
+        }
+        Node::BracedContent(content) => {
+            content.range = range(content.range);
+            content.left = token(&content.left);
+            content.right = opt_token(&content.right);
+        }
+        Node::Concat(concat) => {
+            concat.range = range(concat.range);
+            concat.operator = token(&concat.operator);
+        }
+    }
+    node
+}
+
+fn position_at(text: &str, offset: usize) -> Position {
+    let mut position = Position::new(0, 0);
+    for c in text[..offset].chars() {
+        if c == '\n' {
+            position.line += 1;
+            position.character = 0;
+        } else {
+            position.character += 1;
+        }
+    }
+    position
+}
+
+fn offset_at(text: &str, position: Position) -> usize {
+    let mut stream = CharStream::new(text);
+    stream.seek(position);
+    stream.current_index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::bibtex::{open, Visitor};
+    use indoc::indoc;
+
+    #[derive(Debug, Default)]
+    struct TreeTraversal {
+        nodes: Vec<Node>,
+    }
+
+    impl<'a> Visitor<'a> for TreeTraversal {
+        fn visit(&mut self, tree: &Tree, node: NodeIndex) {
+            self.nodes.push(tree.graph[node].clone());
+            tree.walk(self, node);
+        }
+    }
+
+    fn nodes(tree: &Tree) -> Vec<Node> {
+        let mut traversal = TreeTraversal::default();
+        traversal.visit(tree, tree.root);
+        traversal.nodes
+    }
+
+    fn verify(old_text: &str, new_text: &str) -> String {
+        let old_tree = open(old_text);
+        let actual_tree = reparse(&old_tree, old_text, new_text);
+        assert_eq!(nodes(&actual_tree), nodes(&open(new_text)));
+
+        let edit = find_edit(&old_tree, old_text, new_text).unwrap();
+        new_text[edit.start..edit.end].trim().to_owned()
+    }
+
+    const TEXT: &str = indoc!(
+        r#"
+            @article{foo, title = {Foo}}
+
+            @book{bar, title = {Bar}}
+
+            @misc{baz, title = {Baz}}
+        "#
+    );
+
+    #[test]
+    fn edit_middle_entry() {
+        let new_text = TEXT.replace("{Bar}", "{Bar\nQux}");
+        assert_eq!(verify(TEXT, &new_text), "@book{bar, title = {Bar\nQux}}");
+    }
+
+    #[test]
+    fn edit_first_entry() {
+        let new_text = TEXT.replace("foo,", "foö,");
+        assert_eq!(verify(TEXT, &new_text), "@article{foö, title = {Foo}}");
+    }
+
+    #[test]
+    fn edit_last_entry() {
+        let new_text = TEXT.replace("{Baz}", "{}");
+        assert_eq!(verify(TEXT, &new_text), "@misc{baz, title = {}}");
+    }
+
+    #[test]
+    fn insert_entry() {
+        let new_text = TEXT.replace("\n\n@misc", "\n\n@online{qux,}\n\n@misc");
+        assert_eq!(
+            verify(TEXT, &new_text),
+            "@online{qux,}\n\n@misc{baz, title = {Baz}}"
+        );
+    }
+
+    #[test]
+    fn unterminated_entry() {
+        let new_text = TEXT.replace("{Bar}}", "{Bar}");
+        let old_tree = open(TEXT);
+        let actual_tree = reparse(&old_tree, TEXT, &new_text);
+        assert_eq!(nodes(&actual_tree), nodes(&open(&new_text)));
+    }
+
+    #[test]
+    fn same_text() {
+        assert_eq!(verify(TEXT, TEXT), "");
+    }
+}
@@ -1,9 +1,10 @@
 mod ast;
 mod formatter;
+mod incremental;
 mod lexer;
 mod parser;
 
-pub use self::{ast::*, formatter::*};
+pub use self::{ast::*, formatter::*, incremental::reparse};
 
 use self::{lexer::Lexer, parser::Parser};
 
@@ -393,4 +394,24 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn truncated_document() {
+        let text = concat!(
+            "@string{foo = \"bar\"}\n",
+            "@article{baz,\n",
+            "    author = {Foo {Bar}} # foo,\n",
+            "    title = \"Qux\",\n",
+            "}\n",
+        );
+
+        let key_end = text.find("baz").unwrap() + "baz".len();
+        for end in 0..=text.len() {
+            let tree = open(&text[..end]);
+            let mut traversal = TreeTraversal::default();
+            traversal.visit(&tree, tree.root);
+            assert!(traversal.nodes.len() > 1 || end == 0);
+            assert!(tree.entry_by_key("baz").is_some() || end < key_end);
+        }
+    }
 }
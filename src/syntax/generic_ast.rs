@@ -8,6 +8,7 @@ pub struct AstNodeIndex(usize);
 pub struct Ast<T> {
     nodes: Vec<T>,
     edges: Vec<Vec<AstNodeIndex>>,
+    parents: Vec<Option<AstNodeIndex>>,
 }
 
 impl<T> Ast<T> {
@@ -15,6 +16,7 @@ impl<T> Ast<T> {
         Self {
             nodes: Vec::new(),
             edges: Vec::new(),
+            parents: Vec::new(),
         }
     }
 
@@ -30,16 +32,22 @@ impl<T> Ast<T> {
         let node = AstNodeIndex(self.nodes.len());
         self.nodes.push(value);
         self.edges.push(Vec::new());
+        self.parents.push(None);
         node
     }
 
     pub fn add_edge(&mut self, parent: AstNodeIndex, child: AstNodeIndex) {
         self.edges[parent.0].push(child);
+        self.parents[child.0] = Some(parent);
     }
 
     pub fn children<'a>(&'a self, parent: AstNodeIndex) -> impl Iterator<Item = AstNodeIndex> + 'a {
         self.edges[parent.0].iter().map(|child| *child)
     }
+
+    pub fn parent(&self, child: AstNodeIndex) -> Option<AstNodeIndex> {
+        self.parents[child.0]
+    }
 }
 
 impl<T> Index<AstNodeIndex> for Ast<T> {
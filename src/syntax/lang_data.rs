@@ -1,5 +1,7 @@
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 use serde::{Deserialize, Serialize};
+use std::{fs, io, path::Path};
+use thiserror::Error;
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -44,6 +46,8 @@ pub struct LatexLabelCommand {
     pub name: String,
     pub index: usize,
     pub kind: LatexLabelKind,
+    #[serde(default)]
+    pub optional: bool,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -116,6 +120,13 @@ pub struct LatexTheoremDefinitionCommand {
     pub index: usize,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexCommandReplacement {
+    pub name: String,
+    pub replacement: String,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LatexColorCommand {
@@ -123,6 +134,13 @@ pub struct LatexColorCommand {
     pub index: usize,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexUrlCommand {
+    pub name: String,
+    pub index: usize,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LatexColorModelCommand {
@@ -130,6 +148,41 @@ pub struct LatexColorModelCommand {
     pub index: usize,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexUnitCommand {
+    pub name: String,
+    pub index: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexMathCommand {
+    pub name: String,
+    pub index: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexTextCommand {
+    pub name: String,
+    pub index: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexPageStyle {
+    pub name: String,
+    pub component: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexSpacingCommand {
+    pub name: String,
+    pub math: bool,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum LatexGlossaryEntryKind {
@@ -173,6 +226,13 @@ pub struct BibtexEntryTypeDoc {
     pub documentation: Option<String>,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BibtexCitationDetail {
+    pub name: String,
+    pub fields: Vec<String>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BibtexFieldDoc {
@@ -181,7 +241,7 @@ pub struct BibtexFieldDoc {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
 pub struct LanguageData {
     pub environment_commands: Vec<LatexEnvironmentCommand>,
     pub citation_commands: Vec<LatexCitationCommand>,
@@ -191,17 +251,32 @@ pub struct LanguageData {
     pub command_definition_commands: Vec<LatexCommandDefinitionCommand>,
     pub math_operator_commands: Vec<LatexMathOperatorCommand>,
     pub theorem_definition_commands: Vec<LatexTheoremDefinitionCommand>,
+    pub internal_commands: Vec<String>,
+    pub deprecated_commands: Vec<String>,
+    pub command_replacements: Vec<LatexCommandReplacement>,
     pub colors: Vec<String>,
     pub color_commands: Vec<LatexColorCommand>,
     pub color_model_commands: Vec<LatexColorModelCommand>,
+    pub unit_commands: Vec<LatexUnitCommand>,
+    pub units: Vec<String>,
+    pub url_commands: Vec<LatexUrlCommand>,
+    pub math_commands: Vec<LatexMathCommand>,
+    pub text_commands: Vec<LatexTextCommand>,
     pub glossary_entry_definition_commands: Vec<LatexGlossaryEntryDefinitionCommand>,
     pub glossary_entry_reference_commands: Vec<LatexGlossaryEntryReferenceCommand>,
     pub entry_types: Vec<BibtexEntryTypeDoc>,
     pub fields: Vec<BibtexFieldDoc>,
+    pub citation_details: Vec<BibtexCitationDetail>,
     pub pgf_libraries: Vec<String>,
     pub tikz_libraries: Vec<String>,
     pub math_environments: Vec<String>,
     pub enum_environments: Vec<String>,
+    pub list_environments: Vec<String>,
+    pub beamer_frame_options: Vec<String>,
+    pub graphics_options: Vec<String>,
+    pub page_styles: Vec<LatexPageStyle>,
+    pub spacing_commands: Vec<LatexSpacingCommand>,
+    pub item_labels: Vec<String>,
 }
 
 impl LanguageData {
@@ -212,20 +287,249 @@ impl LanguageData {
             .find(|ty| ty.name.to_lowercase() == name)
     }
 
+    pub fn find_citation_detail(&self, name: &str) -> Option<&BibtexCitationDetail> {
+        let name = name.to_lowercase();
+        self.citation_details
+            .iter()
+            .find(|detail| detail.name == name)
+            .or_else(|| {
+                self.citation_details
+                    .iter()
+                    .find(|detail| detail.name == "misc")
+            })
+    }
+
     pub fn entry_type_documentation(&self, name: &str) -> Option<&str> {
         self.find_entry_type(name)
             .and_then(|ty| ty.documentation.as_ref().map(AsRef::as_ref))
     }
 
+    pub fn command_replacement(&self, name: &str) -> Option<&str> {
+        self.command_replacements
+            .iter()
+            .find(|cmd| cmd.name == name)
+            .map(|cmd| cmd.replacement.as_str())
+    }
+
     pub fn field_documentation(&self, name: &str) -> Option<&str> {
         self.fields
             .iter()
             .find(|field| field.name.to_lowercase() == name.to_lowercase())
             .map(|field| field.documentation.as_ref())
     }
+
+    pub fn merge(&mut self, other: Self) {
+        merge_by(
+            &mut self.environment_commands,
+            other.environment_commands,
+            |cmd| &cmd.name,
+        );
+        merge_by(
+            &mut self.citation_commands,
+            other.citation_commands,
+            |cmd| &cmd.name,
+        );
+        merge_by(&mut self.label_commands, other.label_commands, |cmd| {
+            &cmd.name
+        });
+        merge_by(&mut self.section_commands, other.section_commands, |cmd| {
+            &cmd.name
+        });
+        merge_by(&mut self.include_commands, other.include_commands, |cmd| {
+            &cmd.name
+        });
+        merge_by(
+            &mut self.command_definition_commands,
+            other.command_definition_commands,
+            |cmd| &cmd.name,
+        );
+        merge_by(
+            &mut self.math_operator_commands,
+            other.math_operator_commands,
+            |cmd| &cmd.name,
+        );
+        merge_by(
+            &mut self.theorem_definition_commands,
+            other.theorem_definition_commands,
+            |cmd| &cmd.name,
+        );
+        merge_by(
+            &mut self.internal_commands,
+            other.internal_commands,
+            |cmd| cmd,
+        );
+        merge_by(
+            &mut self.deprecated_commands,
+            other.deprecated_commands,
+            |cmd| cmd,
+        );
+        merge_by(
+            &mut self.command_replacements,
+            other.command_replacements,
+            |cmd| &cmd.name,
+        );
+        merge_by(&mut self.colors, other.colors, |color| color);
+        merge_by(&mut self.color_commands, other.color_commands, |cmd| {
+            &cmd.name
+        });
+        merge_by(
+            &mut self.color_model_commands,
+            other.color_model_commands,
+            |cmd| &cmd.name,
+        );
+        merge_by(&mut self.unit_commands, other.unit_commands, |cmd| {
+            &cmd.name
+        });
+        merge_by(&mut self.units, other.units, |unit| unit);
+        merge_by(&mut self.url_commands, other.url_commands, |cmd| &cmd.name);
+        merge_by(&mut self.math_commands, other.math_commands, |cmd| {
+            &cmd.name
+        });
+        merge_by(&mut self.text_commands, other.text_commands, |cmd| {
+            &cmd.name
+        });
+        merge_by(
+            &mut self.glossary_entry_definition_commands,
+            other.glossary_entry_definition_commands,
+            |cmd| &cmd.name,
+        );
+        merge_by(
+            &mut self.glossary_entry_reference_commands,
+            other.glossary_entry_reference_commands,
+            |cmd| &cmd.name,
+        );
+        merge_by(&mut self.entry_types, other.entry_types, |ty| &ty.name);
+        merge_by(&mut self.fields, other.fields, |field| &field.name);
+        merge_by(
+            &mut self.citation_details,
+            other.citation_details,
+            |detail| &detail.name,
+        );
+        merge_by(&mut self.pgf_libraries, other.pgf_libraries, |lib| lib);
+        merge_by(&mut self.tikz_libraries, other.tikz_libraries, |lib| lib);
+        merge_by(
+            &mut self.math_environments,
+            other.math_environments,
+            |env| env,
+        );
+        merge_by(
+            &mut self.enum_environments,
+            other.enum_environments,
+            |env| env,
+        );
+        merge_by(
+            &mut self.list_environments,
+            other.list_environments,
+            |env| env,
+        );
+        merge_by(
+            &mut self.beamer_frame_options,
+            other.beamer_frame_options,
+            |opt| opt,
+        );
+        merge_by(&mut self.graphics_options, other.graphics_options, |opt| {
+            opt
+        });
+        merge_by(&mut self.page_styles, other.page_styles, |style| {
+            &style.name
+        });
+        merge_by(&mut self.spacing_commands, other.spacing_commands, |cmd| {
+            &cmd.name
+        });
+        merge_by(&mut self.item_labels, other.item_labels, |label| label);
+    }
+}
+
+fn merge_by<T, F>(base: &mut Vec<T>, extra: Vec<T>, key: F)
+where
+    F: Fn(&T) -> &String,
+{
+    let builtin_count = base.len();
+    for item in extra {
+        if base[..builtin_count]
+            .iter()
+            .all(|builtin| key(builtin) != key(&item))
+        {
+            base.push(item);
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum LanguageDataError {
+    #[error("an I/O error occurred: `{0}`")]
+    IO(#[from] io::Error),
+    #[error("invalid language data: `{0}`")]
+    Json(#[from] serde_json::Error),
+    #[error("language data has already been loaded")]
+    AlreadyLoaded,
+}
+
+static USER_LANGUAGE_DATA: OnceCell<LanguageData> = OnceCell::new();
+
+pub fn load_user_language_data(path: &Path) -> Result<(), LanguageDataError> {
+    let text = fs::read_to_string(path)?;
+    let data: LanguageData = serde_json::from_str(&text)?;
+    USER_LANGUAGE_DATA
+        .set(data)
+        .map_err(|_| LanguageDataError::AlreadyLoaded)
 }
 
 pub static LANGUAGE_DATA: Lazy<LanguageData> = Lazy::new(|| {
     const JSON: &str = include_str!("../../data/lang_data.json");
-    serde_json::from_str(JSON).expect("Failed to deserialize language.json")
+    let mut data: LanguageData =
+        serde_json::from_str(JSON).expect("Failed to deserialize language.json");
+    if let Some(user_data) = USER_LANGUAGE_DATA.get() {
+        data.merge(user_data.clone());
+    }
+    data
 });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_appends_new_commands() {
+        let mut data = LanguageData::default();
+        data.merge(serde_json::from_str(r#"{"colors": ["foo"]}"#).unwrap());
+        assert_eq!(data.colors, vec!["foo".to_owned()]);
+    }
+
+    #[test]
+    fn merge_keeps_builtin_commands() {
+        let mut data = LanguageData::default();
+        data.label_commands.push(LatexLabelCommand {
+            name: "\\ref".into(),
+            index: 0,
+            kind: LatexLabelKind::Reference(LatexLabelReferenceSource::Everything),
+            optional: false,
+        });
+
+        let user_data = serde_json::from_str(
+            r#"{
+                "labelCommands": [
+                    { "name": "\\ref", "index": 1, "kind": "definition" },
+                    { "name": "\\figref", "index": 0, "kind": { "reference": "everything" } }
+                ]
+            }"#,
+        )
+        .unwrap();
+        data.merge(user_data);
+
+        assert_eq!(data.label_commands.len(), 2);
+        assert_eq!(data.label_commands[0].index, 0);
+        assert_eq!(data.label_commands[1].name, "\\figref");
+    }
+
+    #[test]
+    fn reject_unknown_fields() {
+        let result = serde_json::from_str::<LanguageData>(r#"{"colours": ["foo"]}"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn builtin_data_is_valid() {
+        assert!(!LANGUAGE_DATA.label_commands.is_empty());
+    }
+}
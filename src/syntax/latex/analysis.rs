@@ -7,6 +7,13 @@ use itertools::{iproduct, Itertools};
 use serde::{Deserialize, Serialize};
 use std::{borrow::Cow, ops::Deref, path::Path};
 
+const ENVIRONMENT_DEFINITION_COMMANDS: &[&str] = &[
+    "\\newenvironment",
+    "\\newenvironment*",
+    "\\renewenvironment",
+    "\\renewenvironment*",
+];
+
 #[derive(Debug, Clone)]
 pub struct SymbolTableParams<'a> {
     pub tree: Tree,
@@ -22,6 +29,7 @@ pub struct SymbolTable {
     pub commands: Vec<AstNodeIndex>,
     pub environments: Vec<Environment>,
     pub is_standalone: bool,
+    pub is_at_letter_file: bool,
     pub includes: Vec<Include>,
     pub imports: Vec<Import>,
     pub components: Vec<String>,
@@ -37,6 +45,10 @@ pub struct SymbolTable {
     pub label_numberings: Vec<LabelNumbering>,
     pub captions: Vec<Caption>,
     pub items: Vec<Item>,
+    pub color_definitions: Vec<ColorDefinition>,
+    pub cref_names: Vec<CrefName>,
+    pub bib_items: Vec<BibItem>,
+    pub list_definitions: Vec<ListDefinition>,
 }
 
 impl SymbolTable {
@@ -74,6 +86,10 @@ impl SymbolTable {
         let mut label_numberings = None;
         let mut captions = None;
         let mut items = None;
+        let mut color_definitions = None;
+        let mut cref_names = None;
+        let mut bib_items = None;
+        let mut list_definitions = None;
 
         rayon::scope(|s| {
             s.spawn(|_| environments = Some(Environment::parse(ctx)));
@@ -91,6 +107,10 @@ impl SymbolTable {
             s.spawn(|_| label_numberings = Some(LabelNumbering::parse(ctx)));
             s.spawn(|_| captions = Some(Caption::parse(ctx)));
             s.spawn(|_| items = Some(Item::parse(ctx)));
+            s.spawn(|_| color_definitions = Some(ColorDefinition::parse(ctx)));
+            s.spawn(|_| cref_names = Some(CrefName::parse(ctx)));
+            s.spawn(|_| bib_items = Some(BibItem::parse(ctx)));
+            s.spawn(|_| list_definitions = Some(ListDefinition::parse(ctx)));
         });
 
         let is_standalone = environments
@@ -99,6 +119,8 @@ impl SymbolTable {
             .iter()
             .any(|env| env.is_root(&tree));
 
+        let is_at_letter_file = super::is_at_letter_file(uri);
+
         let components = includes
             .as_ref()
             .unwrap()
@@ -111,6 +133,7 @@ impl SymbolTable {
             commands,
             environments: environments.unwrap(),
             is_standalone,
+            is_at_letter_file,
             includes: includes.unwrap(),
             imports: imports.unwrap(),
             components,
@@ -126,6 +149,10 @@ impl SymbolTable {
             label_numberings: label_numberings.unwrap(),
             captions: captions.unwrap(),
             items: items.unwrap(),
+            color_definitions: color_definitions.unwrap(),
+            cref_names: cref_names.unwrap(),
+            bib_items: bib_items.unwrap(),
+            list_definitions: list_definitions.unwrap(),
         }
     }
 
@@ -139,6 +166,99 @@ impl SymbolTable {
                 .any(|e| e.range(&self.tree).contains(pos))
     }
 
+    pub fn is_at_letter(&self, pos: Position) -> bool {
+        self.commands
+            .iter()
+            .filter_map(|node| self.tree.as_command(*node))
+            .filter(|cmd| cmd.end() <= pos)
+            .filter_map(|cmd| match cmd.name.text() {
+                "\\makeatletter" => Some((cmd.start(), true)),
+                "\\makeatother" => Some((cmd.start(), false)),
+                _ => None,
+            })
+            .max_by_key(|(start, _)| *start)
+            .map(|(_, at_letter)| at_letter)
+            .unwrap_or(self.is_at_letter_file)
+    }
+
+    pub fn is_inside_definition(&self, pos: Position) -> bool {
+        let tree = &self.tree;
+        let command_bodies = self
+            .command_definitions
+            .iter()
+            .map(|def| def.implementation);
+        let environment_bodies = self
+            .commands
+            .iter()
+            .filter(|node| {
+                let name = tree.as_command(**node).unwrap().name.text();
+                ENVIRONMENT_DEFINITION_COMMANDS.contains(&name)
+            })
+            .flat_map(|node| {
+                (1..=2).filter_map(move |index| tree.extract_group(*node, GroupKind::Group, index))
+            });
+
+        command_bodies
+            .chain(environment_bodies)
+            .any(|body| tree[body].range().contains_exclusive(pos))
+    }
+
+    pub fn is_math_mode(&self, pos: Position) -> bool {
+        let tree = &self.tree;
+        let mut regions: Vec<(Range, bool)> = Vec::new();
+        for eq in &self.equations {
+            let range = Range::new(tree[eq.left].end(), tree[eq.right].start());
+            regions.push((range, true));
+        }
+
+        for inline in &self.inlines {
+            let range = Range::new(tree[inline.left].end(), tree[inline.right].start());
+            regions.push((range, true));
+        }
+
+        for env in self
+            .environments
+            .iter()
+            .filter(|env| env.left.is_math(tree))
+        {
+            let range = Range::new(tree[env.left.parent].end(), tree[env.right.parent].start());
+            regions.push((range, true));
+        }
+
+        for node in &self.commands {
+            let name = tree.as_command(*node).unwrap().name.text();
+            let math_args = LANGUAGE_DATA
+                .math_commands
+                .iter()
+                .filter(|desc| desc.name == name)
+                .map(|desc| (desc.index, true));
+            let text_args = LANGUAGE_DATA
+                .text_commands
+                .iter()
+                .filter(|desc| desc.name == name)
+                .map(|desc| (desc.index, false));
+
+            for (index, is_math) in math_args.chain(text_args) {
+                if let Some(group) = tree
+                    .extract_group(*node, GroupKind::Group, index)
+                    .and_then(|group| tree.as_group(group))
+                {
+                    let end = group
+                        .right
+                        .as_ref()
+                        .map_or_else(|| group.end(), SyntaxNode::start);
+                    regions.push((Range::new(group.left.end(), end), is_math));
+                }
+            }
+        }
+
+        regions
+            .into_iter()
+            .filter(|(range, _)| range.contains(pos))
+            .max_by_key(|(range, _)| range.start)
+            .map_or(false, |(_, is_math)| is_math)
+    }
+
     pub fn is_enum_item(&self, enumeration: Environment, item: Item) -> bool {
         let item_range = self.tree[item.parent].range();
         enumeration.range(&self.tree).contains(item_range.start)
@@ -155,18 +275,87 @@ impl SymbolTable {
                 .any(|env| env.range(&self.tree).contains(item_range.start))
     }
 
-    pub fn find_label_by_range(&self, range: Range) -> Option<&Label> {
+    pub fn label_names(&self) -> impl Iterator<Item = LabelName> + '_ {
+        self.labels.iter().flat_map(move |label| {
+            let range = self[label.parent].range();
+            let label_type = self.label_type(*label);
+            label
+                .names(&self.tree)
+                .into_iter()
+                .map(move |name| LabelName {
+                    label: *label,
+                    name,
+                    range,
+                    label_type,
+                })
+        })
+    }
+
+    fn label_type(&self, label: Label) -> Option<LabelType> {
+        if label.kind != LatexLabelKind::Definition {
+            return None;
+        }
+
+        let pos = self[label.parent].start();
+        self.environments
+            .iter()
+            .filter(|env| env.range(&self.tree).contains(pos))
+            .filter_map(|env| Some((env, self.environment_label_type(*env)?)))
+            .max_by_key(|(env, _)| self[env.left.parent].start())
+            .map(|(_, label_type)| label_type)
+            .or_else(|| {
+                self.sections
+                    .iter()
+                    .find(|section| self[section.parent].start() <= pos)
+                    .map(|_| LabelType::Section)
+            })
+    }
+
+    fn environment_label_type(&self, env: Environment) -> Option<LabelType> {
+        if env.left.is_math(&self.tree) {
+            return Some(LabelType::Equation);
+        }
+
+        if env.left.is_enum(&self.tree) {
+            return Some(LabelType::Item);
+        }
+
+        let name = env.left.name(&self.tree)?.text();
+        match name {
+            "figure" | "figure*" | "subfigure" => Some(LabelType::Figure),
+            "table" | "table*" | "subtable" => Some(LabelType::Table),
+            "listing" | "lstlisting" => Some(LabelType::Listing),
+            "algorithm" => Some(LabelType::Algorithm),
+            _ if self
+                .theorem_definitions
+                .iter()
+                .any(|thm| thm.name(&self.tree).text() == name) =>
+            {
+                Some(LabelType::Theorem)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn find_label_name(&self, pos: Position) -> Option<LabelName> {
+        self.label_names()
+            .find(|label| label.name.range().contains(pos))
+    }
+
+    pub fn label_definitions(&self) -> impl Iterator<Item = &Label> {
         self.labels
             .iter()
             .filter(|label| label.kind == LatexLabelKind::Definition)
+    }
+
+    pub fn find_label_by_range(&self, range: Range) -> Option<&Label> {
+        self.label_definitions()
             .filter(|label| label.names(&self).len() == 1)
             .find(|label| range.contains(self[label.parent].range().start))
     }
 
     pub fn find_label_by_environment(&self, env: Environment) -> Option<&Label> {
-        self.labels
-            .iter()
-            .filter(|label| label.kind == LatexLabelKind::Definition)
+        self.label_definitions()
             .filter(|label| label.names(&self.tree).len() == 1)
             .find(|label| self.is_direct_child(env, self.tree[label.parent].start()))
     }
@@ -325,17 +514,8 @@ impl Include {
             .tree
             .extract_comma_separated_words(parent, GroupKind::Group, desc.index)?;
         for path in paths {
-            let mut targets = Vec::new();
             let base_url = base_url(ctx)?;
-            targets.push(base_url.join(path.text()).ok()?.into());
-
-            if let Some(extensions) = desc.kind.extensions() {
-                for extension in extensions {
-                    let path = format!("{}.{}", path.text(), extension);
-                    targets.push(base_url.join(&path).ok()?.into());
-                }
-            }
-
+            let mut targets = include_targets(&base_url, path.text(), desc.kind)?;
             if let Some(target) = Self::resolve_distro_file(ctx, desc, path.text()) {
                 targets.push(target);
             }
@@ -371,24 +551,53 @@ impl Include {
 }
 
 fn base_url(ctx: SymbolContext) -> Option<Uri> {
-    if let Some(root_directory) = ctx
-        .options
+    include_base_url(ctx.uri, ctx.options, ctx.current_dir)
+}
+
+pub fn include_base_url(uri: &Uri, options: &Options, current_dir: &Path) -> Option<Uri> {
+    if let Some(root_directory) = options
         .latex
         .as_ref()
         .and_then(|opts| opts.root_directory.as_ref())
     {
-        let file_name = ctx.uri.path_segments()?.last()?;
-        let path = ctx.current_dir.join(root_directory).join(file_name);
+        let file_name = uri.path_segments()?.last()?;
+        let path = current_dir.join(root_directory).join(file_name);
         Uri::from_file_path(path).ok()
     } else {
-        Some(ctx.uri.clone())
+        Some(uri.clone())
     }
 }
 
+pub fn include_targets(base_url: &Uri, path: &str, kind: LatexIncludeKind) -> Option<Vec<Uri>> {
+    let mut targets = vec![resolve_path(base_url, path)?];
+    if let Some(extensions) = kind.extensions() {
+        for extension in extensions {
+            let path = format!("{}.{}", path, extension);
+            targets.push(resolve_path(base_url, &path)?);
+        }
+    }
+    Some(targets)
+}
+
+pub fn resolve_path(base_url: &Uri, path: &str) -> Option<Uri> {
+    let url = if base_url.scheme() == "file" {
+        let path = path.replace('\\', "/");
+        if path.chars().nth(1) == Some(':') {
+            base_url.join(&format!("/{}", path))
+        } else {
+            base_url.join(&path)
+        }
+    } else {
+        base_url.join(path)
+    };
+    url.ok().map(Into::into)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Import {
     pub parent: AstNodeIndex,
     pub targets: Vec<Uri>,
+    pub is_relative: bool,
 }
 
 impl Import {
@@ -409,25 +618,52 @@ impl Import {
 
     fn parse_single(ctx: SymbolContext, parent: AstNodeIndex) -> Option<Self> {
         let cmd = ctx.tree.as_command(parent)?;
-        if cmd.name.text() != "\\import" && cmd.name.text() != "\\subimport" {
+        let (base_url, is_relative) = if IMPORT_COMMANDS.contains(&cmd.name.text()) {
+            (base_url(ctx)?, false)
+        } else if SUBIMPORT_COMMANDS.contains(&cmd.name.text()) {
+            (ctx.uri.clone(), true)
+        } else {
             return None;
-        }
+        };
 
         let dir = ctx.tree.extract_word(parent, GroupKind::Group, 0)?;
         let file = ctx.tree.extract_word(parent, GroupKind::Group, 1)?;
+        let (_, targets) = import_targets(&base_url, dir.text(), file.text())?;
+        Some(Self {
+            parent,
+            targets,
+            is_relative,
+        })
+    }
+}
 
-        let mut targets = Vec::new();
-        let base_url = base_url(ctx)?.join(dir.text()).ok()?;
-        targets.push(base_url.join(file.text()).ok()?.into());
-        targets.push(base_url.join(&format!("{}.tex", file.text())).ok()?.into());
-        Some(Self { parent, targets })
+pub fn import_targets(base_url: &Uri, dir: &str, file: &str) -> Option<(Uri, Vec<Uri>)> {
+    let mut dir = dir.to_owned();
+    if !dir.is_empty() && !dir.ends_with('/') {
+        dir.push('/');
     }
+
+    let import_dir = resolve_path(base_url, &dir)?;
+    let targets = vec![
+        import_dir.join(file).ok()?.into(),
+        import_dir.join(&format!("{}.tex", file)).ok()?.into(),
+    ];
+    Some((import_dir, targets))
 }
 
+const IMPORT_COMMANDS: &[&str] = &["\\import", "\\import*", "\\inputfrom", "\\includefrom"];
+
+const SUBIMPORT_COMMANDS: &[&str] = &[
+    "\\subimport",
+    "\\subimport*",
+    "\\subinputfrom",
+    "\\subincludefrom",
+];
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Citation {
-    parent: AstNodeIndex,
-    arg_index: usize,
+    pub parent: AstNodeIndex,
+    pub arg_index: usize,
 }
 
 impl Citation {
@@ -437,27 +673,45 @@ impl Citation {
     }
 
     fn parse(ctx: SymbolContext) -> Vec<Self> {
-        iproduct!(ctx.commands, LANGUAGE_DATA.citation_commands.iter())
-            .filter_map(|(parent, desc)| Self::parse_single(ctx, *parent, desc))
+        let configured_commands = ctx
+            .options
+            .latex
+            .as_ref()
+            .and_then(|opts| opts.completion.as_ref())
+            .map(|opts| opts.citation_commands())
+            .unwrap_or_default()
+            .iter()
+            .map(|cmd| (cmd.name.trim_start_matches('\\'), cmd.index));
+
+        let descs: Vec<_> = LANGUAGE_DATA
+            .citation_commands
+            .iter()
+            .map(|desc| (&desc.name[1..], desc.index))
+            .chain(configured_commands)
+            .collect();
+
+        iproduct!(ctx.commands, descs.iter())
+            .filter_map(|(parent, (name, index))| Self::parse_single(ctx, *parent, name, *index))
             .collect()
     }
 
     fn parse_single(
         ctx: SymbolContext,
         parent: AstNodeIndex,
-        desc: &LatexCitationCommand,
+        name: &str,
+        index: usize,
     ) -> Option<Self> {
         let cmd = ctx.tree.as_command(parent)?;
-        if cmd.name.text() != desc.name {
+        if &cmd.name.text()[1..] != name {
             return None;
         }
 
         ctx.tree
-            .extract_comma_separated_words(parent, GroupKind::Group, desc.index)?;
+            .extract_comma_separated_words(parent, GroupKind::Group, index)?;
 
         Some(Self {
             parent,
-            arg_index: desc.index,
+            arg_index: index,
         })
     }
 }
@@ -764,15 +1018,21 @@ impl Section {
 pub struct Label {
     pub parent: AstNodeIndex,
     pub arg_index: usize,
+    pub group_kind: GroupKind,
     pub kind: LatexLabelKind,
 }
 
 impl Label {
     pub fn names(self, tree: &Tree) -> Vec<&Token> {
-        tree.extract_comma_separated_words(self.parent, GroupKind::Group, self.arg_index)
+        tree.extract_comma_separated_words(self.parent, self.group_kind, self.arg_index)
             .unwrap()
     }
 
+    pub fn is_zref(self, tree: &Tree) -> bool {
+        tree.as_command(self.parent)
+            .map_or(false, |cmd| is_zref_command(cmd.name.text()))
+    }
+
     fn parse(ctx: SymbolContext) -> Vec<Self> {
         iproduct!(ctx.commands, LANGUAGE_DATA.label_commands.iter())
             .filter_map(|(parent, desc)| Self::parse_single(ctx, *parent, desc))
@@ -789,21 +1049,65 @@ impl Label {
             return None;
         }
 
+        let group_kind = if desc.optional {
+            GroupKind::Options
+        } else {
+            GroupKind::Group
+        };
+
         ctx.tree
-            .extract_comma_separated_words(parent, GroupKind::Group, desc.index)?;
+            .extract_comma_separated_words(parent, group_kind, desc.index)?;
 
         Some(Self {
             parent,
             arg_index: desc.index,
+            group_kind,
             kind: desc.kind,
         })
     }
 }
 
+const ZREF_COMMANDS: &[&str] = &["\\zlabel", "\\zref", "\\zpageref", "\\zcref", "\\zcpageref"];
+
+pub fn is_zref_command(name: &str) -> bool {
+    ZREF_COMMANDS.contains(&name)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LabelType {
+    Section,
+    Figure,
+    Table,
+    Listing,
+    Algorithm,
+    Equation,
+    Item,
+    Theorem,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LabelName<'a> {
+    pub label: Label,
+    pub name: &'a Token,
+    pub range: Range,
+    pub label_type: Option<LabelType>,
+}
+
+impl<'a> LabelName<'a> {
+    pub fn text(self) -> &'a str {
+        self.name.text()
+    }
+
+    pub fn kind(self) -> LatexLabelKind {
+        self.label.kind
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LabelNumbering {
     pub parent: AstNodeIndex,
     pub number: String,
+    pub page: Option<String>,
 }
 
 impl LabelNumbering {
@@ -829,9 +1133,22 @@ impl LabelNumbering {
         let arg = ctx.tree.extract_group(parent, GroupKind::Group, 1)?;
         let mut analyzer = FirstText::default();
         analyzer.visit(ctx.tree, arg);
+
+        let page = ctx
+            .tree
+            .children(arg)
+            .filter(|child| ctx.tree.as_group(*child).is_some())
+            .nth(1)
+            .and_then(|child| {
+                let mut analyzer = FirstText::default();
+                analyzer.visit(ctx.tree, child);
+                analyzer.text
+            });
+
         Some(Self {
             parent,
             number: analyzer.text?,
+            page,
         })
     }
 }
@@ -873,7 +1190,7 @@ impl Caption {
 
     fn parse_single(ctx: SymbolContext, parent: AstNodeIndex) -> Option<Self> {
         let cmd = ctx.tree.as_command(parent)?;
-        if cmd.name.text() != "\\caption" {
+        if cmd.name.text() != "\\caption" && cmd.name.text() != "\\subcaption" {
             return None;
         }
 
@@ -911,3 +1228,137 @@ impl Item {
         Some(Self { parent })
     }
 }
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ColorDefinition {
+    pub parent: AstNodeIndex,
+    pub model_index: Option<usize>,
+    pub spec_index: usize,
+}
+
+impl ColorDefinition {
+    pub fn name(self, tree: &Tree) -> &Token {
+        tree.extract_word(self.parent, GroupKind::Group, 0).unwrap()
+    }
+
+    pub fn model(self, tree: &Tree) -> Option<&Token> {
+        tree.extract_word(self.parent, GroupKind::Group, self.model_index?)
+    }
+
+    fn parse(ctx: SymbolContext) -> Vec<Self> {
+        ctx.commands
+            .iter()
+            .filter_map(|parent| Self::parse_single(ctx, *parent))
+            .collect()
+    }
+
+    fn parse_single(ctx: SymbolContext, parent: AstNodeIndex) -> Option<Self> {
+        let cmd = ctx.tree.as_command(parent)?;
+        let (model_index, spec_index) = match cmd.name.text() {
+            "\\definecolor" | "\\providecolor" => (Some(1), 2),
+            "\\colorlet" => (None, 1),
+            _ => return None,
+        };
+
+        ctx.tree.extract_word(parent, GroupKind::Group, 0)?;
+        Some(Self {
+            parent,
+            model_index,
+            spec_index,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CrefName {
+    pub parent: AstNodeIndex,
+    pub capitalized: bool,
+}
+
+impl CrefName {
+    pub fn type_name(self, tree: &Tree) -> &Token {
+        tree.extract_word(self.parent, GroupKind::Group, 0).unwrap()
+    }
+
+    pub fn singular(self, tree: &Tree) -> Option<String> {
+        tree.print_group_content(self.parent, GroupKind::Group, 1)
+    }
+
+    fn parse(ctx: SymbolContext) -> Vec<Self> {
+        ctx.commands
+            .iter()
+            .filter_map(|parent| Self::parse_single(ctx, *parent))
+            .collect()
+    }
+
+    fn parse_single(ctx: SymbolContext, parent: AstNodeIndex) -> Option<Self> {
+        let cmd = ctx.tree.as_command(parent)?;
+        let capitalized = match cmd.name.text() {
+            "\\crefname" => false,
+            "\\Crefname" => true,
+            _ => return None,
+        };
+
+        ctx.tree.extract_word(parent, GroupKind::Group, 0)?;
+        Some(Self {
+            parent,
+            capitalized,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BibItem {
+    pub parent: AstNodeIndex,
+}
+
+impl BibItem {
+    pub fn key(self, tree: &Tree) -> &Token {
+        tree.extract_word(self.parent, GroupKind::Group, 0).unwrap()
+    }
+
+    fn parse(ctx: SymbolContext) -> Vec<Self> {
+        ctx.commands
+            .iter()
+            .filter_map(|parent| Self::parse_single(ctx, *parent))
+            .collect()
+    }
+
+    fn parse_single(ctx: SymbolContext, parent: AstNodeIndex) -> Option<Self> {
+        let cmd = ctx.tree.as_command(parent)?;
+        if cmd.name.text() != "\\bibitem" {
+            return None;
+        }
+
+        ctx.tree.extract_word(parent, GroupKind::Group, 0)?;
+        Some(Self { parent })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ListDefinition {
+    pub parent: AstNodeIndex,
+}
+
+impl ListDefinition {
+    pub fn name(self, tree: &Tree) -> &Token {
+        tree.extract_word(self.parent, GroupKind::Group, 0).unwrap()
+    }
+
+    fn parse(ctx: SymbolContext) -> Vec<Self> {
+        ctx.commands
+            .iter()
+            .filter_map(|parent| Self::parse_single(ctx, *parent))
+            .collect()
+    }
+
+    fn parse_single(ctx: SymbolContext, parent: AstNodeIndex) -> Option<Self> {
+        let cmd = ctx.tree.as_command(parent)?;
+        if cmd.name.text() != "\\newlist" && cmd.name.text() != "\\renewlist" {
+            return None;
+        }
+
+        ctx.tree.extract_word(parent, GroupKind::Group, 0)?;
+        Some(Self { parent })
+    }
+}
@@ -4,12 +4,18 @@ use crate::syntax::text::CharStream;
 #[derive(Debug)]
 pub struct Lexer<'a> {
     stream: CharStream<'a>,
+    at_letter: bool,
 }
 
 impl<'a> Lexer<'a> {
     pub fn new(text: &'a str) -> Self {
+        Self::with_at_letter(text, true)
+    }
+
+    pub fn with_at_letter(text: &'a str, at_letter: bool) -> Self {
         Self {
             stream: CharStream::new(text),
+            at_letter,
         }
     }
 
@@ -31,7 +37,12 @@ impl<'a> Lexer<'a> {
     }
 
     fn command(&mut self) -> Token {
-        let span = self.stream.command();
+        let span = self.stream.control_sequence(self.at_letter);
+        match span.text.as_str() {
+            "\\makeatletter" => self.at_letter = true,
+            "\\makeatother" => self.at_letter = false,
+            _ => (),
+        }
         Token::new(span, TokenKind::Command)
     }
 
@@ -74,6 +85,9 @@ impl<'a> Iterator for Lexer<'a> {
                 Some(',') => {
                     return Some(self.single_char(TokenKind::Comma));
                 }
+                Some('&') => {
+                    return Some(self.single_char(TokenKind::Word));
+                }
                 Some('\\') => {
                     return Some(self.command());
                 }
@@ -102,6 +116,7 @@ fn is_word_char(c: char) -> bool {
         && c != '\\'
         && c != '$'
         && c != ','
+        && c != '&'
 }
 
 #[cfg(test)]
@@ -139,6 +154,26 @@ mod tests {
         assert_eq!(None, lexer.next());
     }
 
+    #[test]
+    fn command_at_other() {
+        let mut lexer = Lexer::with_at_letter("\\foo@bar", false);
+        verify(&mut lexer, 0, 0, "\\foo", TokenKind::Command);
+        verify(&mut lexer, 0, 4, "@bar", TokenKind::Word);
+        assert_eq!(None, lexer.next());
+    }
+
+    #[test]
+    fn command_make_at_letter() {
+        let mut lexer =
+            Lexer::with_at_letter("\\makeatletter\\foo@bar\\makeatother\\foo@bar", false);
+        verify(&mut lexer, 0, 0, "\\makeatletter", TokenKind::Command);
+        verify(&mut lexer, 0, 13, "\\foo@bar", TokenKind::Command);
+        verify(&mut lexer, 0, 21, "\\makeatother", TokenKind::Command);
+        verify(&mut lexer, 0, 33, "\\foo", TokenKind::Command);
+        verify(&mut lexer, 0, 37, "@bar", TokenKind::Word);
+        assert_eq!(None, lexer.next());
+    }
+
     #[test]
     fn escape_sequence() {
         let mut lexer = Lexer::new("\\%\\**");
@@ -167,6 +202,20 @@ mod tests {
         assert_eq!(None, lexer.next());
     }
 
+    #[test]
+    fn alignment_tab() {
+        let mut lexer = Lexer::new("foo&\\ref{bar}&&");
+        verify(&mut lexer, 0, 0, "foo", TokenKind::Word);
+        verify(&mut lexer, 0, 3, "&", TokenKind::Word);
+        verify(&mut lexer, 0, 4, "\\ref", TokenKind::Command);
+        verify(&mut lexer, 0, 8, "{", TokenKind::BeginGroup);
+        verify(&mut lexer, 0, 9, "bar", TokenKind::Word);
+        verify(&mut lexer, 0, 12, "}", TokenKind::EndGroup);
+        verify(&mut lexer, 0, 13, "&", TokenKind::Word);
+        verify(&mut lexer, 0, 14, "&", TokenKind::Word);
+        assert_eq!(None, lexer.next());
+    }
+
     #[test]
     fn line_comment() {
         let mut lexer = Lexer::new(" %foo \nfoo");
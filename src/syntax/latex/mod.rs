@@ -10,7 +10,7 @@ use crate::{
     protocol::{Options, Uri},
     tex::Resolver,
 };
-use std::path::Path;
+use std::{ffi::OsStr, path::Path};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct OpenParams<'a> {
@@ -30,7 +30,7 @@ pub fn open(params: OpenParams) -> SymbolTable {
         current_dir,
     } = params;
 
-    let lexer = Lexer::new(text);
+    let lexer = Lexer::with_at_letter(text, is_at_letter_file(uri));
     let parser = Parser::new(lexer);
     let tree = parser.parse();
 
@@ -44,12 +44,28 @@ pub fn open(params: OpenParams) -> SymbolTable {
     SymbolTable::analyze(params)
 }
 
+fn is_at_letter_file(uri: &Uri) -> bool {
+    Path::new(uri.path())
+        .extension()
+        .and_then(OsStr::to_str)
+        .map(str::to_lowercase)
+        .map_or(false, |ext| match ext.as_str() {
+            "sty" | "cls" | "def" | "lco" | "aux" => true,
+            _ => false,
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        protocol::{Options, Range, RangeExt, Uri},
-        syntax::{generic_ast::AstNodeIndex, text::SyntaxNode},
+        protocol::{
+            CitationCommand, LatexCompletionOptions, LatexOptions, Options, Position, Range,
+            RangeExt, Uri,
+        },
+        syntax::{
+            generic_ast::AstNodeIndex, text::SyntaxNode, LatexLabelKind, LatexLabelReferenceSource,
+        },
         tex::Resolver,
     };
     use indoc::indoc;
@@ -216,9 +232,13 @@ mod tests {
         use super::*;
 
         fn verify(expected_names: Vec<&str>, text: &str) {
+            verify_file(expected_names, "bar.tex", text);
+        }
+
+        fn verify_file(expected_names: Vec<&str>, file: &str, text: &str) {
             let table = open(OpenParams {
                 text,
-                uri: &Uri::parse("http://www.foo.com/bar.tex").unwrap(),
+                uri: &Uri::parse(&format!("http://www.foo.com/{}", file)).unwrap(),
                 resolver: &Resolver::default(),
                 options: &Options::default(),
                 current_dir: &env::current_dir().unwrap(),
@@ -264,7 +284,35 @@ mod tests {
 
         #[test]
         fn at() {
-            verify(vec!["\\foo@bar"], indoc!(r#"\foo@bar"#));
+            verify(
+                vec!["\\makeatletter", "\\foo@bar"],
+                indoc!(r#"\makeatletter\foo@bar"#),
+            );
+        }
+
+        #[test]
+        fn at_other() {
+            verify(vec!["\\foo"], indoc!(r#"\foo@bar"#));
+        }
+
+        #[test]
+        fn at_package() {
+            verify_file(vec!["\\foo@bar"], "foo.sty", indoc!(r#"\foo@bar"#));
+        }
+
+        #[test]
+        fn at_definition_file() {
+            verify_file(vec!["\\foo@bar"], "foo.def", indoc!(r#"\foo@bar"#));
+            verify_file(vec!["\\foo@bar"], "foo.lco", indoc!(r#"\foo@bar"#));
+        }
+
+        #[test]
+        fn at_aux_file() {
+            verify_file(
+                vec!["\\@writefile"],
+                "foo.aux",
+                indoc!(r#"\@writefile{toc}"#),
+            );
         }
 
         #[test]
@@ -431,6 +479,30 @@ mod tests {
             );
         }
 
+        #[test]
+        fn parent_directory_input() {
+            verify(
+                vec![vec![
+                    "http://www.foo.com/dir1/shared/defs",
+                    "http://www.foo.com/dir1/shared/defs.tex",
+                ]],
+                Resolver::default(),
+                indoc!(r#"\input{../shared/defs}"#),
+            );
+        }
+
+        #[test]
+        fn parent_directory_bibliography() {
+            verify(
+                vec![vec![
+                    "http://www.foo.com/dir1/refs/main.bib",
+                    "http://www.foo.com/dir1/refs/main.bib.bib",
+                ]],
+                Resolver::default(),
+                indoc!(r#"\addbibresource{../refs/main.bib}"#),
+            );
+        }
+
         #[test]
         fn distro_file() {
             let mut resolver = Resolver::default();
@@ -492,6 +564,39 @@ mod tests {
         assert_eq!(actual_keys, expected_keys);
     }
 
+    #[test]
+    fn configured_citation() {
+        let options = Options {
+            latex: Some(LatexOptions {
+                completion: Some(LatexCompletionOptions {
+                    citation_commands: Some(vec![CitationCommand {
+                        name: "\\foocite".into(),
+                        index: 1,
+                    }]),
+                    ..LatexCompletionOptions::default()
+                }),
+                ..LatexOptions::default()
+            }),
+            ..Options::default()
+        };
+
+        let table = open(OpenParams {
+            text: r#"\foocite{p. 1}{key1}"#,
+            uri: &Uri::parse("http://www.foo.com/bar.tex").unwrap(),
+            resolver: &Resolver::default(),
+            options: &options,
+            current_dir: &env::current_dir().unwrap(),
+        });
+
+        let actual_keys: Vec<Vec<&str>> = table
+            .citations
+            .iter()
+            .map(|cit| cit.keys(&table.tree).into_iter().map(Token::text).collect())
+            .collect();
+
+        assert_eq!(actual_keys, vec![vec!["key1"]]);
+    }
+
     #[test]
     fn command_definition() {
         let table = open_simple(indoc!(
@@ -560,6 +665,27 @@ mod tests {
         assert_eq!(table.inlines.len(), 1);
     }
 
+    #[test]
+    fn math_mode() {
+        let table = open_simple(r#"$x$ \[y\] \begin{equation}z\end{equation} \ensuremath{w} v"#);
+
+        assert!(table.is_math_mode(Position::new(0, 1)));
+        assert!(!table.is_math_mode(Position::new(0, 3)));
+        assert!(table.is_math_mode(Position::new(0, 7)));
+        assert!(table.is_math_mode(Position::new(0, 26)));
+        assert!(table.is_math_mode(Position::new(0, 54)));
+        assert!(!table.is_math_mode(Position::new(0, 56)));
+    }
+
+    #[test]
+    fn text_mode_inside_math() {
+        let table = open_simple(r#"\[\text{foo $x$} y\]"#);
+
+        assert!(!table.is_math_mode(Position::new(0, 9)));
+        assert!(table.is_math_mode(Position::new(0, 14)));
+        assert!(table.is_math_mode(Position::new(0, 17)));
+    }
+
     #[test]
     fn math_operator() {
         let table = open_simple(indoc!(
@@ -590,6 +716,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn list_definition() {
+        let table = open_simple(indoc!(
+            r#"
+                \newlist{steps}{enumerate}{1}
+            "#
+        ));
+
+        assert_eq!(table.list_definitions.len(), 1);
+        assert_eq!(table.list_definitions[0].name(&table.tree).text(), "steps");
+    }
+
     #[test]
     fn section() {
         let table = open_simple(indoc!(
@@ -632,6 +770,89 @@ mod tests {
         assert_eq!(actual_names, expected_names);
     }
 
+    #[test]
+    fn label_names() {
+        let table = open_simple(indoc!(
+            r#"
+                \label{foo}
+                \ref{bar, baz}
+            "#
+        ));
+
+        let actual_names: Vec<_> = table
+            .label_names()
+            .map(|label| (label.text(), label.kind(), label.range))
+            .collect();
+
+        let ref_range = Range::new_simple(1, 0, 1, 14);
+        let ref_kind = LatexLabelKind::Reference(LatexLabelReferenceSource::Everything);
+        assert_eq!(
+            actual_names,
+            vec![
+                (
+                    "foo",
+                    LatexLabelKind::Definition,
+                    Range::new_simple(0, 0, 0, 11)
+                ),
+                ("bar", ref_kind, ref_range),
+                ("baz", ref_kind, ref_range),
+            ]
+        );
+        assert_eq!(
+            table
+                .find_label_name(Position::new(1, 11))
+                .map(|label| label.text()),
+            Some("baz")
+        );
+    }
+
+    #[test]
+    fn label_inside_caption() {
+        let table = open_simple(r#"\caption{Foo\label{fig:foo}}"#);
+
+        let actual_names: Vec<_> = table
+            .label_definitions()
+            .flat_map(|label| label.names(&table))
+            .map(Token::text)
+            .collect();
+
+        assert_eq!(actual_names, vec!["fig:foo"]);
+    }
+
+    #[test]
+    fn label_types() {
+        let table = open_simple(indoc!(
+            r#"
+                \newtheorem{lemma}{Lemma}
+                \label{none}
+                \section{Foo}\label{sec}
+                \begin{figure}
+                    \begin{equation}\label{eq}\end{equation}
+                    \label{fig}
+                \end{figure}
+                \begin{lemma}\label{lem}\end{lemma}
+                \ref{sec}
+            "#
+        ));
+
+        let actual_types: Vec<_> = table
+            .label_names()
+            .map(|label| (label.text(), label.label_type))
+            .collect();
+
+        assert_eq!(
+            actual_types,
+            vec![
+                ("none", None),
+                ("sec", Some(LabelType::Section)),
+                ("eq", Some(LabelType::Equation)),
+                ("fig", Some(LabelType::Figure)),
+                ("lem", Some(LabelType::Theorem)),
+                ("sec", None),
+            ]
+        );
+    }
+
     #[test]
     fn label_numbering() {
         let table = open_simple(indoc!(
@@ -643,6 +864,20 @@ mod tests {
         assert_eq!(table.label_numberings.len(), 1);
         assert_eq!(table.label_numberings[0].name(&table.tree).text(), "foo");
         assert_eq!(table.label_numberings[0].number, "1");
+        assert_eq!(table.label_numberings[0].page.as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn label_numbering_hyperref() {
+        let table = open_simple(indoc!(
+            r#"
+                \newlabel{sec:foo}{{\relax 2.1}{4}{Foo\relax }{section.2.1}{}}
+            "#
+        ));
+
+        assert_eq!(table.label_numberings.len(), 1);
+        assert_eq!(table.label_numberings[0].number, "2.1");
+        assert_eq!(table.label_numberings[0].page.as_deref(), Some("4"));
     }
 
     #[test]
@@ -683,4 +918,32 @@ mod tests {
         assert_eq!(table.items.len(), 1);
         assert_eq!(table.items[0].name(&table.tree).unwrap(), "foo bar");
     }
+
+    #[test]
+    fn truncated_document() {
+        let text = indoc!(
+            r#"
+                \documentclass{article}
+                \begin{document}
+                \section{Foo}\label{sec:foo}
+                \begin{equation}
+                    x = \frac{1}{2
+                \end{equation}
+                \cite{bar} \ref{sec:foo}]}
+                \end{document}
+            "#
+        );
+
+        let label_end = text.find("\\label{sec:foo}").unwrap() + "\\label{sec:foo}".len();
+        for end in 0..=text.len() {
+            let table = open_simple(&text[..end]);
+            let has_label = table.label_definitions().any(|label| {
+                label
+                    .names(&table)
+                    .iter()
+                    .any(|name| name.text() == "sec:foo")
+            });
+            assert!(has_label || end < label_end);
+        }
+    }
 }
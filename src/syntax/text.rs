@@ -92,16 +92,18 @@ impl<'a> CharStream<'a> {
     }
 
     pub fn seek(&mut self, position: Position) {
-        while self.current_position < position {
-            self.next();
-        }
+        while self.current_position < position && self.next().is_some() {}
     }
 
     pub fn command(&mut self) -> Span {
+        self.control_sequence(true)
+    }
+
+    pub fn control_sequence(&mut self, at_letter: bool) -> Span {
         self.start_span();
         self.next();
         let mut escape = true;
-        while self.satifies(|c| is_command_char(*c)) {
+        while self.satifies(|c| is_command_char(*c, at_letter)) {
             self.next();
             escape = false;
         }
@@ -147,8 +149,8 @@ impl<'a> Iterator for CharStream<'a> {
     }
 }
 
-fn is_command_char(c: char) -> bool {
-    c >= 'a' && c <= 'z' || c >= 'A' && c <= 'Z' || c == '@'
+fn is_command_char(c: char, at_letter: bool) -> bool {
+    c >= 'a' && c <= 'z' || c >= 'A' && c <= 'Z' || (at_letter && c == '@')
 }
 
 #[cfg(test)]
@@ -228,6 +230,17 @@ mod tests {
         assert_eq!(Some('f'), stream.peek());
     }
 
+    #[test]
+    fn seek_past_end() {
+        let mut stream = CharStream::new("abc");
+        stream.seek(Position::new(2, 0));
+        assert_eq!(None, stream.peek());
+        assert_eq!(
+            CharStream::extract("abc", Range::new_simple(0, 1, 5, 0)),
+            "bc"
+        );
+    }
+
     #[test]
     fn command_basic() {
         let mut stream = CharStream::new("\\foo@bar");
@@ -238,6 +251,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn control_sequence_at_other() {
+        let mut stream = CharStream::new("\\foo@bar");
+        let span = stream.control_sequence(false);
+        assert_eq!(
+            Span::new(Range::new_simple(0, 0, 0, 4), "\\foo".to_owned()),
+            span
+        );
+    }
+
     #[test]
     fn command_star() {
         let mut stream = CharStream::new("\\foo*");
@@ -71,6 +71,8 @@ pub struct TestBedBuilder {
     latex_build: Option<LatexBuildOptions>,
     latex_lint: Option<LatexLintOptions>,
     latex_forward_search: Option<LatexForwardSearchOptions>,
+    latex_completion: Option<LatexCompletionOptions>,
+    diagnostics_scope: Option<DiagnosticsScope>,
     bibtex_formatting: Option<BibtexFormattingOptions>,
 }
 
@@ -109,6 +111,16 @@ impl TestBedBuilder {
         self
     }
 
+    pub fn latex_completion(&mut self, options: LatexCompletionOptions) -> &mut Self {
+        self.latex_completion = Some(options);
+        self
+    }
+
+    pub fn diagnostics_scope(&mut self, scope: DiagnosticsScope) -> &mut Self {
+        self.diagnostics_scope = Some(scope);
+        self
+    }
+
     pub fn bibtex_formatting(&mut self, options: BibtexFormattingOptions) -> &mut Self {
         self.bibtex_formatting = Some(options);
         self
@@ -158,11 +170,7 @@ impl TestBedBuilder {
             receiver: rx1,
             client: Arc::clone(&client),
             server: Arc::clone(&server),
-            handler: MessageHandler {
-                client,
-                server,
-                output: tx2.clone(),
-            },
+            handler: MessageHandler::new(server, client, tx2.clone()),
         }
     }
 
@@ -178,6 +186,16 @@ impl TestBedBuilder {
                 build: self.latex_build.clone(),
                 forward_search: self.latex_forward_search.clone(),
                 lint: self.latex_lint.clone(),
+                completion: self.latex_completion.clone(),
+                extensions: None,
+                max_file_size: None,
+                max_file_lines: None,
+                exclude_directories: None,
+                surround_selection: None,
+                feature_timings: None,
+                diagnostics_scope: self.diagnostics_scope,
+                root_file: None,
+                features: None,
             }),
             bibtex: Some(BibtexOptions {
                 formatting: self.bibtex_formatting.clone(),
@@ -191,11 +209,7 @@ impl TestBedBuilder {
             receiver: rx2,
             client: Arc::clone(&test_client),
             server: Arc::clone(&test_server),
-            handler: MessageHandler {
-                client: test_client,
-                server: test_server,
-                output: tx1,
-            },
+            handler: MessageHandler::new(test_server, test_client, tx1),
         }
     }
 }
@@ -485,6 +499,17 @@ impl TestBed {
         self.client.hover(params).await.ok()
     }
 
+    pub async fn diagnostics(&self, relative_path: &str) -> Vec<Diagnostic> {
+        let uri = self.uri(relative_path);
+        let mut receiver = self.server.diagnostics_receiver.lock().await;
+        loop {
+            if let Some(diagnostics) = self.server.diagnostics_by_uri.get(&uri) {
+                return diagnostics.clone();
+            }
+            receiver.next().await;
+        }
+    }
+
     pub async fn detect_root(&self, relative_path: &str) {
         self.client
             .detect_root(self.identifier(relative_path))
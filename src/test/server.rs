@@ -2,7 +2,7 @@ use crate::protocol::*;
 use aovec::Aovec;
 use async_trait::async_trait;
 use chashmap::CHashMap;
-use futures::lock::Mutex;
+use futures::{channel::mpsc, lock::Mutex};
 use jsonrpc::server::{Middleware, Result};
 use jsonrpc_derive::{jsonrpc_method, jsonrpc_server};
 
@@ -11,6 +11,8 @@ pub struct TestLatexLspServer {
     pub show_message_buf: Aovec<ShowMessageParams>,
     pub register_capability_buf: Aovec<RegistrationParams>,
     pub diagnostics_by_uri: CHashMap<Uri, Vec<Diagnostic>>,
+    pub diagnostics_sender: mpsc::UnboundedSender<Uri>,
+    pub diagnostics_receiver: Mutex<mpsc::UnboundedReceiver<Uri>>,
     pub progress_buf: Aovec<ProgressParams>,
     pub work_done_progress_create_buf: Aovec<WorkDoneProgressCreateParams>,
     pub log_message_buf: Aovec<LogMessageParams>,
@@ -20,11 +22,14 @@ pub struct TestLatexLspServer {
 impl TestLatexLspServer {
     pub fn new(options: Options) -> Self {
         let base = 16;
+        let (diagnostics_sender, diagnostics_receiver) = mpsc::unbounded();
         Self {
             options: Mutex::new(options),
             show_message_buf: Aovec::new(base),
             register_capability_buf: Aovec::new(base),
             diagnostics_by_uri: CHashMap::new(),
+            diagnostics_sender,
+            diagnostics_receiver: Mutex::new(diagnostics_receiver),
             progress_buf: Aovec::new(base),
             work_done_progress_create_buf: Aovec::new(base),
             log_message_buf: Aovec::new(base),
@@ -54,9 +59,11 @@ impl TestLatexLspServer {
 
     #[jsonrpc_method("textDocument/publishDiagnostics", kind = "notification")]
     pub async fn publish_diagnostics(&self, params: PublishDiagnosticsParams) {
+        let uri: Uri = params.uri.into();
         let _ = self
             .diagnostics_by_uri
-            .insert(params.uri.into(), params.diagnostics);
+            .insert(uri.clone(), params.diagnostics);
+        let _ = self.diagnostics_sender.unbounded_send(uri);
     }
 
     #[jsonrpc_method("$/progress", kind = "notification")]
@@ -30,11 +30,21 @@ pub enum KpsewhichError {
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct Resolver {
     pub files_by_name: HashMap<String, PathBuf>,
+    pub bibliography_styles: Vec<String>,
 }
 
 impl Resolver {
     pub fn new(files_by_name: HashMap<String, PathBuf>) -> Self {
-        Self { files_by_name }
+        let bibliography_styles = files_by_name
+            .keys()
+            .filter(|file_name| file_name.ends_with(".bst"))
+            .map(|file_name| file_name[0..file_name.len() - 4].to_owned())
+            .collect();
+
+        Self {
+            files_by_name,
+            bibliography_styles,
+        }
     }
 }
 
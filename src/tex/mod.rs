@@ -10,8 +10,9 @@ pub use self::{
 };
 
 use self::{compile::Compiler, miktex::Miktex, tectonic::Tectonic, texlive::Texlive};
+use crate::protocol::Options;
 use async_trait::async_trait;
-use std::{fmt, process::Stdio, sync::Arc};
+use std::{ffi::OsStr, fmt, path::Path, process::Stdio, sync::Arc};
 use tokio::process::Command;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -78,6 +79,26 @@ impl Language {
         }
     }
 
+    pub fn by_path(path: &Path, options: &Options) -> Option<Self> {
+        let extension = path.extension().and_then(OsStr::to_str)?;
+        Self::by_extension(extension).or_else(|| {
+            let is_latex = options
+                .latex
+                .as_ref()
+                .map(|opts| opts.extensions())
+                .unwrap_or_default()
+                .iter()
+                .map(|ext| ext.trim_start_matches('.'))
+                .any(|ext| ext.eq_ignore_ascii_case(extension));
+
+            if is_latex {
+                Some(Language::Latex)
+            } else {
+                None
+            }
+        })
+    }
+
     pub fn by_language_id(language_id: &str) -> Option<Self> {
         match language_id {
             "latex" | "tex" => Some(Language::Latex),
@@ -152,3 +173,41 @@ impl Distribution for UnknownDistribution {
         Arc::clone(&self.resolver)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::LatexOptions;
+
+    #[test]
+    fn by_path_default_extension() {
+        let language = Language::by_path(Path::new("foo.tex"), &Options::default());
+        assert_eq!(language, Some(Language::Latex));
+    }
+
+    #[test]
+    fn by_path_unknown_extension() {
+        let language = Language::by_path(Path::new("foo.dtx"), &Options::default());
+        assert_eq!(language, None);
+    }
+
+    #[test]
+    fn by_path_configured_extension() {
+        let options = Options {
+            latex: Some(LatexOptions {
+                extensions: Some(vec![".dtx".into(), "LTX".into()]),
+                ..LatexOptions::default()
+            }),
+            ..Options::default()
+        };
+        assert_eq!(
+            Language::by_path(Path::new("foo.dtx"), &options),
+            Some(Language::Latex)
+        );
+        assert_eq!(
+            Language::by_path(Path::new("foo.ltx"), &options),
+            Some(Language::Latex)
+        );
+        assert_eq!(Language::by_path(Path::new("foo.ins"), &options), None);
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{
+    protocol::{Location, Position, Range, TodoItem},
+    syntax::{latex, SyntaxNode},
+    workspace::{Document, DocumentContent, ExcludeFilter, Snapshot},
+};
+
+const MARKERS: &[&str] = &["TODO", "FIXME"];
+
+pub fn find_todos(snapshot: &Snapshot, excludes: &ExcludeFilter) -> Vec<TodoItem> {
+    let mut todos = Vec::new();
+    for doc in &snapshot.0 {
+        let is_excluded = doc
+            .uri
+            .to_file_path()
+            .map_or(false, |path| excludes.is_excluded(&path));
+        if is_excluded {
+            continue;
+        }
+
+        if let DocumentContent::Latex(table) = &doc.content {
+            let mut doc_todos = Vec::new();
+            find_comment_todos(doc, &mut doc_todos);
+            find_command_todos(doc, table, &mut doc_todos);
+            doc_todos.sort_by_key(|todo| todo.location.range.start);
+            todos.append(&mut doc_todos);
+        }
+    }
+    todos
+}
+
+fn find_comment_todos(doc: &Document, todos: &mut Vec<TodoItem>) {
+    for (line, text) in doc.text.lines().enumerate() {
+        let comment_start = match find_comment_start(text) {
+            Some(index) => index + 1,
+            None => continue,
+        };
+
+        let comment = &text[comment_start..];
+        if let Some((index, marker)) = MARKERS
+            .iter()
+            .filter_map(|marker| Some((comment.find(marker)?, *marker)))
+            .min()
+        {
+            let start = text[..comment_start + index].chars().count() as u64;
+            let end = text.chars().count() as u64;
+            let range = Range::new(
+                Position::new(line as u64, start),
+                Position::new(line as u64, end),
+            );
+
+            todos.push(TodoItem {
+                location: Location::new(doc.uri.clone().into(), range),
+                marker: marker.into(),
+                text: comment[index + marker.len()..]
+                    .trim_start_matches(':')
+                    .trim()
+                    .into(),
+                color: None,
+                inline: false,
+            });
+        }
+    }
+}
+
+fn find_comment_start(line: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '\\' => escaped = !escaped,
+            '%' if !escaped => return Some(i),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+fn find_command_todos(doc: &Document, table: &latex::SymbolTable, todos: &mut Vec<TodoItem>) {
+    for node in &table.commands {
+        let cmd = table.as_command(*node).unwrap();
+        if cmd.name.text() != "\\todo" {
+            continue;
+        }
+
+        let text = match table.print_group_content(*node, latex::GroupKind::Group, 0) {
+            Some(text) => text,
+            None => continue,
+        };
+
+        let mut color = None;
+        let mut inline = false;
+        let options = table
+            .print_group_content(*node, latex::GroupKind::Options, 0)
+            .unwrap_or_default();
+        for option in options.split(',') {
+            let mut parts = option.splitn(2, '=').map(str::trim);
+            match (parts.next(), parts.next()) {
+                (Some("inline"), None) => inline = true,
+                (Some("noinline"), None) => inline = false,
+                (Some("color"), Some(value)) => color = Some(value.to_owned()),
+                _ => (),
+            }
+        }
+
+        todos.push(TodoItem {
+            location: Location::new(doc.uri.clone().into(), table[*node].range()),
+            marker: cmd.name.text().into(),
+            text,
+            color,
+            inline,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        feature::FeatureTester,
+        protocol::{Options, RangeExt},
+    };
+    use indoc::indoc;
+
+    async fn analyze(files: Vec<(&str, &str)>) -> Vec<TodoItem> {
+        let view = FeatureTester::from_files(files).test_view().await;
+        find_todos(
+            &view.snapshot,
+            &ExcludeFilter::new(None, &Options::default()),
+        )
+    }
+
+    #[tokio::test]
+    async fn comment() {
+        let todos = analyze(vec![(
+            "main.tex",
+            indoc!(
+                r#"
+                    foo % TODO: bar
+                    50\% FIXME not a comment
+                    \\% FIXME baz
+                "#
+            ),
+        )])
+        .await;
+
+        let actual_todos: Vec<_> = todos
+            .iter()
+            .map(|todo| {
+                (
+                    todo.location.range,
+                    todo.marker.as_str(),
+                    todo.text.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            actual_todos,
+            vec![
+                (Range::new_simple(0, 6, 0, 15), "TODO", "bar"),
+                (Range::new_simple(2, 4, 2, 13), "FIXME", "baz"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn command() {
+        let todos = analyze(vec![(
+            "main.tex",
+            r#"\todo[inline, color=red]{Foo}\todo{Bar}"#,
+        )])
+        .await;
+
+        assert_eq!(todos.len(), 2);
+        assert_eq!(todos[0].location.range, Range::new_simple(0, 0, 0, 29));
+        assert_eq!(todos[0].text, "Foo");
+        assert_eq!(todos[0].color.as_deref(), Some("red"));
+        assert!(todos[0].inline);
+        assert_eq!(todos[1].text, "Bar");
+        assert_eq!(todos[1].color, None);
+        assert!(!todos[1].inline);
+    }
+
+    #[tokio::test]
+    async fn multiple_documents() {
+        let todos = analyze(vec![
+            ("foo.tex", r#"\todo{Foo}"#),
+            ("bar.tex", "% FIXME: Bar"),
+        ])
+        .await;
+
+        let actual_uris: Vec<_> = todos
+            .iter()
+            .map(|todo| todo.location.uri.as_str())
+            .collect();
+        assert_eq!(
+            actual_uris,
+            vec![
+                FeatureTester::uri("foo.tex").as_str(),
+                FeatureTester::uri("bar.tex").as_str()
+            ]
+        );
+    }
+}
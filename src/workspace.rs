@@ -1,15 +1,20 @@
 use crate::{
     components::COMPONENT_DATABASE,
-    protocol::{Options, TextDocumentItem, Uri},
+    protocol::{LatexOptions, Options, TextDocumentItem, Uri},
     syntax::{bibtex, latex, LatexIncludeKind},
     tex::{Distribution, Language, Resolver},
 };
 use futures::lock::Mutex;
+use globset::GlobSet;
 use log::{debug, error, warn};
-use petgraph::{graph::Graph, visit::Dfs};
+use once_cell::sync::OnceCell;
+use petgraph::{
+    graph::{Graph, NodeIndex},
+    visit::{Dfs, VisitMap},
+    Undirected,
+};
 use std::{
-    collections::HashMap,
-    ffi::OsStr,
+    collections::{HashMap, HashSet},
     hash::{Hash, Hasher},
     io,
     path::{Path, PathBuf},
@@ -59,6 +64,7 @@ pub struct Document {
     pub text: String,
     pub content: DocumentContent,
     pub modified: SystemTime,
+    line_count: usize,
 }
 
 impl Document {
@@ -66,6 +72,12 @@ impl Document {
         self.uri.scheme() == "file"
     }
 
+    pub fn is_large(&self, options: &Options) -> bool {
+        let default_options = LatexOptions::default();
+        let options = options.latex.as_ref().unwrap_or(&default_options);
+        self.text.len() > options.max_file_size() || self.line_count > options.max_file_lines()
+    }
+
     pub fn open(params: DocumentParams) -> Self {
         let DocumentParams {
             uri,
@@ -93,11 +105,17 @@ impl Document {
             }
         };
 
+        Self::new(uri, text, content)
+    }
+
+    fn new(uri: Uri, text: String, content: DocumentContent) -> Self {
+        let line_count = text.lines().count();
         Self {
             uri,
             text,
             content,
             modified: SystemTime::now(),
+            line_count,
         }
     }
 }
@@ -132,38 +150,90 @@ impl Snapshot {
         self.0.iter().find(|doc| doc.uri == *uri).map(Arc::clone)
     }
 
+    pub fn find_target(&self, targets: &[Uri]) -> Option<Arc<Document>> {
+        targets.iter().find_map(|target| self.find(target))
+    }
+
     pub fn relations(
         &self,
         uri: &Uri,
         options: &Options,
         current_dir: &Path,
     ) -> Vec<Arc<Document>> {
+        let (graph, indices_by_uri) = self.relation_graph(options, current_dir);
+        let mut documents = Vec::new();
+        if self.find(uri).is_some() {
+            let mut dfs = Dfs::new(&graph, indices_by_uri[uri]);
+            while let Some(index) = dfs.next(&graph) {
+                documents.push(Arc::clone(&graph[index]));
+            }
+        }
+        documents
+    }
+
+    pub fn components(&self, options: &Options, current_dir: &Path) -> Vec<Vec<Arc<Document>>> {
+        let (graph, _) = self.relation_graph(options, current_dir);
+        let mut dfs = Dfs::empty(&graph);
+        let mut components = Vec::new();
+        for start in graph.node_indices() {
+            if dfs.discovered.is_visited(&start) {
+                continue;
+            }
+
+            let mut component = Vec::new();
+            dfs.move_to(start);
+            while let Some(index) = dfs.next(&graph) {
+                component.push(Arc::clone(&graph[index]));
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    fn relation_graph(
+        &self,
+        options: &Options,
+        current_dir: &Path,
+    ) -> (
+        Graph<&Arc<Document>, (), Undirected>,
+        HashMap<&Uri, NodeIndex>,
+    ) {
         let mut graph = Graph::new_undirected();
         let mut indices_by_uri = HashMap::new();
         for document in &self.0 {
             indices_by_uri.insert(&document.uri, graph.add_node(document));
         }
 
+        let roots = self.include_roots(options, current_dir);
+        let (import_edges, import_dirs) = self.imported_documents(options, current_dir);
+        for (parent, child) in &import_edges {
+            graph.add_edge(indices_by_uri[&parent.uri], indices_by_uri[&child.uri], ());
+        }
+
         for parent in &self.0 {
             if let DocumentContent::Latex(table) = &parent.content {
-                table
-                    .includes
-                    .iter()
-                    .flat_map(|include| include.all_targets.iter())
-                    .filter_map(|targets| targets.iter().find_map(|target| self.find(target)))
-                    .for_each(|child| {
-                        graph.add_edge(indices_by_uri[&parent.uri], indices_by_uri[&child.uri], ());
-                    });
+                for child in self.included_documents(parent, &roots) {
+                    graph.add_edge(indices_by_uri[&parent.uri], indices_by_uri[&child.uri], ());
+                }
 
-                table
-                    .imports
-                    .iter()
-                    .flat_map(|import| import.targets.iter())
-                    .find_map(|target| self.find(target))
-                    .into_iter()
-                    .for_each(|child| {
-                        graph.add_edge(indices_by_uri[&parent.uri], indices_by_uri[&child.uri], ());
-                    });
+                if let Some(import_dir) = import_dirs.get(&parent.uri) {
+                    table
+                        .includes
+                        .iter()
+                        .flat_map(|include| {
+                            include.paths(&table).into_iter().filter_map(move |path| {
+                                latex::include_targets(import_dir, path.text(), include.kind)
+                            })
+                        })
+                        .filter_map(|targets| self.find_target(&targets))
+                        .for_each(|child| {
+                            graph.add_edge(
+                                indices_by_uri[&parent.uri],
+                                indices_by_uri[&child.uri],
+                                (),
+                            );
+                        });
+                }
 
                 self.resolve_aux_targets(&parent.uri, options, current_dir, "aux")
                     .into_iter()
@@ -176,14 +246,161 @@ impl Snapshot {
             }
         }
 
-        let mut documents = Vec::new();
-        if self.find(uri).is_some() {
-            let mut dfs = Dfs::new(&graph, indices_by_uri[uri]);
-            while let Some(index) = dfs.next(&graph) {
-                documents.push(Arc::clone(&graph[index]));
+        (graph, indices_by_uri)
+    }
+
+    fn imported_documents(
+        &self,
+        options: &Options,
+        current_dir: &Path,
+    ) -> (Vec<(Arc<Document>, Arc<Document>)>, HashMap<Uri, Uri>) {
+        let imported: HashSet<_> = self
+            .0
+            .iter()
+            .filter_map(|doc| doc.content.as_latex())
+            .flat_map(|table| table.imports.iter())
+            .filter_map(|import| self.find_target(&import.targets))
+            .map(|doc| doc.uri.clone())
+            .collect();
+
+        let starts = self
+            .0
+            .iter()
+            .filter(|doc| !imported.contains(&doc.uri))
+            .chain(self.0.iter().filter(|doc| imported.contains(&doc.uri)));
+
+        let mut edges = Vec::new();
+        let mut import_dirs = HashMap::new();
+        let mut visited = HashSet::new();
+        for start in starts {
+            if !visited.insert(start.uri.clone()) {
+                continue;
+            }
+
+            let base_url = match latex::include_base_url(&start.uri, options, current_dir) {
+                Some(base_url) => base_url,
+                None => continue,
+            };
+
+            let mut stack = vec![(Arc::clone(start), start.uri.clone())];
+            while let Some((parent, parent_dir)) = stack.pop() {
+                let table = match &parent.content {
+                    DocumentContent::Latex(table) => table,
+                    DocumentContent::Bibtex(_) => continue,
+                };
+
+                for import in &table.imports {
+                    let import_base = if import.is_relative {
+                        &parent_dir
+                    } else {
+                        &base_url
+                    };
+
+                    let dir = import.dir(&table).text();
+                    let file = import.file(&table).text();
+                    if let Some((import_dir, child)) = latex::import_targets(import_base, dir, file)
+                        .and_then(|(import_dir, targets)| {
+                            Some((import_dir, self.find_target(&targets)?))
+                        })
+                    {
+                        edges.push((Arc::clone(&parent), Arc::clone(&child)));
+                        if visited.insert(child.uri.clone()) {
+                            import_dirs.insert(child.uri.clone(), import_dir.clone());
+                            stack.push((child, import_dir));
+                        }
+                    }
+                }
             }
         }
-        documents
+        (edges, import_dirs)
+    }
+
+    fn include_roots(&self, options: &Options, current_dir: &Path) -> Vec<Uri> {
+        self.0
+            .iter()
+            .filter(|doc| match &doc.content {
+                DocumentContent::Latex(table) => table.is_standalone,
+                DocumentContent::Bibtex(_) => false,
+            })
+            .filter_map(|doc| latex::include_base_url(&doc.uri, options, current_dir))
+            .collect()
+    }
+
+    fn included_documents(&self, parent: &Document, roots: &[Uri]) -> Vec<Arc<Document>> {
+        let mut children = Vec::new();
+        if let DocumentContent::Latex(table) = &parent.content {
+            for include in &table.includes {
+                let paths = include.paths(&table);
+                for (path, targets) in paths.iter().zip(&include.all_targets) {
+                    if let Some(child) = self.find_target(targets).or_else(|| {
+                        self.find_relative_to_roots(roots, &parent.uri, path.text(), include.kind)
+                    }) {
+                        children.push(child);
+                    }
+                }
+            }
+        }
+        children
+    }
+
+    fn includes_transitively(&self, parent: &Arc<Document>, uri: &Uri, roots: &[Uri]) -> bool {
+        let mut visited = HashSet::new();
+        let mut stack = vec![Arc::clone(parent)];
+        while let Some(doc) = stack.pop() {
+            if doc.uri == *uri {
+                return true;
+            }
+
+            if visited.insert(doc.uri.clone()) {
+                stack.extend(self.included_documents(&doc, roots));
+            }
+        }
+        false
+    }
+
+    fn find_parent<P>(
+        &self,
+        uri: &Uri,
+        options: &Options,
+        current_dir: &Path,
+        predicate: P,
+    ) -> Option<Arc<Document>>
+    where
+        P: Fn(&latex::SymbolTable) -> bool,
+    {
+        let candidates: Vec<_> = self
+            .relations(uri, options, current_dir)
+            .into_iter()
+            .filter(|doc| match &doc.content {
+                DocumentContent::Latex(table) => table.is_standalone && predicate(table),
+                DocumentContent::Bibtex(_) => false,
+            })
+            .collect();
+
+        let roots = self.include_roots(options, current_dir);
+        candidates
+            .iter()
+            .find(|doc| self.includes_transitively(doc, uri, &roots))
+            .or_else(|| candidates.first())
+            .map(Arc::clone)
+    }
+
+    fn find_relative_to_roots(
+        &self,
+        roots: &[Uri],
+        parent: &Uri,
+        path: &str,
+        kind: LatexIncludeKind,
+    ) -> Option<Arc<Document>> {
+        roots
+            .iter()
+            .filter(|root| {
+                let root = root.as_str();
+                let root_dir = &root[..root.rfind('/').map_or(0, |index| index + 1)];
+                parent.as_str().starts_with(root_dir)
+            })
+            .filter_map(|root| latex::include_targets(root, path, kind))
+            .find_map(|targets| self.find_target(&targets))
     }
 
     pub fn parent(
@@ -192,14 +409,7 @@ impl Snapshot {
         options: &Options,
         current_dir: &Path,
     ) -> Option<Arc<Document>> {
-        for doc in self.relations(uri, options, current_dir) {
-            if let DocumentContent::Latex(table) = &doc.content {
-                if table.is_standalone {
-                    return Some(doc);
-                }
-            }
-        }
-        None
+        self.find_parent(uri, options, current_dir, |_| true)
     }
 
     pub fn parent_subfile(
@@ -208,16 +418,9 @@ impl Snapshot {
         options: &Options,
         current_dir: &Path,
     ) -> Option<Arc<Document>> {
-        for doc in self.relations(uri, options, current_dir) {
-            if let DocumentContent::Latex(table) = &doc.content {
-                if table.is_standalone
-                    && !table.components.iter().any(|comp| comp == "subfiles.cls")
-                {
-                    return Some(doc);
-                }
-            }
-        }
-        None
+        self.find_parent(uri, options, current_dir, |table| {
+            !table.components.iter().any(|comp| comp == "subfiles.cls")
+        })
     }
 
     pub fn expand(&self, options: &Options, current_dir: &Path) -> Vec<Uri> {
@@ -229,19 +432,14 @@ impl Snapshot {
                     .iter()
                     .filter(|include| Self::should_expand_include(&table, include))
                     .flat_map(|include| include.all_targets.iter())
-                    .filter(|targets| targets.iter().all(|target| self.find(target).is_none()))
+                    .filter(|targets| self.find_target(targets).is_none())
                     .flatten()
                     .for_each(|target| unknown_targets.push(target.clone()));
 
                 table
                     .imports
                     .iter()
-                    .filter(|import| {
-                        import
-                            .targets
-                            .iter()
-                            .all(|target| self.find(target).is_none())
-                    })
+                    .filter(|import| self.find_target(&import.targets).is_none())
                     .flat_map(|import| import.targets.iter())
                     .for_each(|target| unknown_targets.push(target.clone()));
 
@@ -312,6 +510,8 @@ pub enum WorkspaceLoadError {
     UnknownLanguage,
     #[error("invalid file path")]
     InvalidPath,
+    #[error("the file is not encoded in UTF-8")]
+    InvalidEncoding,
     #[error("an I/O error occurred: `{0}`")]
     IO(#[from] io::Error),
 }
@@ -319,7 +519,9 @@ pub enum WorkspaceLoadError {
 pub struct Workspace {
     distro: Arc<dyn Distribution>,
     current_dir: Arc<PathBuf>,
+    root_dir: OnceCell<PathBuf>,
     snapshot: Mutex<Arc<Snapshot>>,
+    excludes: Mutex<Option<Arc<ExcludeFilter>>>,
 }
 
 impl Workspace {
@@ -327,7 +529,25 @@ impl Workspace {
         Self {
             distro,
             current_dir,
+            root_dir: OnceCell::new(),
             snapshot: Mutex::default(),
+            excludes: Mutex::default(),
+        }
+    }
+
+    pub fn set_root_dir(&self, root_dir: PathBuf) {
+        let _ = self.root_dir.set(root_dir);
+    }
+
+    pub async fn excludes(&self, options: &Options) -> Arc<ExcludeFilter> {
+        let mut excludes = self.excludes.lock().await;
+        match &*excludes {
+            Some(filter) if filter.patterns == exclude_patterns(options) => Arc::clone(filter),
+            _ => {
+                let filter = Arc::new(ExcludeFilter::new(self.root_dir.get().cloned(), options));
+                *excludes = Some(Arc::clone(&filter));
+                filter
+            }
         }
     }
 
@@ -337,7 +557,13 @@ impl Workspace {
     }
 
     pub async fn add(&self, document: TextDocumentItem, options: &Options) {
-        let language = match Language::by_language_id(&document.language_id) {
+        let language = match Language::by_language_id(&document.language_id).or_else(|| {
+            document
+                .uri
+                .to_file_path()
+                .ok()
+                .and_then(|path| Language::by_path(&path, options))
+        }) {
             Some(language) => language,
             None => {
                 error!(
@@ -362,11 +588,7 @@ impl Workspace {
     }
 
     pub async fn load(&self, path: &Path, options: &Options) -> Result<(), WorkspaceLoadError> {
-        let language = match path
-            .extension()
-            .and_then(OsStr::to_str)
-            .and_then(Language::by_extension)
-        {
+        let language = match Language::by_path(path, options) {
             Some(language) => language,
             None => {
                 warn!("Could not determine language: {}", path.to_string_lossy());
@@ -382,14 +604,24 @@ impl Workspace {
             }
         };
 
-        let text = match fs::read_to_string(path).await {
-            Ok(text) => text,
+        // Positions and edits are computed on the UTF-8 text,
+        // so files in other encodings are rejected instead of being converted lossily.
+        let bytes = match fs::read(path).await {
+            Ok(bytes) => bytes,
             Err(why) => {
                 warn!("Could not open file: {}", uri);
                 return Err(WorkspaceLoadError::IO(why));
             }
         };
 
+        let text = match String::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(_) => {
+                warn!("Could not open file: {} (not encoded in UTF-8)", uri);
+                return Err(WorkspaceLoadError::InvalidEncoding);
+            }
+        };
+
         debug!("Loading document: {}", uri);
         let mut snapshot = self.snapshot.lock().await;
         *snapshot = self
@@ -409,14 +641,17 @@ impl Workspace {
             }
         };
 
-        let language = match old_document.content {
-            DocumentContent::Latex(_) => Language::Latex,
-            DocumentContent::Bibtex(_) => Language::Bibtex,
+        *snapshot = match &old_document.content {
+            DocumentContent::Latex(_) => {
+                self.add_or_update(&snapshot, uri, text, Language::Latex, options)
+                    .await
+            }
+            DocumentContent::Bibtex(old_tree) => {
+                let tree = bibtex::reparse(old_tree, &old_document.text, &text);
+                let document = Document::new(uri, text, DocumentContent::Bibtex(Box::new(tree)));
+                Self::insert(&snapshot, document)
+            }
         };
-
-        *snapshot = self
-            .add_or_update(&snapshot, uri, text, language, options)
-            .await;
     }
 
     pub async fn reparse(&self, options: &Options) {
@@ -446,8 +681,13 @@ impl Workspace {
             return Ok(());
         }
 
+        let excludes = self.excludes(options).await;
         if let Ok(mut path) = uri.to_file_path() {
             while path.pop() {
+                if excludes.is_excluded(&path) {
+                    continue;
+                }
+
                 let snapshot = self.get().await;
                 if snapshot
                     .parent_subfile(&uri, &options, &self.current_dir)
@@ -460,12 +700,7 @@ impl Workspace {
                 while let Some(entry) = entries.next_entry().await? {
                     if entry.file_type().await?.is_file() {
                         let path = entry.path();
-                        if path
-                            .extension()
-                            .and_then(OsStr::to_str)
-                            .and_then(Language::by_extension)
-                            .is_some()
-                        {
+                        if Language::by_path(&path, options).is_some() {
                             if let Ok(parent_uri) = Uri::from_file_path(&path) {
                                 if snapshot.find(&parent_uri).is_none() {
                                     let _ = self.load(&path, options).await;
@@ -479,7 +714,50 @@ impl Workspace {
         Ok(())
     }
 
+    pub async fn find_files(&self, root: &Path, options: &Options) -> Vec<PathBuf> {
+        let excludes = self.excludes(options).await;
+        let mut files = Vec::new();
+        let mut dirs = vec![root.to_owned()];
+        while let Some(dir) = dirs.pop() {
+            let mut entries = match fs::read_dir(&dir).await {
+                Ok(entries) => entries,
+                Err(why) => {
+                    warn!("Unable to read directory {}: {}", dir.display(), why);
+                    continue;
+                }
+            };
+
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                let path = entry.path();
+                let is_hidden = path
+                    .file_name()
+                    .map_or(false, |name| name.to_string_lossy().starts_with('.'));
+                if is_hidden {
+                    continue;
+                }
+
+                let file_type = match entry.file_type().await {
+                    Ok(file_type) => file_type,
+                    Err(why) => {
+                        warn!("Unable to read file type of {}: {}", path.display(), why);
+                        continue;
+                    }
+                };
+                if file_type.is_dir() {
+                    if !excludes.is_excluded(&path) {
+                        dirs.push(path);
+                    }
+                } else if file_type.is_file() && Language::by_path(&path, options).is_some() {
+                    files.push(path);
+                }
+            }
+        }
+        files.sort();
+        files
+    }
+
     pub async fn detect_children(&self, options: &Options) {
+        let excludes = self.excludes(options).await;
         loop {
             let mut changed = false;
 
@@ -489,6 +767,7 @@ impl Workspace {
                 .into_iter()
                 .filter(|uri| uri.scheme() == "file" && uri.fragment().is_none())
                 .filter_map(|uri| uri.to_file_path().ok())
+                .filter(|path| !excludes.is_excluded(path))
             {
                 if path.exists() {
                     changed |= self.load(&path, &options).await.is_ok();
@@ -545,7 +824,10 @@ impl Workspace {
             options,
             current_dir: &self.current_dir,
         });
+        Self::insert(snapshot, document)
+    }
 
+    fn insert(snapshot: &Snapshot, document: Document) -> Arc<Snapshot> {
         let mut documents: Vec<Arc<Document>> = snapshot
             .0
             .iter()
@@ -558,12 +840,63 @@ impl Workspace {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct ExcludeFilter {
+    root_dir: Option<PathBuf>,
+    patterns: Vec<String>,
+    globs: GlobSet,
+}
+
+impl ExcludeFilter {
+    pub fn new(root_dir: Option<PathBuf>, options: &Options) -> Self {
+        Self {
+            root_dir,
+            patterns: exclude_patterns(options).to_vec(),
+            globs: options
+                .latex
+                .as_ref()
+                .map_or_else(GlobSet::empty, LatexOptions::exclude_directories),
+        }
+    }
+
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        let path = match &self.root_dir {
+            Some(root_dir) => match path.strip_prefix(root_dir) {
+                Ok(path) => path,
+                Err(_) => return false,
+            },
+            None => path,
+        };
+
+        path.ancestors()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .any(|dir| {
+                self.globs.is_match(dir)
+                    || dir
+                        .file_name()
+                        .map_or(false, |name| self.globs.is_match(name))
+            })
+    }
+}
+
+fn exclude_patterns(options: &Options) -> &[String] {
+    options
+        .latex
+        .as_ref()
+        .and_then(|opts| opts.exclude_directories.as_deref())
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::protocol::{LatexBuildOptions, LatexOptions};
+    use crate::{
+        protocol::{LatexBuildOptions, LatexOptions},
+        tex::UnknownDistribution,
+    };
     use itertools::Itertools;
     use std::env;
+    use tempfile::tempdir;
 
     fn create_simple_document(uri: &Uri, language: Language, text: &str) -> Arc<Document> {
         Arc::new(Document::open(DocumentParams {
@@ -828,6 +1161,130 @@ mod tests {
         assert_eq!(actual_uris, vec![uri1, uri2, uri3]);
     }
 
+    #[test]
+    fn relations_multiple_imports() {
+        let uri1 = Uri::parse("http://www.example.com/foo.tex").unwrap();
+        let uri2 = Uri::parse("http://www.example.com/bar/baz.tex").unwrap();
+        let uri3 = Uri::parse("http://www.example.com/qux/quux.tex").unwrap();
+        let mut snapshot = Snapshot::new();
+        snapshot.0 = vec![
+            create_simple_document(
+                &uri1,
+                Language::Latex,
+                r#"\import{bar/}{baz}\import{qux/}{quux}"#,
+            ),
+            create_simple_document(&uri2, Language::Latex, r#""#),
+            create_simple_document(&uri3, Language::Latex, r#""#),
+        ];
+
+        let actual_uris: Vec<_> = snapshot
+            .relations(&uri1, &Options::default(), &env::current_dir().unwrap())
+            .into_iter()
+            .map(|doc| doc.uri.clone())
+            .collect();
+
+        assert_eq!(actual_uris, vec![uri1, uri2, uri3]);
+    }
+
+    #[test]
+    fn relations_nested_import() {
+        let uri1 = Uri::parse("http://www.example.com/foo.tex").unwrap();
+        let uri2 = Uri::parse("http://www.example.com/bar/sub/baz.tex").unwrap();
+        let uri3 = Uri::parse("http://www.example.com/bar/qux.tex").unwrap();
+        let uri4 = Uri::parse("http://www.example.com/bar/quux.tex").unwrap();
+        let uri5 = Uri::parse("http://www.example.com/corge/grault.tex").unwrap();
+        let mut snapshot = Snapshot::new();
+        snapshot.0 = vec![
+            create_simple_document(&uri1, Language::Latex, r#"\import{bar}{sub/baz}"#),
+            create_simple_document(
+                &uri2,
+                Language::Latex,
+                r#"\subimport{}{qux}\input{quux}\import{corge}{grault}"#,
+            ),
+            create_simple_document(&uri3, Language::Latex, r#""#),
+            create_simple_document(&uri4, Language::Latex, r#""#),
+            create_simple_document(&uri5, Language::Latex, r#""#),
+        ];
+
+        let mut actual_uris: Vec<_> = snapshot
+            .relations(&uri1, &Options::default(), &env::current_dir().unwrap())
+            .into_iter()
+            .map(|doc| doc.uri.clone())
+            .collect();
+        actual_uris.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
+        assert_eq!(actual_uris, vec![uri4, uri3, uri2, uri5, uri1]);
+    }
+
+    #[test]
+    fn relations_other_project_root() {
+        let uri1 = Uri::parse("http://www.example.com/foo/main.tex").unwrap();
+        let uri2 = Uri::parse("http://www.example.com/foo/chapters/one.tex").unwrap();
+        let uri3 = Uri::parse("http://www.example.com/bar/main.tex").unwrap();
+        let uri4 = Uri::parse("http://www.example.com/bar/chapters/two.tex").unwrap();
+        let mut snapshot = Snapshot::new();
+        snapshot.0 = vec![
+            create_simple_document(
+                &uri1,
+                Language::Latex,
+                r#"\begin{document}\include{chapters/one}\end{document}"#,
+            ),
+            create_simple_document(&uri2, Language::Latex, r#"\input{chapters/two}"#),
+            create_simple_document(&uri3, Language::Latex, r#"\begin{document}\end{document}"#),
+            create_simple_document(&uri4, Language::Latex, r#""#),
+        ];
+
+        let actual_uris: Vec<_> = snapshot
+            .relations(&uri1, &Options::default(), &env::current_dir().unwrap())
+            .into_iter()
+            .map(|doc| doc.uri.clone())
+            .collect();
+
+        assert_eq!(actual_uris, vec![uri1, uri2]);
+    }
+
+    #[test]
+    fn relations_include_from() {
+        let uri1 = Uri::parse("http://www.example.com/foo.tex").unwrap();
+        let uri2 = Uri::parse("http://www.example.com/bar/baz.tex").unwrap();
+        let uri3 = Uri::parse("http://www.example.com/bar/qux/quux.tex").unwrap();
+        let mut snapshot = Snapshot::new();
+        snapshot.0 = vec![
+            create_simple_document(&uri1, Language::Latex, r#"\includefrom{bar}{baz}"#),
+            create_simple_document(&uri2, Language::Latex, r#"\subinputfrom{qux}{quux}"#),
+            create_simple_document(&uri3, Language::Latex, r#""#),
+        ];
+
+        let actual_uris: Vec<_> = snapshot
+            .relations(&uri1, &Options::default(), &env::current_dir().unwrap())
+            .into_iter()
+            .map(|doc| doc.uri.clone())
+            .collect();
+
+        assert_eq!(actual_uris, vec![uri1, uri2, uri3]);
+    }
+
+    #[test]
+    fn components() {
+        let uri1 = Uri::parse("http://www.example.com/foo.tex").unwrap();
+        let uri2 = Uri::parse("http://www.example.com/bar.tex").unwrap();
+        let uri3 = Uri::parse("http://www.example.com/baz.tex").unwrap();
+        let mut snapshot = Snapshot::new();
+        snapshot.0 = vec![
+            create_simple_document(&uri1, Language::Latex, r#"\include{baz}"#),
+            create_simple_document(&uri2, Language::Latex, r#""#),
+            create_simple_document(&uri3, Language::Latex, r#""#),
+        ];
+
+        let actual_uris: Vec<Vec<_>> = snapshot
+            .components(&Options::default(), &env::current_dir().unwrap())
+            .into_iter()
+            .map(|component| component.into_iter().map(|doc| doc.uri.clone()).collect())
+            .collect();
+
+        assert_eq!(actual_uris, vec![vec![uri1, uri3], vec![uri2]]);
+    }
+
     #[test]
     fn parent() {
         let uri1 = Uri::parse("http://www.example.com/foo.tex").unwrap();
@@ -860,6 +1317,59 @@ mod tests {
         assert_eq!(doc, None);
     }
 
+    #[test]
+    fn parent_including_document() {
+        let uri1 = Uri::parse("http://www.example.com/main.tex").unwrap();
+        let uri2 = Uri::parse("http://www.example.com/chapters/foo.tex").unwrap();
+        let uri3 = Uri::parse("http://www.example.com/chapters/bar.tex").unwrap();
+        let uri4 = Uri::parse("http://www.example.com/chapters/baz.tex").unwrap();
+        let uri5 = Uri::parse("http://www.example.com/other.tex").unwrap();
+        let uri6 = Uri::parse("http://www.example.com/chapters/qux.sty").unwrap();
+        let mut snapshot = Snapshot::new();
+        snapshot.0 = vec![
+            create_simple_document(
+                &uri5,
+                Language::Latex,
+                r#"\usepackage{chapters/qux}\begin{document}\end{document}"#,
+            ),
+            create_simple_document(
+                &uri1,
+                Language::Latex,
+                r#"\begin{document}\include{chapters/foo}\include{chapters/bar}\end{document}"#,
+            ),
+            create_simple_document(&uri2, Language::Latex, r#"\usepackage{qux}"#),
+            create_simple_document(&uri3, Language::Latex, r#"\input{chapters/baz}"#),
+            create_simple_document(&uri4, Language::Latex, r#""#),
+            create_simple_document(&uri6, Language::Latex, r#""#),
+        ];
+
+        for uri in &[&uri2, &uri3, &uri4] {
+            let doc = snapshot
+                .parent(uri, &Options::default(), &env::current_dir().unwrap())
+                .unwrap();
+            assert_eq!(doc.uri, uri1);
+        }
+    }
+
+    #[test]
+    fn parent_standalone_document() {
+        let uri1 = Uri::parse("http://www.example.com/foo.tex").unwrap();
+        let uri2 = Uri::parse("http://www.example.com/bar.tex").unwrap();
+        let mut snapshot = Snapshot::new();
+        snapshot.0 = vec![
+            create_simple_document(
+                &uri1,
+                Language::Latex,
+                r#"\begin{document}\include{bar}\end{document}"#,
+            ),
+            create_simple_document(&uri2, Language::Latex, r#"\begin{document}\end{document}"#),
+        ];
+        let doc = snapshot
+            .parent(&uri2, &Options::default(), &env::current_dir().unwrap())
+            .unwrap();
+        assert_eq!(doc.uri, uri2);
+    }
+
     #[test]
     fn expand_aux_file() {
         let uri = Uri::parse("http://www.example.com/foo.tex").unwrap();
@@ -961,4 +1471,110 @@ mod tests {
             vec!["http://www.example.com/qux/baz/foo-bar/qux.tex"]
         )
     }
+
+    #[tokio::test]
+    async fn load_utf8_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("main.tex");
+        std::fs::write(&path, "\\section{Übersicht}").unwrap();
+        let workspace = Workspace::new(
+            Arc::new(UnknownDistribution::default()),
+            Arc::new(dir.path().to_owned()),
+        );
+
+        workspace.load(&path, &Options::default()).await.unwrap();
+
+        let snapshot = workspace.get().await;
+        let uri = Uri::from_file_path(&path).unwrap();
+        assert_eq!(snapshot.find(&uri).unwrap().text, "\\section{Übersicht}");
+    }
+
+    #[tokio::test]
+    async fn load_non_utf8_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("main.tex");
+        std::fs::write(&path, b"\\section{\xDCbersicht}").unwrap();
+        let workspace = Workspace::new(
+            Arc::new(UnknownDistribution::default()),
+            Arc::new(dir.path().to_owned()),
+        );
+
+        let result = workspace.load(&path, &Options::default()).await;
+
+        assert!(matches!(result, Err(WorkspaceLoadError::InvalidEncoding)));
+        assert!(workspace.get().await.0.is_empty());
+    }
+
+    #[tokio::test]
+    async fn find_files() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("chapters")).unwrap();
+        std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+        std::fs::write(dir.path().join("main.tex"), "").unwrap();
+        std::fs::write(dir.path().join("main.pdf"), "").unwrap();
+        std::fs::write(dir.path().join("chapters/intro.tex"), "").unwrap();
+        std::fs::write(dir.path().join("references.bib"), "").unwrap();
+        std::fs::write(dir.path().join(".git/foo.tex"), "").unwrap();
+        let workspace = Workspace::new(
+            Arc::new(UnknownDistribution::default()),
+            Arc::new(dir.path().to_owned()),
+        );
+
+        let actual_files = workspace.find_files(dir.path(), &Options::default()).await;
+
+        assert_eq!(
+            actual_files,
+            vec![
+                dir.path().join("chapters/intro.tex"),
+                dir.path().join("main.tex"),
+                dir.path().join("references.bib"),
+            ]
+        );
+    }
+
+    #[test]
+    fn exclude_relative_to_root() {
+        let root_dir = env::temp_dir().join("project");
+        let options = Options {
+            latex: Some(LatexOptions {
+                exclude_directories: Some(vec!["chapters/drafts".into()]),
+                ..LatexOptions::default()
+            }),
+            ..Options::default()
+        };
+        let filter = ExcludeFilter::new(Some(root_dir.clone()), &options);
+
+        assert!(filter.is_excluded(&root_dir.join("chapters/drafts/foo.tex")));
+        assert!(!filter.is_excluded(&root_dir.join("chapters/foo.tex")));
+        assert!(!filter.is_excluded(&env::temp_dir().join("chapters/drafts/foo.tex")));
+    }
+
+    #[tokio::test]
+    async fn find_files_excluded() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("build/chapters")).unwrap();
+        std::fs::create_dir_all(dir.path().join("src/texmf")).unwrap();
+        std::fs::write(dir.path().join("main.tex"), "").unwrap();
+        std::fs::write(dir.path().join("build/chapters/intro.tex"), "").unwrap();
+        std::fs::write(dir.path().join("src/texmf/foo.sty"), "").unwrap();
+        std::fs::write(dir.path().join("src/bar.tex"), "").unwrap();
+        let workspace = Workspace::new(
+            Arc::new(UnknownDistribution::default()),
+            Arc::new(dir.path().to_owned()),
+        );
+        let options = Options {
+            latex: Some(LatexOptions {
+                exclude_directories: Some(vec!["build".into(), "texmf".into()]),
+                ..LatexOptions::default()
+            }),
+            ..Options::default()
+        };
+
+        let actual_files = workspace.find_files(dir.path(), &options).await;
+
+        assert_eq!(
+            actual_files,
+            vec![dir.path().join("main.tex"), dir.path().join("src/bar.tex")]
+        );
+    }
 }
@@ -4,8 +4,13 @@ use texlab::protocol::{MarkupContent, MarkupKind};
 use indoc::indoc;
 use itertools::Itertools;
 use texlab::{
-    protocol::{CompletionItem, CompletionTextEdit, Documentation, Range, RangeExt, TextEdit},
-    test::{TestBed, TestBedBuilder, TestLspClient, PULL_CAPABILITIES},
+    protocol::{
+        CitationCommand, ClientCapabilities, CompletionCapability, CompletionItem,
+        CompletionItemCapability, CompletionItemTag, CompletionTextEdit, Documentation,
+        LatexCompletionOptions, Range, RangeExt, TagSupport, TextDocumentClientCapabilities,
+        TextEdit,
+    },
+    test::{TestBed, TestBedBuilder, TestLspClient, PULL_CAPABILITIES, PUSH_CAPABILITIES},
 };
 
 async fn run_item(
@@ -343,6 +348,7 @@ async fn latex_citation_valid() {
     test_bed.shutdown().await;
 
     verify_text_edit(&actual_item, 3, 6, 3, 6, "foo:2019");
+    verify_detail(&actual_item, "Foo Bar, 2019");
     assert_eq!(
         actual_item.documentation.unwrap(),
         Documentation::MarkupContent(MarkupContent {
@@ -421,6 +427,7 @@ async fn latex_color_name() {
     test_bed.shutdown().await;
 
     verify_text_edit(&actual_item, 0, 7, 0, 9, "red");
+    verify_detail(&actual_item, "{rgb}{1,0,0}");
 }
 
 #[tokio::test]
@@ -507,6 +514,55 @@ async fn latex_component_kernel_command() {
     verify_text_edit(&actual_item, 0, 1, 0, 14, "documentclass");
 }
 
+#[tokio::test]
+async fn latex_component_kernel_command_deprecated() {
+    let mut test_bed = TestBedBuilder::new()
+        .file("main.tex", r#"\bf"#)
+        .build()
+        .await;
+    test_bed.spawn();
+    let capabilities = ClientCapabilities {
+        text_document: Some(TextDocumentClientCapabilities {
+            completion: Some(CompletionCapability {
+                completion_item: Some(CompletionItemCapability {
+                    tag_support: Some(TagSupport {
+                        value_set: vec![CompletionItemTag::Deprecated],
+                    }),
+                    ..CompletionItemCapability::default()
+                }),
+                ..CompletionCapability::default()
+            }),
+            ..TextDocumentClientCapabilities::default()
+        }),
+        ..PULL_CAPABILITIES.clone()
+    };
+    test_bed.initialize(capabilities).await;
+    test_bed.open("main.tex").await;
+
+    let actual_item = run_item(&test_bed, "main.tex", 0, 3, "bf").await;
+
+    test_bed.shutdown().await;
+
+    assert_eq!(actual_item.tags, Some(vec![CompletionItemTag::Deprecated]));
+}
+
+#[tokio::test]
+async fn latex_component_kernel_command_deprecated_without_tag_support() {
+    let mut test_bed = TestBedBuilder::new()
+        .file("main.tex", r#"\bf"#)
+        .build()
+        .await;
+    test_bed.spawn();
+    test_bed.initialize(PULL_CAPABILITIES.clone()).await;
+    test_bed.open("main.tex").await;
+
+    let actual_item = run_item(&test_bed, "main.tex", 0, 3, "bf").await;
+
+    test_bed.shutdown().await;
+
+    assert_eq!(actual_item.tags, None);
+}
+
 #[tokio::test]
 async fn latex_component_kernel_command_glyph() {
     let mut test_bed = TestBedBuilder::new()
@@ -539,6 +595,35 @@ async fn latex_component_kernel_command_glyph() {
     verify_text_edit(&actual_item, 7, 1, 7, 7, "varepsilon");
 }
 
+#[tokio::test]
+async fn latex_component_kernel_command_image() {
+    let mut test_bed = TestBedBuilder::new()
+        .file("main.tex", r#"\vareps"#)
+        .build()
+        .await;
+    test_bed.spawn();
+    test_bed.initialize(PULL_CAPABILITIES.clone()).await;
+    test_bed.open("main.tex").await;
+
+    let unresolved_item = test_bed
+        .completion("main.tex", 0, 7)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|item| item.label == "varepsilon")
+        .unwrap();
+    let actual_item = test_bed
+        .client
+        .completion_resolve(unresolved_item.clone())
+        .await
+        .unwrap();
+
+    test_bed.shutdown().await;
+
+    assert_eq!(unresolved_item.documentation, None);
+    assert!(actual_item.documentation.is_some());
+}
+
 #[tokio::test]
 async fn latex_component_kernel_environment() {
     let mut test_bed = TestBedBuilder::new()
@@ -906,6 +991,80 @@ async fn latex_include_relative_bibliography() {
     assert_eq!(actual_items, vec!["foo.bib", "qux"]);
 }
 
+async fn run_file_type(line: u64, character: u64) -> Vec<String> {
+    let mut test_bed = TestBedBuilder::new()
+        .file(
+            "main.tex",
+            indoc!(
+                r#"
+                    \input{}
+                    \includegraphics{}
+                    \includesvg{}
+                "#
+            ),
+        )
+        .file("bar.tex", "")
+        .file("foo.png", "")
+        .file("baz.pdf", "")
+        .file("qux.svg", "")
+        .file("figures/foo.jpg", "")
+        .build()
+        .await;
+    test_bed.spawn();
+    test_bed.initialize(PULL_CAPABILITIES.clone()).await;
+    test_bed.open("main.tex").await;
+
+    let actual_items = run_list(&test_bed, "main.tex", line, character).await;
+
+    test_bed.shutdown().await;
+    actual_items
+}
+
+#[tokio::test]
+async fn latex_include_file_type_input() {
+    let actual_items = run_file_type(0, 7).await;
+    assert_eq!(actual_items, vec!["bar.tex", "figures", "main.tex"]);
+}
+
+#[tokio::test]
+async fn latex_include_file_type_graphics() {
+    let actual_items = run_file_type(1, 17).await;
+    assert_eq!(actual_items, vec!["baz.pdf", "figures", "foo.png"]);
+}
+
+#[tokio::test]
+async fn latex_include_file_type_svg() {
+    let actual_items = run_file_type(2, 12).await;
+    assert_eq!(actual_items, vec!["figures", "qux"]);
+}
+
+#[tokio::test]
+async fn latex_bibliography_style_local() {
+    let mut test_bed = TestBedBuilder::new()
+        .file(
+            "main.tex",
+            indoc!(
+                r#"
+                    \documentclass{article}
+                    \bibliographystyle{}
+                "#
+            ),
+        )
+        .file("foo.bst", "")
+        .file("bar.bib", "")
+        .build()
+        .await;
+    test_bed.spawn();
+    test_bed.initialize(PULL_CAPABILITIES.clone()).await;
+    test_bed.open("main.tex").await;
+
+    let actual_items = run_list(&test_bed, "main.tex", 1, 19).await;
+
+    test_bed.shutdown().await;
+
+    assert_eq!(actual_items, vec!["foo"]);
+}
+
 #[tokio::test]
 async fn latex_include_root_dir() {
     let mut test_bed = TestBedBuilder::new()
@@ -1063,6 +1222,45 @@ async fn latex_label() {
     );
 }
 
+#[tokio::test]
+async fn latex_label_proximity() {
+    let mut test_bed = TestBedBuilder::new()
+        .file(
+            "foo.tex",
+            indoc!(
+                r#"
+                    \include{bar}
+                    \label{foo}
+                    \label{bar}
+                    \ref{}
+                "#
+            ),
+        )
+        .file("bar.tex", r#"\label{baz}"#)
+        .latex_completion(LatexCompletionOptions {
+            sort_labels_by_proximity: Some(true),
+            ..LatexCompletionOptions::default()
+        })
+        .build()
+        .await;
+    test_bed.spawn();
+    test_bed.initialize(PULL_CAPABILITIES.clone()).await;
+    test_bed.open("foo.tex").await;
+    test_bed.open("bar.tex").await;
+
+    let actual_labels: Vec<_> = test_bed
+        .completion("foo.tex", 3, 5)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|item| item.label)
+        .collect();
+
+    test_bed.shutdown().await;
+
+    assert_eq!(actual_labels, vec!["bar", "foo", "baz"]);
+}
+
 #[tokio::test]
 async fn latex_preselect_environment() {
     let mut test_bed = TestBedBuilder::new()
@@ -1225,3 +1423,31 @@ async fn latex_user_environment() {
     verify_detail(&actual_item, "user-defined");
     verify_text_edit(&actual_item, 4, 7, 4, 9, "foo");
 }
+
+#[tokio::test]
+async fn configured_citation_command_after_change() {
+    let mut test_bed = TestBedBuilder::new()
+        .file("main.tex", "\\addbibresource{main.bib}\n\\foocite{}")
+        .file("main.bib", "@article{foo,}")
+        .latex_completion(LatexCompletionOptions {
+            citation_commands: Some(vec![CitationCommand {
+                name: "foocite".into(),
+                index: 0,
+            }]),
+            ..LatexCompletionOptions::default()
+        })
+        .build()
+        .await;
+    test_bed.spawn();
+    test_bed.initialize(PUSH_CAPABILITIES.clone()).await;
+    test_bed.open("main.tex").await;
+
+    let items_before = run_list(&test_bed, "main.tex", 1, 9).await;
+    test_bed.push_options().await;
+    let items_after = run_list(&test_bed, "main.tex", 1, 9).await;
+
+    test_bed.shutdown().await;
+
+    assert!(items_before.is_empty());
+    assert_eq!(items_after, vec!["foo"]);
+}
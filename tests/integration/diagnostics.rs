@@ -0,0 +1,44 @@
+use texlab::{
+    protocol::DiagnosticsScope,
+    test::{TestBed, TestBedBuilder, PULL_CAPABILITIES},
+};
+
+async fn run(scope: DiagnosticsScope) -> TestBed {
+    let mut test_bed = TestBedBuilder::new()
+        .file("main.tex", r#"\include{foo}"#)
+        .file("foo.tex", r#"\usepackage{amsmath}\usepackage{amsmath}"#)
+        .diagnostics_scope(scope)
+        .build()
+        .await;
+    test_bed.spawn();
+    test_bed.initialize(PULL_CAPABILITIES.clone()).await;
+    test_bed.open("main.tex").await;
+    test_bed
+}
+
+#[tokio::test]
+async fn workspace_scope() {
+    let test_bed = run(DiagnosticsScope::Workspace).await;
+
+    let actual_diagnostics = test_bed.diagnostics("foo.tex").await;
+
+    test_bed.shutdown().await;
+    assert!(actual_diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.message == "Package loaded more than once: amsmath"));
+}
+
+#[tokio::test]
+async fn open_scope() {
+    let test_bed = run(DiagnosticsScope::Open).await;
+
+    // Both documents are published by the same pass, so waiting for the open one is enough.
+    test_bed.diagnostics("main.tex").await;
+    let is_published = test_bed
+        .server
+        .diagnostics_by_uri
+        .contains_key(&test_bed.uri("foo.tex"));
+
+    test_bed.shutdown().await;
+    assert!(!is_published);
+}
@@ -1,5 +1,6 @@
 mod completion;
 mod definition;
+mod diagnostics;
 mod document_symbol;
 mod folding;
 mod highlight;
@@ -226,3 +226,35 @@ async fn filter_bibtex() {
     verify_symbol_info(&actual_symbols[0], &test_bed, "bar.bib", "foo", 0, 0, 0, 14);
     verify_symbol_info(&actual_symbols[1], &test_bed, "bar.bib", "bar", 2, 0, 2, 20);
 }
+
+#[tokio::test]
+async fn parent_directory_input() {
+    let mut test_bed = TestBedBuilder::new()
+        .file("main/main.tex", r#"\input{../shared/defs}"#)
+        .file("shared/defs.tex", r#"\section{Shared}"#)
+        .build()
+        .await;
+    test_bed.spawn();
+    test_bed.initialize(PULL_CAPABILITIES.clone()).await;
+    test_bed.open("main/main.tex").await;
+    test_bed.open("main/../shared/defs.tex").await;
+
+    let params = WorkspaceSymbolParams {
+        query: "section".into(),
+        ..WorkspaceSymbolParams::default()
+    };
+    let actual_symbols = test_bed.client.workspace_symbol(params).await.unwrap();
+    test_bed.shutdown().await;
+
+    assert_eq!(actual_symbols.len(), 1);
+    verify_symbol_info(
+        &actual_symbols[0],
+        &test_bed,
+        "shared/defs.tex",
+        "Shared",
+        0,
+        0,
+        0,
+        16,
+    );
+}